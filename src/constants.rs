@@ -1,9 +1,9 @@
 use std::f64::consts::PI;
 
 use crate::types::{Kilometres, Metres, Minutes};
-use wasm_bindgen::prelude::*;
 
 pub const DEG_TO_RAD: f64 = PI / 180.0;
+pub const ARCSEC_TO_RAD: f64 = DEG_TO_RAD / 3600.;
 
 pub const EARTH_FLATTENING: f64 = 1.0 / 298.257223563;
 pub const EARTH_FLATTENING_SQUARED: f64 = EARTH_FLATTENING * EARTH_FLATTENING;
@@ -26,6 +26,8 @@ pub const MINUTES_PER_DAY: Minutes = 24. * 60.;
 
 pub const GM: f64 = 398600.0; // Kilometers^3/seconds^2 .
 pub const SIDEREAL_SOLAR: f64 = 1.0027379093;
+// Radians per SI second, for a day of nominal length
+pub const EARTH_ROTATION_RATE: f64 = 7.292115146706979e-5;
 
 pub const LTLIM: f64 = 14.5;
 
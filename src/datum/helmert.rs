@@ -0,0 +1,112 @@
+use crate::{
+    reference_frames::ecef::ECEF,
+    types::{ArcSeconds, Metres},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RotationConvention {
+    // The two conventions only differ in the sign of the rotations
+    // EPSG method 1033 / ISO 19111 / IERS
+    PositionVector,
+    // EPSG method 1032 / US & Australian practice
+    CoordinateFrame,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HelmertParams {
+    // Seven parameter similarity transform, in the units they are published in
+    pub tx: Metres,
+    pub ty: Metres,
+    pub tz: Metres,
+    pub rx: ArcSeconds,
+    pub ry: ArcSeconds,
+    pub rz: ArcSeconds,
+    // Scale change in parts per million
+    pub scale_ppm: f64,
+    pub convention: RotationConvention,
+}
+
+impl HelmertParams {
+    pub const fn new_translation(tx: Metres, ty: Metres, tz: Metres) -> HelmertParams {
+        // Three parameter geocentric translation (EPSG method 1031)
+        return HelmertParams {
+            tx,
+            ty,
+            tz,
+            rx: 0.0,
+            ry: 0.0,
+            rz: 0.0,
+            scale_ppm: 0.0,
+            convention: RotationConvention::PositionVector,
+        };
+    }
+
+    pub fn to_convention(&self, convention: RotationConvention) -> HelmertParams {
+        // Same transform expressed in the other rotation convention
+        if convention == self.convention {
+            return *self;
+        }
+
+        return HelmertParams {
+            rx: -self.rx,
+            ry: -self.ry,
+            rz: -self.rz,
+            convention,
+            ..*self
+        };
+    }
+
+    pub fn reversed(&self) -> HelmertParams {
+        // The published reverse transform - every parameter negated
+        // Only good to first order (mm level for typical datum shifts); apply_inverse is exact
+        return HelmertParams {
+            tx: -self.tx,
+            ty: -self.ty,
+            tz: -self.tz,
+            rx: -self.rx,
+            ry: -self.ry,
+            rz: -self.rz,
+            scale_ppm: -self.scale_ppm,
+            convention: self.convention,
+        };
+    }
+
+    pub fn apply(&self, ecef: &ECEF) -> ECEF {
+        // X' = T + (1 + s) * R * X with R the small angle position vector rotation
+        let (rx, ry, rz) = self.position_vector_rotations();
+        let scale = 1. + self.scale_ppm * 1e-6;
+
+        let x = self.tx + scale * (ecef.x - rz * ecef.y + ry * ecef.z);
+        let y = self.ty + scale * (rz * ecef.x + ecef.y - rx * ecef.z);
+        let z = self.tz + scale * (-ry * ecef.x + rx * ecef.y + ecef.z);
+
+        return ECEF { x, y, z };
+    }
+
+    pub fn apply_inverse(&self, ecef: &ECEF) -> ECEF {
+        // Exact inverse of apply, so that round trips don't accumulate the second order terms
+        // R = I + W with W skew symmetric, and (I + W)^-1 = (I - W + w w^T) / (1 + |w|^2)
+        let (rx, ry, rz) = self.position_vector_rotations();
+        let scale = 1. + self.scale_ppm * 1e-6;
+
+        let u = (ecef.x - self.tx) / scale;
+        let v = (ecef.y - self.ty) / scale;
+        let w = (ecef.z - self.tz) / scale;
+
+        let w_dot = rx * u + ry * v + rz * w;
+        let norm = 1. + rx * rx + ry * ry + rz * rz;
+
+        let x = (u + rz * v - ry * w + rx * w_dot) / norm;
+        let y = (-rz * u + v + rx * w + ry * w_dot) / norm;
+        let z = (ry * u - rx * v + w + rz * w_dot) / norm;
+
+        return ECEF { x, y, z };
+    }
+
+    fn position_vector_rotations(&self) -> (f64, f64, f64) {
+        let params = self.to_convention(RotationConvention::PositionVector);
+        let arcsec = (1_f64 / 3600.).to_radians();
+
+        return (params.rx * arcsec, params.ry * arcsec, params.rz * arcsec);
+    }
+}
@@ -0,0 +1,167 @@
+pub mod helmert;
+pub mod molodensky;
+
+use crate::{
+    ellipsoid::Ellipsoid,
+    reference_frames::{ecef::ECEF, wgs84::WGS84Coord},
+};
+
+use self::{
+    helmert::{HelmertParams, RotationConvention},
+    molodensky::{abridged_molodensky, molodensky, MolodenskyParams},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Datum {
+    // A geodetic datum and the published parameters that take it to WGS84
+    pub name: &'static str,
+    pub ellipsoid: Ellipsoid,
+    // Seven (or three) parameter Helmert transform, this datum -> WGS84
+    pub to_wgs84: HelmertParams,
+    // Mean geocentric shift for the Molodensky transforms, this datum -> WGS84
+    pub molodensky: MolodenskyParams,
+}
+
+impl Datum {
+    pub const WGS84: Datum = Datum {
+        name: "WGS84",
+        ellipsoid: Ellipsoid::WGS84,
+        to_wgs84: HelmertParams::new_translation(0.0, 0.0, 0.0),
+        molodensky: MolodenskyParams {
+            dx: 0.0,
+            dy: 0.0,
+            dz: 0.0,
+        },
+    };
+
+    // Ordnance Survey's published WGS84 -> OSGB36 transform (position vector), reversed
+    // Good to a few metres over Great Britain; use OSTN15 for anything better
+    pub const OSGB36: Datum = Datum {
+        name: "OSGB36",
+        ellipsoid: Ellipsoid::AIRY_1830,
+        to_wgs84: HelmertParams {
+            tx: 446.448,
+            ty: -125.157,
+            tz: 542.060,
+            rx: 0.1502,
+            ry: 0.2470,
+            rz: 0.8421,
+            scale_ppm: -20.4894,
+            convention: RotationConvention::PositionVector,
+        },
+        molodensky: MolodenskyParams {
+            dx: 375.0,
+            dy: -111.0,
+            dz: 431.0,
+        },
+    };
+
+    // DMA TR 8350.2 mean solution for Western Europe (EPSG:1133)
+    pub const ED50: Datum = Datum {
+        name: "ED50",
+        ellipsoid: Ellipsoid::INTERNATIONAL_1924,
+        to_wgs84: HelmertParams::new_translation(-87.0, -98.0, -121.0),
+        molodensky: MolodenskyParams {
+            dx: -87.0,
+            dy: -98.0,
+            dz: -121.0,
+        },
+    };
+
+    // DMA TR 8350.2 mean solution for CONUS (EPSG:1173)
+    pub const NAD27: Datum = Datum {
+        name: "NAD27",
+        ellipsoid: Ellipsoid::CLARKE_1866,
+        to_wgs84: HelmertParams::new_translation(-8.0, 160.0, 176.0),
+        molodensky: MolodenskyParams {
+            dx: -8.0,
+            dy: 160.0,
+            dz: 176.0,
+        },
+    };
+
+    // DMA TR 8350.2 mean solution for Japan, Okinawa and South Korea (EPSG:1148)
+    pub const TOKYO: Datum = Datum {
+        name: "Tokyo",
+        ellipsoid: Ellipsoid::BESSEL_1841,
+        to_wgs84: HelmertParams::new_translation(-148.0, 507.0, 685.0),
+        molodensky: MolodenskyParams {
+            dx: -148.0,
+            dy: 507.0,
+            dz: 685.0,
+        },
+    };
+
+    pub const ALL: [Datum; 5] = [
+        Datum::WGS84,
+        Datum::OSGB36,
+        Datum::ED50,
+        Datum::NAD27,
+        Datum::TOKYO,
+    ];
+
+    pub fn new_from_name(name: &str) -> Option<Datum> {
+        // Case insensitive lookup in the registry
+        return Datum::ALL
+            .iter()
+            .find(|datum| datum.name.eq_ignore_ascii_case(name))
+            .copied();
+    }
+
+    pub fn ecef_to_wgs84(&self, ecef: &ECEF) -> ECEF {
+        return self.to_wgs84.apply(ecef);
+    }
+
+    pub fn ecef_from_wgs84(&self, ecef: &ECEF) -> ECEF {
+        return self.to_wgs84.apply_inverse(ecef);
+    }
+
+    pub fn to_wgs84(&self, coord: &WGS84Coord) -> WGS84Coord {
+        // Given a latitude, longitude and height on this datum gives the same point on WGS84
+        let local = ECEF::new_from_geodetic(coord, &self.ellipsoid);
+        let wgs84 = self.ecef_to_wgs84(&local);
+
+        return WGS84Coord::new_from_ecef_struct(&wgs84);
+    }
+
+    pub fn from_wgs84(&self, coord: &WGS84Coord) -> WGS84Coord {
+        // Given a WGS84 coordinate gives the latitude, longitude and height on this datum
+        let wgs84 = ECEF::new_from_wgs84(coord);
+        let local = self.ecef_from_wgs84(&wgs84);
+
+        return WGS84Coord::new_from_ecef_on_ellipsoid(local.x, local.y, local.z, &self.ellipsoid);
+    }
+
+    pub fn to_wgs84_molodensky(&self, coord: &WGS84Coord, abridged: bool) -> WGS84Coord {
+        if abridged {
+            return abridged_molodensky(
+                coord,
+                &self.molodensky,
+                &self.ellipsoid,
+                &Ellipsoid::WGS84,
+            );
+        }
+        return molodensky(coord, &self.molodensky, &self.ellipsoid, &Ellipsoid::WGS84);
+    }
+
+    pub fn from_wgs84_molodensky(&self, coord: &WGS84Coord, abridged: bool) -> WGS84Coord {
+        let params = self.molodensky.reversed();
+        if abridged {
+            return abridged_molodensky(coord, &params, &Ellipsoid::WGS84, &self.ellipsoid);
+        }
+        return molodensky(coord, &params, &Ellipsoid::WGS84, &self.ellipsoid);
+    }
+
+    pub fn transform(&self, coord: &WGS84Coord, target: &Datum) -> WGS84Coord {
+        // Moves a coordinate from this datum to any other in the registry, pivoting through WGS84
+        let ecef = self.ecef_to_wgs84(&ECEF::new_from_geodetic(coord, &self.ellipsoid));
+        let local = target.ecef_from_wgs84(&ecef);
+
+        return WGS84Coord::new_from_ecef_on_ellipsoid(
+            local.x,
+            local.y,
+            local.z,
+            &target.ellipsoid,
+        );
+    }
+}
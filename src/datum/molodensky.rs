@@ -0,0 +1,103 @@
+use crate::{ellipsoid::Ellipsoid, reference_frames::wgs84::WGS84Coord, types::Metres};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MolodenskyParams {
+    // Geocentric origin shift from the source datum to the target datum
+    pub dx: Metres,
+    pub dy: Metres,
+    pub dz: Metres,
+}
+
+impl MolodenskyParams {
+    pub fn reversed(&self) -> MolodenskyParams {
+        return MolodenskyParams {
+            dx: -self.dx,
+            dy: -self.dy,
+            dz: -self.dz,
+        };
+    }
+}
+
+pub fn molodensky(
+    coord: &WGS84Coord,
+    params: &MolodenskyParams,
+    from: &Ellipsoid,
+    to: &Ellipsoid,
+) -> WGS84Coord {
+    // Standard Molodensky transform (DMA TR 8350.2 eqns 7-1 to 7-3)
+    // Moves a geodetic coordinate between datums without going through ECEF
+    let lat = coord.get_lat_radians();
+    let lon = coord.get_lon_radians();
+    let h = coord.get_altitude();
+
+    let a = from.get_semi_major();
+    let b = from.get_semi_minor();
+    let f = from.get_flattening();
+    let e2 = from.get_eccentricity_squared();
+    let da = to.get_semi_major() - a;
+    let df = to.get_flattening() - f;
+
+    let (sin_lat, cos_lat) = lat.sin_cos();
+    let (sin_lon, cos_lon) = lon.sin_cos();
+
+    let w = (1. - e2 * sin_lat * sin_lat).sqrt();
+    let rn = a / w;
+    let rm = a * (1. - e2) / w.powf(3.);
+
+    let d_lat = (-params.dx * sin_lat * cos_lon - params.dy * sin_lat * sin_lon
+        + params.dz * cos_lat
+        + da * (rn * e2 * sin_lat * cos_lat) / a
+        + df * (rm * a / b + rn * b / a) * sin_lat * cos_lat)
+        / (rm + h);
+
+    let d_lon = (-params.dx * sin_lon + params.dy * cos_lon) / ((rn + h) * cos_lat);
+
+    let d_h = params.dx * cos_lat * cos_lon + params.dy * cos_lat * sin_lon + params.dz * sin_lat
+        - da * a / rn
+        + df * (b / a) * rn * sin_lat * sin_lat;
+
+    return WGS84Coord::new_from_radians(lat + d_lat, lon + d_lon, h + d_h);
+}
+
+pub fn abridged_molodensky(
+    coord: &WGS84Coord,
+    params: &MolodenskyParams,
+    from: &Ellipsoid,
+    to: &Ellipsoid,
+) -> WGS84Coord {
+    // Abridged Molodensky (DMA TR 8350.2 eqns 7-4 to 7-6)
+    // Drops the height and small ellipsoid terms, good to a few metres
+    let lat = coord.get_lat_radians();
+    let lon = coord.get_lon_radians();
+    let h = coord.get_altitude();
+
+    let a = from.get_semi_major();
+    let f = from.get_flattening();
+    let e2 = from.get_eccentricity_squared();
+    let da = to.get_semi_major() - a;
+    let df = to.get_flattening() - f;
+
+    let (sin_lat, cos_lat) = lat.sin_cos();
+    let (sin_lon, cos_lon) = lon.sin_cos();
+
+    let w = (1. - e2 * sin_lat * sin_lat).sqrt();
+    let rn = a / w;
+    let rm = a * (1. - e2) / w.powf(3.);
+
+    let ellipsoid_term = a * df + f * da;
+
+    let d_lat = (-params.dx * sin_lat * cos_lon - params.dy * sin_lat * sin_lon
+        + params.dz * cos_lat
+        + ellipsoid_term * (2. * lat).sin())
+        / rm;
+
+    let d_lon = (-params.dx * sin_lon + params.dy * cos_lon) / (rn * cos_lat);
+
+    let d_h = params.dx * cos_lat * cos_lon
+        + params.dy * cos_lat * sin_lon
+        + params.dz * sin_lat
+        + ellipsoid_term * sin_lat * sin_lat
+        - da;
+
+    return WGS84Coord::new_from_radians(lat + d_lat, lon + d_lon, h + d_h);
+}
@@ -0,0 +1,85 @@
+use crate::{
+    constants::{EARTH_FLATTENING, EARTH_MAJOR, EARTH_MEAN_RADIUS},
+    types::Metres,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ellipsoid {
+    // Reference ellipsoid defined by its semi-major axis and flattening
+    // Everything else is derived once on construction so the solvers don't recompute it
+    major: Metres,
+    flattening: f64,
+    minor: Metres,
+    eccentricity_squared: f64,
+    second_eccentricity_squared: f64,
+    third_flattening: f64,
+}
+
+impl Ellipsoid {
+    pub const WGS84: Ellipsoid = Ellipsoid::new(EARTH_MAJOR, EARTH_FLATTENING);
+    pub const GRS80: Ellipsoid = Ellipsoid::new(6378137.0, 1.0 / 298.257222101);
+    pub const AIRY_1830: Ellipsoid = Ellipsoid::new(6377563.396, 1.0 / 299.3249646);
+    pub const INTERNATIONAL_1924: Ellipsoid = Ellipsoid::new(6378388.0, 1.0 / 297.0);
+    pub const CLARKE_1866: Ellipsoid = Ellipsoid::new_from_axes(6378206.4, 6356583.8);
+    pub const BESSEL_1841: Ellipsoid = Ellipsoid::new(6377397.155, 1.0 / 299.1528128);
+    pub const SPHERE: Ellipsoid = Ellipsoid::new(EARTH_MEAN_RADIUS, 0.0);
+
+    pub const fn new(major: Metres, flattening: f64) -> Ellipsoid {
+        // Given a semi-major axis in m and a flattening (not inverse flattening) gives an Ellipsoid
+        let eccentricity_squared = flattening * (2. - flattening);
+
+        return Ellipsoid {
+            major,
+            flattening,
+            minor: major * (1. - flattening),
+            eccentricity_squared,
+            second_eccentricity_squared: eccentricity_squared / (1. - eccentricity_squared),
+            third_flattening: flattening / (2. - flattening),
+        };
+    }
+
+    pub const fn new_from_axes(major: Metres, minor: Metres) -> Ellipsoid {
+        // Some older ellipsoids (e.g. Clarke 1866) are defined by both axes rather than a flattening
+        return Ellipsoid::new(major, (major - minor) / major);
+    }
+
+    pub fn get_semi_major(&self) -> Metres {
+        return self.major;
+    }
+
+    pub fn get_semi_minor(&self) -> Metres {
+        return self.minor;
+    }
+
+    pub fn get_flattening(&self) -> f64 {
+        return self.flattening;
+    }
+
+    pub fn get_inverse_flattening(&self) -> f64 {
+        // Infinite for a sphere
+        return 1. / self.flattening;
+    }
+
+    pub fn get_eccentricity_squared(&self) -> f64 {
+        return self.eccentricity_squared;
+    }
+
+    pub fn get_second_eccentricity_squared(&self) -> f64 {
+        return self.second_eccentricity_squared;
+    }
+
+    pub fn get_third_flattening(&self) -> f64 {
+        return self.third_flattening;
+    }
+
+    pub fn prime_vertical_radius(&self, lat: f64) -> Metres {
+        // Radius of curvature in the prime vertical (often written N) at a geodetic latitude in radians
+        return self.major / (1. - self.eccentricity_squared * lat.sin().powf(2.)).sqrt();
+    }
+}
+
+impl Default for Ellipsoid {
+    fn default() -> Self {
+        return Ellipsoid::WGS84;
+    }
+}
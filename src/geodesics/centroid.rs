@@ -0,0 +1,101 @@
+use crate::{
+    ellipsoid::Ellipsoid,
+    geodesics::karney::Geodesic,
+    reference_frames::{ecef::ECEF, wgs84::WGS84Coord},
+    types::Metres,
+};
+
+// Weiszfeld's algorithm converges linearly, quickly for clustered points and slowly when the
+// median sits on one of them
+const MAX_ITERATIONS: usize = 200;
+const MEDIAN_TOLERANCE: Metres = 1e-6;
+
+pub fn centroid(points: &[WGS84Coord]) -> Option<WGS84Coord> {
+    return centroid_on_ellipsoid(points, &Ellipsoid::WGS84);
+}
+
+pub fn centroid_on_ellipsoid(points: &[WGS84Coord], ellipsoid: &Ellipsoid) -> Option<WGS84Coord> {
+    // Averages the points in ECEF and projects the mean back onto the ellipsoid along its normal,
+    // which has no trouble with the antimeridian or the poles. The altitude is the mean altitude
+    // None for no points, or points spread evenly enough around the Earth that the mean is its centre
+    if points.is_empty() {
+        return None;
+    }
+
+    let count = points.len() as f64;
+    let (mut x, mut y, mut z, mut altitude) = (0_f64, 0_f64, 0_f64, 0_f64);
+    for point in points.iter() {
+        let ecef = ECEF::new_from_geodetic(point, ellipsoid);
+        x += ecef.x / count;
+        y += ecef.y / count;
+        z += ecef.z / count;
+        altitude += point.get_altitude() / count;
+    }
+
+    // Round-off in the sums is around 1e-9 of the radius when the points cancel out
+    if x.hypot(y).hypot(z) < 1e-8 * ellipsoid.get_semi_major() {
+        return None;
+    }
+
+    let mut mean = WGS84Coord::new_from_ecef_on_ellipsoid(x, y, z, ellipsoid);
+    mean.set_altitude(altitude);
+    return Some(mean);
+}
+
+pub fn geodesic_median(points: &[WGS84Coord]) -> Option<WGS84Coord> {
+    return geodesic_median_on_ellipsoid(points, &Ellipsoid::WGS84);
+}
+
+pub fn geodesic_median_on_ellipsoid(
+    points: &[WGS84Coord],
+    ellipsoid: &Ellipsoid,
+) -> Option<WGS84Coord> {
+    // The point minimising the sum of the geodesic distances to the points, which unlike the
+    // centroid isn't dragged about by a few outlying fixes. The altitude is the median altitude
+    //
+    // Weiszfeld's algorithm, starting from the centroid. Around the current estimate the points are
+    // placed by their distance and azimuth (an azimuthal equidistant projection) and the estimate
+    // moves to the mean of the points weighted by the reciprocal of their distance. Points
+    // coinciding with the estimate are left out, as they would have infinite weight
+    let geodesic = Geodesic::new(ellipsoid);
+    let start = centroid_on_ellipsoid(points, ellipsoid)?;
+    let (mut lat, mut lon) = (start.get_lat_radians(), start.get_lon_radians());
+
+    for _ in 0..MAX_ITERATIONS {
+        let (mut x, mut y, mut weight) = (0_f64, 0_f64, 0_f64);
+        for point in points.iter() {
+            let inverse =
+                geodesic.inverse(lat, lon, point.get_lat_radians(), point.get_lon_radians());
+            if inverse.distance < MEDIAN_TOLERANCE {
+                continue;
+            }
+            let (sin_azimuth, cos_azimuth) = inverse.azimuth1.sin_cos();
+            x += sin_azimuth;
+            y += cos_azimuth;
+            weight += 1. / inverse.distance;
+        }
+
+        if weight == 0. {
+            break;
+        }
+
+        let step = x.hypot(y) / weight;
+        let next = geodesic.direct(lat, lon, x.atan2(y), step);
+        (lat, lon) = (next.lat, next.lon);
+
+        if step < MEDIAN_TOLERANCE {
+            break;
+        }
+    }
+
+    let mut altitudes: Vec<Metres> = points.iter().map(|point| point.get_altitude()).collect();
+    altitudes.sort_by(|a, b| a.total_cmp(b));
+    let middle = altitudes.len() / 2;
+    let altitude = if altitudes.len() % 2 == 0 {
+        (altitudes[middle - 1] + altitudes[middle]) / 2.
+    } else {
+        altitudes[middle]
+    };
+
+    return Some(WGS84Coord::new_from_radians(lat, lon, altitude));
+}
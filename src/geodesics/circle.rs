@@ -0,0 +1,424 @@
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use crate::{
+    ellipsoid::Ellipsoid,
+    geodesics::{geodesic_line::GeodesicLine, karney, karney::Geodesic},
+    reference_frames::wgs84::WGS84Coord,
+    types::{Metres, Radians},
+    utils::math::ang_normalize,
+};
+
+// Golden section search on the azimuth for the furthest east point, enough to pin the azimuth to
+// about 1e-8 rad, which puts the longitude within round-off of the maximum
+const GOLDEN_SECTION_ITERATIONS: usize = 60;
+
+// Widens the bounding box to cover round-off in the direct solutions, about 6 mm
+const BOX_MARGIN: Radians = 1e-9;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub min_lat: Radians,
+    pub max_lat: Radians,
+    // min_lon > max_lon when the box crosses the antimeridian
+    pub min_lon: Radians,
+    pub max_lon: Radians,
+}
+
+impl BoundingBox {
+    pub fn crosses_antimeridian(&self) -> bool {
+        return self.min_lon > self.max_lon;
+    }
+
+    pub fn contains(&self, lat: Radians, lon: Radians) -> bool {
+        if lat < self.min_lat || lat > self.max_lat {
+            return false;
+        }
+        let lon = ang_normalize(lon);
+        if self.crosses_antimeridian() {
+            return lon >= self.min_lon || lon <= self.max_lon;
+        }
+        return lon >= self.min_lon && lon <= self.max_lon;
+    }
+}
+
+pub fn bounding_box(centre: &WGS84Coord, radius: Metres) -> BoundingBox {
+    return bounding_box_on_ellipsoid(centre, radius, &Ellipsoid::WGS84);
+}
+
+pub fn bounding_box_on_ellipsoid(
+    centre: &WGS84Coord,
+    radius: Metres,
+    ellipsoid: &Ellipsoid,
+) -> BoundingBox {
+    // Latitude and longitude limits containing every point within a geodesic distance of the centre
+    // A circle around a pole takes in every longitude, and its latitude limit is the pole itself
+    let (lat, lon) = (centre.get_lat_radians(), centre.get_lon_radians());
+
+    // The meridian is the shortest way to any parallel, so the latitude limits are due north and south
+    let to_north_pole = karney::inverse_on_ellipsoid(lat, lon, FRAC_PI_2, lon, ellipsoid).distance;
+    let to_south_pole = karney::inverse_on_ellipsoid(lat, lon, -FRAC_PI_2, lon, ellipsoid).distance;
+
+    let max_lat = if radius >= to_north_pole {
+        FRAC_PI_2
+    } else {
+        let north = karney::location_and_bearing_on_ellipsoid(lat, lon, 0., radius, ellipsoid);
+        (north.lat + BOX_MARGIN).min(FRAC_PI_2)
+    };
+    let min_lat = if radius >= to_south_pole {
+        -FRAC_PI_2
+    } else {
+        let south = karney::location_and_bearing_on_ellipsoid(lat, lon, PI, radius, ellipsoid);
+        (south.lat - BOX_MARGIN).max(-FRAC_PI_2)
+    };
+
+    if radius >= to_north_pole || radius >= to_south_pole {
+        return BoundingBox {
+            min_lat,
+            max_lat,
+            min_lon: -PI,
+            max_lon: PI,
+        };
+    }
+
+    // The circle is symmetric about the meridian, so only the furthest east point is needed
+    let east_of_centre = |azimuth: Radians| {
+        let point = karney::location_and_bearing_on_ellipsoid(lat, lon, azimuth, radius, ellipsoid);
+        return ang_normalize(point.lon - lon);
+    };
+
+    let ratio = (5_f64.sqrt() - 1.) / 2.;
+    let (mut low, mut high) = (0., PI);
+    let mut left = high - ratio * (high - low);
+    let mut right = low + ratio * (high - low);
+    let (mut left_value, mut right_value) = (east_of_centre(left), east_of_centre(right));
+    for _ in 0..GOLDEN_SECTION_ITERATIONS {
+        if left_value < right_value {
+            low = left;
+            left = right;
+            left_value = right_value;
+            right = low + ratio * (high - low);
+            right_value = east_of_centre(right);
+        } else {
+            high = right;
+            right = left;
+            right_value = left_value;
+            left = high - ratio * (high - low);
+            left_value = east_of_centre(left);
+        }
+    }
+    let half_width = left_value.max(right_value) + BOX_MARGIN;
+
+    return BoundingBox {
+        min_lat,
+        max_lat,
+        min_lon: ang_normalize(lon - half_width),
+        max_lon: ang_normalize(lon + half_width),
+    };
+}
+
+pub fn circle(centre: &WGS84Coord, radius: Metres, num_vertices: usize) -> Vec<WGS84Coord> {
+    return circle_on_ellipsoid(centre, radius, num_vertices, &Ellipsoid::WGS84);
+}
+
+pub fn circle_on_ellipsoid(
+    centre: &WGS84Coord,
+    radius: Metres,
+    num_vertices: usize,
+    ellipsoid: &Ellipsoid,
+) -> Vec<WGS84Coord> {
+    // Vertices at equal steps of azimuth around the centre, starting due north and running
+    // counter-clockwise as polygon::area_and_perimeter expects. The ring is not closed
+    return ellipse_on_ellipsoid(centre, radius, radius, 0., num_vertices, ellipsoid);
+}
+
+pub fn ellipse(
+    centre: &WGS84Coord,
+    semi_major: Metres,
+    semi_minor: Metres,
+    orientation: Radians,
+    num_vertices: usize,
+) -> Vec<WGS84Coord> {
+    return ellipse_on_ellipsoid(
+        centre,
+        semi_major,
+        semi_minor,
+        orientation,
+        num_vertices,
+        &Ellipsoid::WGS84,
+    );
+}
+
+pub fn ellipse_on_ellipsoid(
+    centre: &WGS84Coord,
+    semi_major: Metres,
+    semi_minor: Metres,
+    orientation: Radians,
+    num_vertices: usize,
+    ellipsoid: &Ellipsoid,
+) -> Vec<WGS84Coord> {
+    // As circle, with the geodesic distance in each direction given by the polar equation of an
+    // ellipse whose major axis lies along the orientation (an azimuth), e.g. an error ellipse
+    let (lat, lon) = (centre.get_lat_radians(), centre.get_lon_radians());
+    let mut vertices = Vec::with_capacity(num_vertices);
+
+    for i in 0..num_vertices {
+        let azimuth = -2. * PI * i as f64 / num_vertices as f64;
+        let (sin_theta, cos_theta) = (azimuth - orientation).sin_cos();
+        let distance =
+            semi_major * semi_minor / (semi_minor * cos_theta).hypot(semi_major * sin_theta);
+
+        let point =
+            karney::location_and_bearing_on_ellipsoid(lat, lon, azimuth, distance, ellipsoid);
+        vertices.push(WGS84Coord::new_from_radians(point.lat, point.lon, 0.));
+    }
+
+    return vertices;
+}
+
+pub fn buffer(polyline: &[WGS84Coord], distance: Metres, num_vertices: usize) -> Vec<WGS84Coord> {
+    return buffer_on_ellipsoid(polyline, distance, num_vertices, &Ellipsoid::WGS84);
+}
+
+pub fn buffer_on_ellipsoid(
+    polyline: &[WGS84Coord],
+    distance: Metres,
+    num_vertices: usize,
+    ellipsoid: &Ellipsoid,
+) -> Vec<WGS84Coord> {
+    // Outline of the points within a distance of a polyline of geodesic segments, counter-clockwise
+    // and not closed. num_vertices is the resolution of a full circle, used for the round caps and
+    // the outside of each bend, and segments are split so that no piece covers a bigger angle
+    // The inside of each bend is the point where the two offset edges meet. Bends too sharp for
+    // them to meet on the segments fall back to the polyline vertex, so the ring never overshoots
+    let geodesic = Geodesic::new(ellipsoid);
+    let num_vertices = num_vertices.max(3);
+
+    let mut points: Vec<&WGS84Coord> = Vec::with_capacity(polyline.len());
+    for point in polyline.iter() {
+        let repeated = points.last().is_some_and(|last| {
+            last.get_lat_radians() == point.get_lat_radians()
+                && last.get_lon_radians() == point.get_lon_radians()
+        });
+        if !repeated {
+            points.push(point);
+        }
+    }
+
+    match points.len() {
+        0 => return Vec::new(),
+        1 => return circle_on_ellipsoid(points[0], distance, num_vertices, ellipsoid),
+        _ => (),
+    }
+
+    let segments: Vec<Segment> = points
+        .windows(2)
+        .map(|pair| Segment::new(&geodesic, pair[0], pair[1]))
+        .collect();
+
+    // Pieces along a segment cover the same angle at the Earth's centre as a step around a cap
+    let step = 2. * PI * ellipsoid.get_semi_major() / num_vertices as f64;
+    let step_angle = 2. * PI / num_vertices as f64;
+    let mut ring = Vec::new();
+
+    // Start cap, from the left of the first segment round the back to its right
+    let first = &segments[0];
+    arc(
+        &mut ring,
+        &first.line,
+        0.,
+        first.start_azimuth - FRAC_PI_2,
+        -PI,
+        distance,
+        step_angle,
+        ellipsoid,
+    );
+
+    // Right hand side, forwards
+    for (i, segment) in segments.iter().enumerate() {
+        if i > 0 {
+            join(
+                &mut ring,
+                &segments[i - 1],
+                segment,
+                distance,
+                step_angle,
+                true,
+                ellipsoid,
+            );
+        }
+        segment.offsets(&mut ring, distance, step, FRAC_PI_2, false, ellipsoid);
+    }
+
+    // End cap
+    let last = &segments[segments.len() - 1];
+    arc(
+        &mut ring,
+        &last.line,
+        last.length,
+        last.end_azimuth + FRAC_PI_2,
+        -PI,
+        distance,
+        step_angle,
+        ellipsoid,
+    );
+
+    // Left hand side, backwards
+    for (i, segment) in segments.iter().enumerate().rev() {
+        segment.offsets(&mut ring, distance, step, -FRAC_PI_2, true, ellipsoid);
+        if i > 0 {
+            join(
+                &mut ring,
+                &segments[i - 1],
+                segment,
+                distance,
+                step_angle,
+                false,
+                ellipsoid,
+            );
+        }
+    }
+
+    return ring;
+}
+
+struct Segment {
+    line: GeodesicLine,
+    length: Metres,
+    start_azimuth: Radians,
+    end_azimuth: Radians,
+}
+
+impl Segment {
+    fn new(geodesic: &Geodesic, start: &WGS84Coord, end: &WGS84Coord) -> Segment {
+        let line = GeodesicLine::new_from_endpoints(
+            geodesic,
+            start.get_lat_radians(),
+            start.get_lon_radians(),
+            end.get_lat_radians(),
+            end.get_lon_radians(),
+        );
+        let length = line.get_length().unwrap_or(0.);
+        let end_azimuth = line.position(length).azimuth;
+
+        return Segment {
+            start_azimuth: line.get_azimuth1(),
+            line,
+            length,
+            end_azimuth,
+        };
+    }
+
+    fn offsets(
+        &self,
+        ring: &mut Vec<WGS84Coord>,
+        distance: Metres,
+        step: Metres,
+        side: Radians,
+        backwards: bool,
+        ellipsoid: &Ellipsoid,
+    ) {
+        // Points offset to one side of the segment, excluding the ends which belong to the joins and caps
+        let pieces = (self.length / step).ceil().max(1.) as usize;
+        let mut indices: Vec<usize> = (1..pieces).collect();
+        if backwards {
+            indices.reverse();
+        }
+        for k in indices {
+            let along = self.line.position(self.length * k as f64 / pieces as f64);
+            ring.push(offset(
+                along.lat,
+                along.lon,
+                along.azimuth + side,
+                distance,
+                ellipsoid,
+            ));
+        }
+    }
+}
+
+fn join(
+    ring: &mut Vec<WGS84Coord>,
+    incoming: &Segment,
+    outgoing: &Segment,
+    distance: Metres,
+    step_angle: Radians,
+    right: bool,
+    ellipsoid: &Ellipsoid,
+) {
+    // The bend at the vertex between two segments, positive turns are to the right
+    let turn = ang_normalize(outgoing.start_azimuth - incoming.end_azimuth);
+    let vertex = outgoing.line.position(0.);
+    let inside = if right { turn >= 0. } else { turn <= 0. };
+
+    if inside {
+        // Where the offset edges meet, on the bisector of the bend
+        let half_turn = turn / 2.;
+        if distance * half_turn.abs().tan() > incoming.length.min(outgoing.length) {
+            ring.push(WGS84Coord::new_from_radians(vertex.lat, vertex.lon, 0.));
+            return;
+        }
+        let side = if right { FRAC_PI_2 } else { -FRAC_PI_2 };
+        let azimuth = incoming.end_azimuth + side + half_turn;
+        ring.push(offset(
+            vertex.lat,
+            vertex.lon,
+            azimuth,
+            distance / half_turn.cos(),
+            ellipsoid,
+        ));
+    } else if right {
+        arc(
+            ring,
+            &outgoing.line,
+            0.,
+            incoming.end_azimuth + FRAC_PI_2,
+            turn,
+            distance,
+            step_angle,
+            ellipsoid,
+        );
+    } else {
+        arc(
+            ring,
+            &outgoing.line,
+            0.,
+            outgoing.start_azimuth - FRAC_PI_2,
+            -turn,
+            distance,
+            step_angle,
+            ellipsoid,
+        );
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn arc(
+    ring: &mut Vec<WGS84Coord>,
+    line: &GeodesicLine,
+    along: Metres,
+    start_azimuth: Radians,
+    sweep: Radians,
+    distance: Metres,
+    step_angle: Radians,
+    ellipsoid: &Ellipsoid,
+) {
+    // Points on the circle around a point of a line, from an azimuth through a sweep (negative
+    // is counter-clockwise) including both ends
+    let centre = line.position(along);
+    let steps = (sweep.abs() / step_angle).ceil().max(1.) as usize;
+    for k in 0..=steps {
+        let azimuth = start_azimuth + sweep * k as f64 / steps as f64;
+        ring.push(offset(centre.lat, centre.lon, azimuth, distance, ellipsoid));
+    }
+}
+
+fn offset(
+    lat: Radians,
+    lon: Radians,
+    azimuth: Radians,
+    distance: Metres,
+    ellipsoid: &Ellipsoid,
+) -> WGS84Coord {
+    let point = karney::location_and_bearing_on_ellipsoid(lat, lon, azimuth, distance, ellipsoid);
+    return WGS84Coord::new_from_radians(point.lat, point.lon, 0.);
+}
@@ -0,0 +1,193 @@
+use crate::{
+    constants::EARTH_MEAN_RADIUS,
+    ellipsoid::Ellipsoid,
+    geodesics::{geodesic_line::GeodesicLine, haversine, karney::Geodesic},
+    projections::gnomonic::Gnomonic,
+    reference_frames::wgs84::WGS84Coord,
+    types::{Metres, Radians},
+    utils::math::ang_normalize,
+};
+
+// The intercept converges quadratically, a handful of iterations is enough from the spherical guess
+const MAX_ITERATIONS: usize = 20;
+const INTERCEPT_TOLERANCE: Metres = 1e-9;
+
+#[derive(Debug, Clone, Copy)]
+pub struct TrackDistances {
+    // Perpendicular distance from the route, positive to the right of the direction of travel
+    pub cross_track: Metres,
+    // Distance from the start of the route to the foot of the perpendicular, negative behind the start
+    pub along_track: Metres,
+}
+
+pub fn spherical_cross_track(
+    lat1: Radians,
+    lon1: Radians,
+    lat2: Radians,
+    lon2: Radians,
+    lat: Radians,
+    lon: Radians,
+) -> TrackDistances {
+    // Given a great circle route from point 1 to point 2 gives the cross and along track
+    // distances of a point, on a sphere of the mean radius
+    let route = haversine::distance_and_bearing(lat1, lon1, lat2, lon2);
+    let to_point = haversine::distance_and_bearing(lat1, lon1, lat, lon);
+
+    let (sin_angle, cos_angle) = (to_point.distance / EARTH_MEAN_RADIUS).sin_cos();
+    let (sin_bearing, cos_bearing) = (to_point.bearing - route.bearing).sin_cos();
+
+    // Napier's rules on the right angled triangle formed with the foot of the perpendicular
+    let cross_track = (sin_angle * sin_bearing).asin();
+    let along_track = (sin_angle * cos_bearing).atan2(cos_angle);
+
+    return TrackDistances {
+        cross_track: EARTH_MEAN_RADIUS * cross_track,
+        along_track: EARTH_MEAN_RADIUS * along_track,
+    };
+}
+
+pub fn spherical_closest_point_on_segment(
+    lat1: Radians,
+    lon1: Radians,
+    lat2: Radians,
+    lon2: Radians,
+    lat: Radians,
+    lon: Radians,
+) -> WGS84Coord {
+    // The closest point to (lat, lon) on the great circle segment between points 1 and 2,
+    // at zero altitude. Beyond either end of the segment this is the end itself
+    let route = haversine::distance_and_bearing(lat1, lon1, lat2, lon2);
+    let track = spherical_cross_track(lat1, lon1, lat2, lon2, lat, lon);
+
+    if track.along_track <= 0. {
+        return WGS84Coord::new_from_radians(lat1, lon1, 0.);
+    }
+    if track.along_track >= route.distance {
+        return WGS84Coord::new_from_radians(lat2, lon2, 0.);
+    }
+
+    let foot = haversine::location_and_bearing(lat1, lon1, route.bearing, track.along_track);
+    return WGS84Coord::new_from_radians(foot.lat, ang_normalize(foot.lon), 0.);
+}
+
+pub fn cross_track(
+    lat1: Radians,
+    lon1: Radians,
+    lat2: Radians,
+    lon2: Radians,
+    lat: Radians,
+    lon: Radians,
+) -> TrackDistances {
+    return cross_track_on_ellipsoid(lat1, lon1, lat2, lon2, lat, lon, &Ellipsoid::WGS84);
+}
+
+pub fn cross_track_on_ellipsoid(
+    lat1: Radians,
+    lon1: Radians,
+    lat2: Radians,
+    lon2: Radians,
+    lat: Radians,
+    lon: Radians,
+    ellipsoid: &Ellipsoid,
+) -> TrackDistances {
+    // As spherical_cross_track for the geodesic from point 1 to point 2, with the foot of the
+    // perpendicular converged to a nanometre. The point must be within about 10000 km of the route
+    let geodesic = Geodesic::new(ellipsoid);
+    let (line, along_track) = intercept(&geodesic, lat1, lon1, lat2, lon2, lat, lon);
+
+    let foot = line.position(along_track);
+    let to_point = geodesic.inverse(foot.lat, foot.lon, lat, lon);
+    let cross_track = if (to_point.azimuth1 - foot.azimuth).sin() < 0. {
+        -to_point.distance
+    } else {
+        to_point.distance
+    };
+
+    return TrackDistances {
+        cross_track,
+        along_track,
+    };
+}
+
+pub fn closest_point_on_segment(
+    lat1: Radians,
+    lon1: Radians,
+    lat2: Radians,
+    lon2: Radians,
+    lat: Radians,
+    lon: Radians,
+) -> WGS84Coord {
+    return closest_point_on_segment_on_ellipsoid(
+        lat1,
+        lon1,
+        lat2,
+        lon2,
+        lat,
+        lon,
+        &Ellipsoid::WGS84,
+    );
+}
+
+pub fn closest_point_on_segment_on_ellipsoid(
+    lat1: Radians,
+    lon1: Radians,
+    lat2: Radians,
+    lon2: Radians,
+    lat: Radians,
+    lon: Radians,
+    ellipsoid: &Ellipsoid,
+) -> WGS84Coord {
+    // As spherical_closest_point_on_segment for the geodesic between points 1 and 2
+    let geodesic = Geodesic::new(ellipsoid);
+    let (line, along_track) = intercept(&geodesic, lat1, lon1, lat2, lon2, lat, lon);
+
+    if along_track <= 0. {
+        return WGS84Coord::new_from_radians(lat1, lon1, 0.);
+    }
+    if along_track >= line.get_length().unwrap_or(0.) {
+        return WGS84Coord::new_from_radians(lat2, lon2, 0.);
+    }
+
+    let foot = line.position(along_track);
+    return WGS84Coord::new_from_radians(foot.lat, foot.lon, 0.);
+}
+
+fn intercept(
+    geodesic: &Geodesic,
+    lat1: Radians,
+    lon1: Radians,
+    lat2: Radians,
+    lon2: Radians,
+    lat: Radians,
+    lon: Radians,
+) -> (GeodesicLine, Metres) {
+    // Karney's (2011) intercept method, giving the route and the along track distance of the foot
+    // of the perpendicular. In a gnomonic projection centred on the current guess the route is a
+    // straight line through the origin, and the projection is azimuthal, so the point's component
+    // along the route is zero exactly when the geodesic to it is perpendicular. Stepping by that
+    // component (mapped back to a distance as on a sphere) converges from the spherical guess
+    let ellipsoid = geodesic.get_ellipsoid();
+    let gnomonic = Gnomonic::new(&ellipsoid);
+    let major = ellipsoid.get_semi_major();
+    let line = GeodesicLine::new_from_endpoints(geodesic, lat1, lon1, lat2, lon2);
+
+    let guess = spherical_cross_track(lat1, lon1, lat2, lon2, lat, lon);
+    let mut along_track = guess.along_track * major / EARTH_MEAN_RADIUS;
+
+    for _ in 0..MAX_ITERATIONS {
+        let foot = line.position(along_track);
+        let projected = gnomonic.forward(foot.lat, foot.lon, lat, lon);
+        let (sin_azimuth, cos_azimuth) = foot.azimuth.sin_cos();
+        let along = projected.x * sin_azimuth + projected.y * cos_azimuth;
+
+        let step = major * (along / major).atan();
+        along_track += step;
+
+        // NaN means the point is over the horizon, there's no use carrying on
+        if step.abs() < INTERCEPT_TOLERANCE || step.is_nan() {
+            break;
+        }
+    }
+
+    return (line, along_track);
+}
@@ -0,0 +1,393 @@
+use crate::{
+    geodesics::karney::{self, Geodesic, GEODESIC_ORDER},
+    types::{Metres, Radians, SquareMetres},
+    utils::math::{ang_normalize_degrees, ang_round, atan2d, norm, sin_cos_series, sincosd},
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct GeodesicPosition {
+    // A point along a geodesic line, with the same quantities as karney::GeodesicInverse
+    pub lat: Radians,
+    pub lon: Radians,
+    pub azimuth: Radians,       // forward azimuth at this point
+    pub distance: Metres,       // s12 from the start of the line
+    pub arc_length: Radians,    // a12
+    pub reduced_length: Metres, // m12
+    pub geodesic_scale12: f64,  // M12
+    pub geodesic_scale21: f64,  // M21
+    pub area: SquareMetres,     // S12
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct GeodesicLine {
+    // A geodesic fixed by its starting point and azimuth. Everything which depends only on the
+    // line is worked out once here, so each position along it is a handful of series evaluations
+    lat1: Radians,
+    lon1: Radians,
+    azimuth1: Radians,
+    length: Option<Metres>,
+
+    minor: Metres,
+    flattening: f64,
+    one_minus_flattening: f64,
+    authalic_radius_squared: f64,
+
+    salp1: f64,
+    calp1: f64,
+    salp0: f64,
+    calp0: f64,
+    ssig1: f64,
+    csig1: f64,
+    somg1: f64,
+    comg1: f64,
+    stau1: f64,
+    ctau1: f64,
+    dn1: f64,
+    k2: f64,
+
+    a1m1: f64,
+    a2m1: f64,
+    a3c: f64,
+    a4: f64,
+    b11: f64,
+    b21: f64,
+    b31: f64,
+    b41: f64,
+    c1a: [f64; GEODESIC_ORDER + 1],
+    c1pa: [f64; GEODESIC_ORDER + 1],
+    c2a: [f64; GEODESIC_ORDER + 1],
+    c3a: [f64; GEODESIC_ORDER],
+    c4a: [f64; GEODESIC_ORDER],
+}
+
+impl GeodesicLine {
+    pub fn new(
+        geodesic: &Geodesic,
+        lat1: Radians,
+        lon1: Radians,
+        azimuth1: Radians,
+    ) -> GeodesicLine {
+        let azi1 = ang_normalize_degrees(azimuth1.to_degrees());
+        let (salp1, calp1) = sincosd(ang_round(azi1));
+
+        return GeodesicLine::new_from_sincos(
+            geodesic,
+            lat1,
+            lon1,
+            azi1.to_radians(),
+            salp1,
+            calp1,
+        );
+    }
+
+    pub fn new_from_endpoints(
+        geodesic: &Geodesic,
+        lat1: Radians,
+        lon1: Radians,
+        lat2: Radians,
+        lon2: Radians,
+    ) -> GeodesicLine {
+        // The line through two points, starting at the first and with its length set to the distance
+        // between them. The azimuth is taken from the inverse solution as a sine/cosine pair so that
+        // lines leaving a pole or running along a meridian keep their exact direction
+        let solution = geodesic.gen_inverse(
+            lat1.to_degrees(),
+            lon1.to_degrees(),
+            lat2.to_degrees(),
+            lon2.to_degrees(),
+        );
+        let azi1 = atan2d(solution.salp1, solution.calp1);
+
+        let mut line = GeodesicLine::new_from_sincos(
+            geodesic,
+            lat1,
+            lon1,
+            azi1.to_radians(),
+            solution.salp1,
+            solution.calp1,
+        );
+        line.length = Some(solution.s12);
+
+        return line;
+    }
+
+    fn new_from_sincos(
+        geodesic: &Geodesic,
+        lat1: Radians,
+        lon1: Radians,
+        azimuth1: Radians,
+        salp1: f64,
+        calp1: f64,
+    ) -> GeodesicLine {
+        let tiny = f64::MIN_POSITIVE.sqrt();
+        let lat1_degrees = if lat1.to_degrees().abs() > 90. {
+            f64::NAN
+        } else {
+            lat1.to_degrees()
+        };
+
+        // Reduced latitude of the start
+        let (sbet1, cbet1) = sincosd(ang_round(lat1_degrees));
+        let (sbet1, cbet1) = norm(geodesic.one_minus_flattening * sbet1, cbet1);
+        let cbet1 = cbet1.max(tiny);
+        let dn1 = (1. + geodesic.second_eccentricity_squared * sbet1.powi(2)).sqrt();
+
+        // Azimuth at the equator crossing
+        let salp0 = salp1 * cbet1;
+        let calp0 = calp1.hypot(salp1 * sbet1);
+
+        // Arc length and longitude on the auxiliary sphere from that equator crossing
+        // sig1 is 0 at the node of an equatorial line, which has no equator crossing of its own
+        let somg1 = salp0 * sbet1;
+        let csig1 = if sbet1 != 0. || calp1 != 0. {
+            cbet1 * calp1
+        } else {
+            1.
+        };
+        let comg1 = csig1;
+        let (ssig1, csig1) = norm(sbet1, csig1);
+
+        let k2 = calp0.powi(2) * geodesic.second_eccentricity_squared;
+        let eps = k2 / (2. * (1. + (1. + k2).sqrt()) + k2);
+
+        let a1m1 = karney::a1m1f(eps);
+        let mut c1a = [0_f64; GEODESIC_ORDER + 1];
+        karney::c1f(eps, &mut c1a);
+        let b11 = sin_cos_series(true, ssig1, csig1, &c1a);
+        let (sb11, cb11) = (b11.sin(), b11.cos());
+        // tau1 = sig1 + B11
+        let stau1 = ssig1 * cb11 + csig1 * sb11;
+        let ctau1 = csig1 * cb11 - ssig1 * sb11;
+
+        let mut c1pa = [0_f64; GEODESIC_ORDER + 1];
+        karney::c1pf(eps, &mut c1pa);
+
+        let a2m1 = karney::a2m1f(eps);
+        let mut c2a = [0_f64; GEODESIC_ORDER + 1];
+        karney::c2f(eps, &mut c2a);
+        let b21 = sin_cos_series(true, ssig1, csig1, &c2a);
+
+        let mut c3a = [0_f64; GEODESIC_ORDER];
+        geodesic.c3f(eps, &mut c3a);
+        let a3c = -geodesic.flattening * salp0 * geodesic.a3f(eps);
+        let b31 = sin_cos_series(true, ssig1, csig1, &c3a);
+
+        let mut c4a = [0_f64; GEODESIC_ORDER];
+        geodesic.c4f(eps, &mut c4a);
+        let a4 = geodesic.major.powi(2) * calp0 * salp0 * geodesic.eccentricity_squared;
+        let b41 = sin_cos_series(false, ssig1, csig1, &c4a);
+
+        return GeodesicLine {
+            lat1,
+            lon1,
+            azimuth1,
+            length: None,
+            minor: geodesic.minor,
+            flattening: geodesic.flattening,
+            one_minus_flattening: geodesic.one_minus_flattening,
+            authalic_radius_squared: geodesic.authalic_radius_squared,
+            salp1,
+            calp1,
+            salp0,
+            calp0,
+            ssig1,
+            csig1,
+            somg1,
+            comg1,
+            stau1,
+            ctau1,
+            dn1,
+            k2,
+            a1m1,
+            a2m1,
+            a3c,
+            a4,
+            b11,
+            b21,
+            b31,
+            b41,
+            c1a,
+            c1pa,
+            c2a,
+            c3a,
+            c4a,
+        };
+    }
+
+    pub fn get_lat1(&self) -> Radians {
+        return self.lat1;
+    }
+
+    pub fn get_lon1(&self) -> Radians {
+        return self.lon1;
+    }
+
+    pub fn get_azimuth1(&self) -> Radians {
+        return self.azimuth1;
+    }
+
+    pub fn get_length(&self) -> Option<Metres> {
+        // Only known for lines built from two endpoints
+        return self.length;
+    }
+
+    pub fn position(&self, distance: Metres) -> GeodesicPosition {
+        // Point at a distance along the line, negative distances go backwards from the start
+        return self.gen_position(false, distance, false);
+    }
+
+    pub fn arc_position(&self, arc_length: Radians) -> GeodesicPosition {
+        // Point at an arc length along the line on the auxiliary sphere
+        return self.gen_position(true, arc_length, false);
+    }
+
+    pub(crate) fn position_unrolled(&self, distance: Metres) -> GeodesicPosition {
+        // As position, but with the longitude left unreduced so that lon - lon1 counts
+        // the number of times the line has wrapped around the earth
+        return self.gen_position(false, distance, true);
+    }
+
+    pub fn equally_spaced(&self, distance: Metres, n: usize) -> Vec<GeodesicPosition> {
+        // n points from the start of the line to distance inclusive, for densifying a route
+        if n < 2 {
+            return (0..n).map(|_| self.position(0.)).collect();
+        }
+
+        let step = distance / (n - 1) as f64;
+        return (0..n).map(|i| self.position(step * i as f64)).collect();
+    }
+
+    fn gen_position(&self, arcmode: bool, s12_a12: f64, unroll: bool) -> GeodesicPosition {
+        let tiny = f64::MIN_POSITIVE.sqrt();
+
+        let mut b12 = 0_f64;
+        let (mut sig12, mut ssig12, mut csig12);
+
+        if arcmode {
+            sig12 = s12_a12;
+            ssig12 = sig12.sin();
+            csig12 = sig12.cos();
+        } else {
+            // Invert the distance series: tau12 = s12 / (b A1), then sig12 from the C'1 series
+            let tau12 = s12_a12 / (self.minor * (1. + self.a1m1));
+            let (s, c) = (tau12.sin(), tau12.cos());
+            b12 = -sin_cos_series(
+                true,
+                self.stau1 * c + self.ctau1 * s,
+                self.ctau1 * c - self.stau1 * s,
+                &self.c1pa,
+            );
+            sig12 = tau12 - (b12 - self.b11);
+            ssig12 = sig12.sin();
+            csig12 = sig12.cos();
+
+            if self.flattening.abs() > 0.01 {
+                // The reverted series is only accurate for small flattenings, so take one
+                // Newton step on the forward series to correct it
+                let ssig2 = self.ssig1 * csig12 + self.csig1 * ssig12;
+                let csig2 = self.csig1 * csig12 - self.ssig1 * ssig12;
+                b12 = sin_cos_series(true, ssig2, csig2, &self.c1a);
+                let serr = (1. + self.a1m1) * (sig12 + (b12 - self.b11)) - s12_a12 / self.minor;
+                sig12 -= serr / (1. + self.k2 * ssig2.powi(2)).sqrt();
+                ssig12 = sig12.sin();
+                csig12 = sig12.cos();
+            }
+        }
+
+        // sig2 = sig1 + sig12
+        let ssig2 = self.ssig1 * csig12 + self.csig1 * ssig12;
+        let mut csig2 = self.csig1 * csig12 - self.ssig1 * ssig12;
+        let dn2 = (1. + self.k2 * ssig2.powi(2)).sqrt();
+
+        if arcmode || self.flattening.abs() > 0.01 {
+            b12 = sin_cos_series(true, ssig2, csig2, &self.c1a);
+        }
+        let ab1 = (1. + self.a1m1) * (b12 - self.b11);
+
+        let sbet2 = self.calp0 * ssig2;
+        let mut cbet2 = self.salp0.hypot(self.calp0 * csig2);
+        if cbet2 == 0. {
+            // The line has reached a pole, break the degeneracy there
+            cbet2 = tiny;
+            csig2 = tiny;
+        }
+        let salp2 = self.salp0;
+        let calp2 = self.calp0 * csig2;
+
+        let distance = if arcmode {
+            self.minor * ((1. + self.a1m1) * sig12 + ab1)
+        } else {
+            s12_a12
+        };
+
+        // Longitude, Karney (2013) eq. 8
+        let somg2 = self.salp0 * ssig2;
+        let comg2 = csig2;
+        let omg12 = if unroll {
+            let e = 1_f64.copysign(self.salp0);
+            e * (sig12 - (ssig2.atan2(csig2) - self.ssig1.atan2(self.csig1))
+                + ((e * somg2).atan2(comg2) - (e * self.somg1).atan2(self.comg1)))
+        } else {
+            (somg2 * self.comg1 - comg2 * self.somg1).atan2(comg2 * self.comg1 + somg2 * self.somg1)
+        };
+        let lam12 =
+            omg12 + self.a3c * (sig12 + (sin_cos_series(true, ssig2, csig2, &self.c3a) - self.b31));
+        let lon2 = if unroll {
+            self.lon1.to_degrees() + lam12.to_degrees()
+        } else {
+            ang_normalize_degrees(
+                ang_normalize_degrees(self.lon1.to_degrees())
+                    + ang_normalize_degrees(lam12.to_degrees()),
+            )
+        };
+
+        let lat2 = atan2d(sbet2, self.one_minus_flattening * cbet2);
+        let azi2 = atan2d(salp2, calp2);
+
+        // Reduced length and geodesic scales
+        let b22 = sin_cos_series(true, ssig2, csig2, &self.c2a);
+        let ab2 = (1. + self.a2m1) * (b22 - self.b21);
+        let j12 = (self.a1m1 - self.a2m1) * sig12 + (ab1 - ab2);
+        let reduced_length = self.minor
+            * ((dn2 * (self.csig1 * ssig2) - self.dn1 * (self.ssig1 * csig2))
+                - self.csig1 * csig2 * j12);
+        let t = self.k2 * (ssig2 - self.ssig1) * (ssig2 + self.ssig1) / (self.dn1 + dn2);
+        let geodesic_scale12 = csig12 + (t * ssig2 - csig2 * j12) * self.ssig1 / self.dn1;
+        let geodesic_scale21 = csig12 - (t * self.ssig1 - self.csig1 * j12) * ssig2 / dn2;
+
+        // Area between the line and the equator
+        let b42 = sin_cos_series(false, ssig2, csig2, &self.c4a);
+        let (salp12, calp12) = if self.calp0 == 0. || self.salp0 == 0. {
+            // alp12 = alp2 - alp1
+            (
+                salp2 * self.calp1 - calp2 * self.salp1,
+                calp2 * self.calp1 + salp2 * self.salp1,
+            )
+        } else {
+            // Written to avoid cancellation when alp12 is close to 0 or pi
+            (
+                self.calp0
+                    * self.salp0
+                    * if csig12 <= 0. {
+                        self.csig1 * (1. - csig12) + ssig12 * self.ssig1
+                    } else {
+                        ssig12 * (self.csig1 * ssig12 / (1. + csig12) + self.ssig1)
+                    },
+                self.salp0.powi(2) + self.calp0.powi(2) * self.csig1 * csig2,
+            )
+        };
+        let area = self.authalic_radius_squared * salp12.atan2(calp12) + self.a4 * (b42 - self.b41);
+
+        return GeodesicPosition {
+            lat: lat2.to_radians(),
+            lon: lon2.to_radians(),
+            azimuth: azi2.to_radians(),
+            distance,
+            arc_length: sig12,
+            reduced_length,
+            geodesic_scale12,
+            geodesic_scale21,
+            area,
+        };
+    }
+}
@@ -5,6 +5,7 @@ use crate::{
     types::{DistBearing, LocBearing, Metres, Radians},
 };
 
+#[cfg(target_arch = "wasm32")]
 use wasm_bindgen::prelude::*;
 
 #[cfg(target_arch = "wasm32")]
@@ -31,7 +32,7 @@ pub fn distance_and_bearing(
 
     let a = ((lat2 - lat1) / 2.).sin().powf(2.)
         + (lat1_cos * lat2_cos) * (delta_lon / 2.).sin().powf(2.);
-    let c = 2. * a.sqrt().atan2(1. - a);
+    let c = 2. * a.sqrt().atan2((1. - a).sqrt());
 
     let distance = EARTH_MEAN_RADIUS * c;
 
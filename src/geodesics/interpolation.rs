@@ -0,0 +1,44 @@
+use crate::{
+    ellipsoid::Ellipsoid,
+    geodesics::{geodesic_line::GeodesicLine, karney::Geodesic},
+    reference_frames::wgs84::WGS84Coord,
+};
+
+pub fn midpoint(start: &WGS84Coord, end: &WGS84Coord) -> WGS84Coord {
+    return interpolate_on_ellipsoid(start, end, 0.5, &Ellipsoid::WGS84);
+}
+
+pub fn midpoint_on_ellipsoid(
+    start: &WGS84Coord,
+    end: &WGS84Coord,
+    ellipsoid: &Ellipsoid,
+) -> WGS84Coord {
+    // The point halfway along the geodesic, which is not in general the average of the latitudes and longitudes
+    return interpolate_on_ellipsoid(start, end, 0.5, ellipsoid);
+}
+
+pub fn interpolate(start: &WGS84Coord, end: &WGS84Coord, fraction: f64) -> WGS84Coord {
+    return interpolate_on_ellipsoid(start, end, fraction, &Ellipsoid::WGS84);
+}
+
+pub fn interpolate_on_ellipsoid(
+    start: &WGS84Coord,
+    end: &WGS84Coord,
+    fraction: f64,
+    ellipsoid: &Ellipsoid,
+) -> WGS84Coord {
+    // The point a fraction of the way along the geodesic from start (0) to end (1). Fractions
+    // outside [0, 1] extend the geodesic past either end. The altitude is interpolated linearly
+    let geodesic = Geodesic::new(ellipsoid);
+    let line = GeodesicLine::new_from_endpoints(
+        &geodesic,
+        start.get_lat_radians(),
+        start.get_lon_radians(),
+        end.get_lat_radians(),
+        end.get_lon_radians(),
+    );
+    let position = line.position(fraction * line.get_length().unwrap_or(0.));
+    let altitude = start.get_altitude() + fraction * (end.get_altitude() - start.get_altitude());
+
+    return WGS84Coord::new_from_radians(position.lat, position.lon, altitude);
+}
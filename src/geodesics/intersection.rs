@@ -0,0 +1,372 @@
+use crate::{
+    constants::EARTH_MEAN_RADIUS,
+    ellipsoid::Ellipsoid,
+    geodesics::{geodesic_line::GeodesicLine, karney::Geodesic},
+    projections::gnomonic::Gnomonic,
+    reference_frames::wgs84::WGS84Coord,
+    types::{Metres, Radians},
+};
+
+// Each iteration gains about as many digits as the log of the Earth radius over the distance to
+// the anchor points, so even segments thousands of km long settle within 5 or 6 iterations
+const MAX_ITERATIONS: usize = 20;
+const INTERSECTION_TOLERANCE: Metres = 1e-8;
+
+// Distance either side of the first guess at which the anchors of a line given by a point and an azimuth
+// are placed, close enough to keep the lines nearly straight in the gnomonic projection
+const ANCHOR_SPACING: Metres = 100e3;
+
+#[derive(Debug, Clone)]
+pub struct Intersection {
+    pub coord: WGS84Coord,
+    // Signed distances along each line from its start to the intersection, negative behind the start
+    pub distance1: Metres,
+    pub distance2: Metres,
+    // For segments, whether the intersection lies between the ends of each one. For lines given by
+    // a point and an azimuth, whether it lies ahead of the start
+    pub within1: bool,
+    pub within2: bool,
+}
+
+impl Intersection {
+    pub fn is_within_both(&self) -> bool {
+        return self.within1 && self.within2;
+    }
+}
+
+pub fn spherical_intersections(
+    coord1: &WGS84Coord,
+    azimuth1: Radians,
+    coord2: &WGS84Coord,
+    azimuth2: Radians,
+) -> Option<[Intersection; 2]> {
+    // Great circles through each point with the given initial bearings, on a sphere of the mean
+    // radius, meet at two antipodal points. These come closest first, by the sum of the distances
+    // from the starts. None when both are the same great circle
+    let p1 = unit_vector(coord1.get_lat_radians(), coord1.get_lon_radians());
+    let p2 = unit_vector(coord2.get_lat_radians(), coord2.get_lon_radians());
+    let d1 = direction_vector(coord1.get_lat_radians(), coord1.get_lon_radians(), azimuth1);
+    let d2 = direction_vector(coord2.get_lat_radians(), coord2.get_lon_radians(), azimuth2);
+
+    let [(a, a1, a2), (b, b1, b2)] = great_circle_intersections(p1, d1, p2, d2, 0., 0.)?;
+
+    return Some([
+        spherical_result(a, a1, a2, a1 >= 0., a2 >= 0.),
+        spherical_result(b, b1, b2, b1 >= 0., b2 >= 0.),
+    ]);
+}
+
+pub fn spherical_intersection(
+    coord1: &WGS84Coord,
+    azimuth1: Radians,
+    coord2: &WGS84Coord,
+    azimuth2: Radians,
+) -> Option<Intersection> {
+    let [closest, _] = spherical_intersections(coord1, azimuth1, coord2, azimuth2)?;
+    return Some(closest);
+}
+
+pub fn spherical_segment_intersection(
+    start1: &WGS84Coord,
+    end1: &WGS84Coord,
+    start2: &WGS84Coord,
+    end2: &WGS84Coord,
+) -> Option<Intersection> {
+    // Of the two points where the great circles through each segment meet, the one closest to the
+    // middle of the segments. None when the segments are on the same great circle or one has no length
+    let a1 = unit_vector(start1.get_lat_radians(), start1.get_lon_radians());
+    let b1 = unit_vector(end1.get_lat_radians(), end1.get_lon_radians());
+    let a2 = unit_vector(start2.get_lat_radians(), start2.get_lon_radians());
+    let b2 = unit_vector(end2.get_lat_radians(), end2.get_lon_radians());
+
+    let (d1, length1) = segment_direction(a1, b1)?;
+    let (d2, length2) = segment_direction(a2, b2)?;
+
+    let [(point, sigma1, sigma2), _] =
+        great_circle_intersections(a1, d1, a2, d2, length1 / 2., length2 / 2.)?;
+
+    return Some(spherical_result(
+        point,
+        sigma1,
+        sigma2,
+        (0. ..=length1).contains(&sigma1),
+        (0. ..=length2).contains(&sigma2),
+    ));
+}
+
+pub fn intersection(
+    coord1: &WGS84Coord,
+    azimuth1: Radians,
+    coord2: &WGS84Coord,
+    azimuth2: Radians,
+) -> Option<Intersection> {
+    return intersection_on_ellipsoid(coord1, azimuth1, coord2, azimuth2, &Ellipsoid::WGS84);
+}
+
+pub fn intersection_on_ellipsoid(
+    coord1: &WGS84Coord,
+    azimuth1: Radians,
+    coord2: &WGS84Coord,
+    azimuth2: Radians,
+    ellipsoid: &Ellipsoid,
+) -> Option<Intersection> {
+    // The intersection of two geodesics given by a point and an azimuth, seeded from the closest
+    // spherical intersection. Each line is anchored either side of the spherical guess, so the
+    // intersection found is the one near it rather than one on the far side of the world
+    let geodesic = Geodesic::new(ellipsoid);
+    let guess = spherical_intersection(coord1, azimuth1, coord2, azimuth2)?;
+    let scale = ellipsoid.get_semi_major() / EARTH_MEAN_RADIUS;
+
+    let line1 = GeodesicLine::new(
+        &geodesic,
+        coord1.get_lat_radians(),
+        coord1.get_lon_radians(),
+        azimuth1,
+    );
+    let line2 = GeodesicLine::new(
+        &geodesic,
+        coord2.get_lat_radians(),
+        coord2.get_lon_radians(),
+        azimuth2,
+    );
+
+    let (lat, lon) = gnomonic_intersection(
+        ellipsoid,
+        &guess.coord,
+        anchors(&line1, guess.distance1 * scale),
+        anchors(&line2, guess.distance2 * scale),
+    )?;
+
+    let distance1 = signed_distance(&geodesic, &line1, lat, lon);
+    let distance2 = signed_distance(&geodesic, &line2, lat, lon);
+
+    return Some(Intersection {
+        coord: WGS84Coord::new_from_radians(lat, lon, 0.),
+        distance1,
+        distance2,
+        within1: distance1 >= 0.,
+        within2: distance2 >= 0.,
+    });
+}
+
+pub fn segment_intersection(
+    start1: &WGS84Coord,
+    end1: &WGS84Coord,
+    start2: &WGS84Coord,
+    end2: &WGS84Coord,
+) -> Option<Intersection> {
+    return segment_intersection_on_ellipsoid(start1, end1, start2, end2, &Ellipsoid::WGS84);
+}
+
+pub fn segment_intersection_on_ellipsoid(
+    start1: &WGS84Coord,
+    end1: &WGS84Coord,
+    start2: &WGS84Coord,
+    end2: &WGS84Coord,
+    ellipsoid: &Ellipsoid,
+) -> Option<Intersection> {
+    // As spherical_segment_intersection for geodesic segments. The ends of both segments must be
+    // within 10000 km or so of the intersection, as they have to fit in one gnomonic projection
+    let geodesic = Geodesic::new(ellipsoid);
+    let guess = spherical_segment_intersection(start1, end1, start2, end2)?;
+
+    let line1 = GeodesicLine::new_from_endpoints(
+        &geodesic,
+        start1.get_lat_radians(),
+        start1.get_lon_radians(),
+        end1.get_lat_radians(),
+        end1.get_lon_radians(),
+    );
+    let line2 = GeodesicLine::new_from_endpoints(
+        &geodesic,
+        start2.get_lat_radians(),
+        start2.get_lon_radians(),
+        end2.get_lat_radians(),
+        end2.get_lon_radians(),
+    );
+
+    let (lat, lon) = gnomonic_intersection(
+        ellipsoid,
+        &guess.coord,
+        [
+            (start1.get_lat_radians(), start1.get_lon_radians()),
+            (end1.get_lat_radians(), end1.get_lon_radians()),
+        ],
+        [
+            (start2.get_lat_radians(), start2.get_lon_radians()),
+            (end2.get_lat_radians(), end2.get_lon_radians()),
+        ],
+    )?;
+
+    let distance1 = signed_distance(&geodesic, &line1, lat, lon);
+    let distance2 = signed_distance(&geodesic, &line2, lat, lon);
+    let length1 = line1.get_length().unwrap_or(0.);
+    let length2 = line2.get_length().unwrap_or(0.);
+
+    return Some(Intersection {
+        coord: WGS84Coord::new_from_radians(lat, lon, 0.),
+        distance1,
+        distance2,
+        within1: (0. ..=length1).contains(&distance1),
+        within2: (0. ..=length2).contains(&distance2),
+    });
+}
+
+fn gnomonic_intersection(
+    ellipsoid: &Ellipsoid,
+    guess: &WGS84Coord,
+    anchors1: [(Radians, Radians); 2],
+    anchors2: [(Radians, Radians); 2],
+) -> Option<(Radians, Radians)> {
+    // Karney's (2011) method: geodesics through the centre of a gnomonic projection are straight,
+    // so if the centre is the intersection the straight lines through the projected anchors of
+    // each geodesic cross at the origin. Otherwise where they cross is a much better guess
+    let gnomonic = Gnomonic::new(ellipsoid);
+    let (mut lat0, mut lon0) = (guess.get_lat_radians(), guess.get_lon_radians());
+
+    for _ in 0..MAX_ITERATIONS {
+        let project = |(lat, lon): (Radians, Radians)| {
+            let point = gnomonic.forward(lat0, lon0, lat, lon);
+            return [point.x, point.y, 1.];
+        };
+
+        // Lines and their intersection in homogeneous coordinates
+        let line1 = cross(project(anchors1[0]), project(anchors1[1]));
+        let line2 = cross(project(anchors2[0]), project(anchors2[1]));
+        let meet = cross(line1, line2);
+        let (x, y) = (meet[0] / meet[2], meet[1] / meet[2]);
+
+        if !(x.is_finite() && y.is_finite()) {
+            return None;
+        }
+
+        let next = gnomonic.reverse(lat0, lon0, x, y);
+        (lat0, lon0) = (next.lat, next.lon);
+
+        if x.hypot(y) < INTERSECTION_TOLERANCE {
+            break;
+        }
+    }
+
+    return Some((lat0, lon0));
+}
+
+fn anchors(line: &GeodesicLine, distance: Metres) -> [(Radians, Radians); 2] {
+    let before = line.position(distance - ANCHOR_SPACING);
+    let after = line.position(distance + ANCHOR_SPACING);
+    return [(before.lat, before.lon), (after.lat, after.lon)];
+}
+
+fn signed_distance(geodesic: &Geodesic, line: &GeodesicLine, lat: Radians, lon: Radians) -> Metres {
+    // Distance from the start of a line to a point on it, negative if the point is behind the start
+    let inverse = geodesic.inverse(line.get_lat1(), line.get_lon1(), lat, lon);
+    if (inverse.azimuth1 - line.get_azimuth1()).cos() < 0. {
+        return -inverse.distance;
+    }
+    return inverse.distance;
+}
+
+fn great_circle_intersections(
+    p1: [f64; 3],
+    d1: [f64; 3],
+    p2: [f64; 3],
+    d2: [f64; 3],
+    centre1: Radians,
+    centre2: Radians,
+) -> Option<[([f64; 3], Radians, Radians); 2]> {
+    // Both intersections of the great circles through p1 heading d1 and p2 heading d2, with the
+    // signed angles to them from p1 and p2. Sorted by how far they are from the points at angles
+    // centre1 and centre2 along each great circle
+    let meet = cross(cross(p1, d1), cross(p2, d2));
+    let length = dot(meet, meet).sqrt();
+    if length < f64::EPSILON {
+        return None;
+    }
+
+    let point = [meet[0] / length, meet[1] / length, meet[2] / length];
+    let antipode = [-point[0], -point[1], -point[2]];
+
+    let angles = |x: [f64; 3]| {
+        return (
+            x,
+            dot(x, d1).atan2(dot(x, p1)),
+            dot(x, d2).atan2(dot(x, p2)),
+        );
+    };
+    let (a, b) = (angles(point), angles(antipode));
+    let cost = |(_, sigma1, sigma2): ([f64; 3], Radians, Radians)| {
+        return (sigma1 - centre1).abs() + (sigma2 - centre2).abs();
+    };
+
+    if cost(b) < cost(a) {
+        return Some([b, a]);
+    }
+    return Some([a, b]);
+}
+
+fn spherical_result(
+    point: [f64; 3],
+    sigma1: Radians,
+    sigma2: Radians,
+    within1: bool,
+    within2: bool,
+) -> Intersection {
+    let lat = point[2].atan2(point[0].hypot(point[1]));
+    let lon = point[1].atan2(point[0]);
+
+    return Intersection {
+        coord: WGS84Coord::new_from_radians(lat, lon, 0.),
+        distance1: EARTH_MEAN_RADIUS * sigma1,
+        distance2: EARTH_MEAN_RADIUS * sigma2,
+        within1,
+        within2,
+    };
+}
+
+fn segment_direction(start: [f64; 3], end: [f64; 3]) -> Option<([f64; 3], Radians)> {
+    // Unit vector heading from start towards end, perpendicular to start, and the angle between them
+    let normal = cross(start, end);
+    let sin_angle = dot(normal, normal).sqrt();
+    if sin_angle < f64::EPSILON {
+        return None;
+    }
+
+    let normal = [
+        normal[0] / sin_angle,
+        normal[1] / sin_angle,
+        normal[2] / sin_angle,
+    ];
+    return Some((cross(normal, start), sin_angle.atan2(dot(start, end))));
+}
+
+fn unit_vector(lat: Radians, lon: Radians) -> [f64; 3] {
+    return [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()];
+}
+
+fn direction_vector(lat: Radians, lon: Radians, azimuth: Radians) -> [f64; 3] {
+    // Unit vector along the surface at (lat, lon) heading at an azimuth, from the local north and east
+    let (sin_lat, cos_lat) = lat.sin_cos();
+    let (sin_lon, cos_lon) = lon.sin_cos();
+    let (sin_azimuth, cos_azimuth) = azimuth.sin_cos();
+
+    let north = [-sin_lat * cos_lon, -sin_lat * sin_lon, cos_lat];
+    let east = [-sin_lon, cos_lon, 0.];
+
+    return [
+        north[0] * cos_azimuth + east[0] * sin_azimuth,
+        north[1] * cos_azimuth + east[1] * sin_azimuth,
+        north[2] * cos_azimuth + east[2] * sin_azimuth,
+    ];
+}
+
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    return [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ];
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    return a[0] * b[0] + a[1] * b[1] + a[2] * b[2];
+}
@@ -1,11 +1,16 @@
+use std::f64::consts::{FRAC_1_SQRT_2, PI};
+
 use crate::{
-    constants::{
-        EARTH_ECCENTRICITY_SQUARED, EARTH_FLATTENING, EARTH_MAJOR, EARTH_MINOR,
-        EARTH_SECOND_ECCENTRICITY_SQUARED, EARTH_THIRD_FLATTENING,
+    ellipsoid::Ellipsoid,
+    geodesics::geodesic_line::{GeodesicLine, GeodesicPosition},
+    types::{DistBearing, LocBearing, Metres, Radians, SquareMetres},
+    utils::math::{
+        ang_diff_degrees, ang_normalize, ang_round, astroid, atan2d, eatanhe, norm, polyval,
+        sin_cos_series, sincosd,
     },
-    types::{DistBearing, LocBearing, Metres, Radians},
 };
 
+#[cfg(target_arch = "wasm32")]
 use wasm_bindgen::prelude::*;
 
 #[cfg(target_arch = "wasm32")]
@@ -25,7 +30,22 @@ pub fn location_and_bearing(
     bearing: Radians,
     distance: Metres,
 ) -> LocBearing {
-    let beta: f64 = ((1. - EARTH_FLATTENING) * lat1.tan()).atan();
+    return location_and_bearing_on_ellipsoid(lat1, lon1, bearing, distance, &Ellipsoid::WGS84);
+}
+
+pub fn location_and_bearing_on_ellipsoid(
+    lat1: Radians,
+    lon1: Radians,
+    bearing: Radians,
+    distance: Metres,
+    ellipsoid: &Ellipsoid,
+) -> LocBearing {
+    let flattening = ellipsoid.get_flattening();
+    let minor = ellipsoid.get_semi_minor();
+    let second_eccentricity_squared = ellipsoid.get_second_eccentricity_squared();
+    let third_flattening = ellipsoid.get_third_flattening();
+
+    let beta: f64 = ((1. - flattening) * lat1.tan()).atan();
 
     let sin_alpha = bearing.sin();
     let cos_alpha = bearing.cos();
@@ -42,7 +62,7 @@ pub fn location_and_bearing(
 
     let omega = (sin_alpha_0 * sigma.sin()).atan2(sigma.cos());
 
-    let k_squared = EARTH_SECOND_ECCENTRICITY_SQUARED * cos_alpha_0.powf(2.);
+    let k_squared = second_eccentricity_squared * cos_alpha_0.powf(2.);
 
     let one_plus_k_squared_square_rooted = (1. + k_squared).sqrt();
 
@@ -79,11 +99,11 @@ pub fn location_and_bearing(
         + (49. / 65536. * epsilon_pow10))
         / (1. - epsilon);
 
-    let s_one = i_one_fourier_series(epsilon_pow_tup, sigma, a_1) * EARTH_MINOR;
+    let s_one = i_one_fourier_series(epsilon_pow_tup, sigma, a_1) * minor;
 
     let s_two = s_one + distance;
 
-    let tau_two = s_two / (EARTH_MINOR * a_1);
+    let tau_two = s_two / (minor * a_1);
 
     let sigma_two = sigma_two_fourier_series(epsilon_pow_tup, tau_two);
 
@@ -98,51 +118,49 @@ pub fn location_and_bearing(
     let omega_two = (sin_alpha_0 * sin_sigma_two).atan2(cos_sigma_two);
 
     let a_3 = 1.
-        - (1. / 2. - 1. / 2. * EARTH_THIRD_FLATTENING) * epsilon
-        - (1. / 4. + 1. / 8. * EARTH_THIRD_FLATTENING - 3. / 8. * EARTH_THIRD_FLATTENING.powf(2.))
+        - (1. / 2. - 1. / 2. * third_flattening) * epsilon
+        - (1. / 4. + 1. / 8. * third_flattening - 3. / 8. * third_flattening.powf(2.))
             * epsilon_pow2
-        - (1. / 16.
-            + 3. / 16. * EARTH_THIRD_FLATTENING
-            + 1. / 16. * EARTH_THIRD_FLATTENING.powf(2.)
-            - 5. / 16. * EARTH_THIRD_FLATTENING.powf(3.))
+        - (1. / 16. + 3. / 16. * third_flattening + 1. / 16. * third_flattening.powf(2.)
+            - 5. / 16. * third_flattening.powf(3.))
             * epsilon_pow3
         - (3. / 64.
-            + 1. / 32. * EARTH_THIRD_FLATTENING
-            + 5. / 32. * EARTH_THIRD_FLATTENING.powf(2.)
-            + 5. / 128. * EARTH_THIRD_FLATTENING.powf(3.)
-            - 35. / 128. * EARTH_THIRD_FLATTENING.powf(4.))
+            + 1. / 32. * third_flattening
+            + 5. / 32. * third_flattening.powf(2.)
+            + 5. / 128. * third_flattening.powf(3.)
+            - 35. / 128. * third_flattening.powf(4.))
             * epsilon_pow4
         - (3. / 128.
-            + 5. / 128. * EARTH_THIRD_FLATTENING
-            + 5. / 256. * EARTH_THIRD_FLATTENING.powf(2.)
-            + 35. / 256. * EARTH_THIRD_FLATTENING.powf(3.)
-            + 7. / 256. * EARTH_THIRD_FLATTENING.powf(4.))
+            + 5. / 128. * third_flattening
+            + 5. / 256. * third_flattening.powf(2.)
+            + 35. / 256. * third_flattening.powf(3.)
+            + 7. / 256. * third_flattening.powf(4.))
             * epsilon_pow5
         - (5. / 256.
-            + 15. / 1024. * EARTH_THIRD_FLATTENING
-            + 35. / 1024. * EARTH_THIRD_FLATTENING.powf(2.)
-            + 7. / 512. * EARTH_THIRD_FLATTENING.powf(3.))
+            + 15. / 1024. * third_flattening
+            + 35. / 1024. * third_flattening.powf(2.)
+            + 7. / 512. * third_flattening.powf(3.))
             * epsilon_pow6
-        - (25. / 2048.
-            + 35. / 2048. * EARTH_THIRD_FLATTENING
-            + 21. / 2048. * EARTH_THIRD_FLATTENING.powf(2.))
+        - (25. / 2048. + 35. / 2048. * third_flattening + 21. / 2048. * third_flattening.powf(2.))
             * epsilon_pow7
-        - (175. / 16384. + 35. / 4096. * EARTH_THIRD_FLATTENING) * epsilon_pow8
+        - (175. / 16384. + 35. / 4096. * third_flattening) * epsilon_pow8
         - 245. / 32768. * epsilon_pow9;
 
     let lambda_one = omega
-        - (EARTH_FLATTENING * sin_alpha_0 * i_three_fourier_series(epsilon_pow_tup, sigma, a_3));
+        - (flattening
+            * sin_alpha_0
+            * i_three_fourier_series(epsilon_pow_tup, sigma, a_3, third_flattening));
     let lambda_two = omega_two
-        - (EARTH_FLATTENING
+        - (flattening
             * sin_alpha_0
-            * i_three_fourier_series(epsilon_pow_tup, sigma_two, a_3));
+            * i_three_fourier_series(epsilon_pow_tup, sigma_two, a_3, third_flattening));
 
     let lon = lambda_two - lambda_one;
-    let lat = (beta_two.tan() / (1. - EARTH_FLATTENING)).atan();
+    let lat = (beta_two.tan() / (1. - flattening)).atan();
 
     return LocBearing {
         lat,
-        lon: lon + lon1,
+        lon: ang_normalize(lon + lon1),
         bearing: alpha_two,
     };
 }
@@ -164,43 +182,38 @@ pub fn distance_and_bearing(
     lat2: Radians,
     lon2: Radians,
 ) -> DistBearing {
-    let lambda_one_two = lon2 - lon1;
-    let beta_one = ((1. - EARTH_FLATTENING) * lat1.tan()).atan();
-    let beta_two = ((1. - EARTH_FLATTENING) * lat2.tan()).atan();
-
-    let sin_beta_one = beta_one.sin();
-    let sin_beta_two = beta_two.sin();
-    let cos_beta_one = beta_one.cos();
-    let cos_beta_two = beta_two.cos();
-
-    let omega =
-        (1. - EARTH_ECCENTRICITY_SQUARED * ((cos_beta_one + cos_beta_two) / 2.).powf(2.)).sqrt();
-
-    let omega_one_two = lambda_one_two / omega;
-
-    let cos_omega_one_two = omega_one_two.cos();
-    let sin_omega_one_two = omega_one_two.sin();
-
-    let abs_z_one = (((cos_beta_one * sin_beta_two)
-        - (sin_beta_one * cos_beta_two * cos_omega_one_two))
-        .powf(2.)
-        + (cos_beta_two * sin_omega_one_two).powf(2.))
-    .sqrt();
-
-    let sigma_one_two = abs_z_one
-        .atan2((sin_beta_one * sin_beta_two) + (cos_beta_one * cos_beta_two * cos_omega_one_two));
-
-    let alpha_one = (cos_beta_two * sin_omega_one_two)
-        .atan2(cos_beta_one * sin_beta_two - sin_beta_one * cos_beta_two * cos_omega_one_two);
+    return distance_and_bearing_on_ellipsoid(lat1, lon1, lat2, lon2, &Ellipsoid::WGS84);
+}
 
-    let distance = EARTH_MAJOR * omega * sigma_one_two;
+pub fn distance_and_bearing_on_ellipsoid(
+    lat1: Radians,
+    lon1: Radians,
+    lat2: Radians,
+    lon2: Radians,
+    ellipsoid: &Ellipsoid,
+) -> DistBearing {
+    let inverse = inverse_on_ellipsoid(lat1, lon1, lat2, lon2, ellipsoid);
 
     return DistBearing {
-        distance,
-        bearing: alpha_one,
+        distance: inverse.distance,
+        bearing: inverse.azimuth1,
     };
 }
 
+pub fn inverse(lat1: Radians, lon1: Radians, lat2: Radians, lon2: Radians) -> GeodesicInverse {
+    return inverse_on_ellipsoid(lat1, lon1, lat2, lon2, &Ellipsoid::WGS84);
+}
+
+pub fn inverse_on_ellipsoid(
+    lat1: Radians,
+    lon1: Radians,
+    lat2: Radians,
+    lon2: Radians,
+    ellipsoid: &Ellipsoid,
+) -> GeodesicInverse {
+    return Geodesic::new(ellipsoid).inverse(lat1, lon1, lat2, lon2);
+}
+
 fn i_one_fourier_series(
     epsilon_pow_tup: (f64, f64, f64, f64, f64, f64, f64, f64, f64, f64),
     sigma: f64,
@@ -292,51 +305,946 @@ fn i_three_fourier_series(
     epsilon_pow_tup: (f64, f64, f64, f64, f64, f64, f64, f64, f64, f64),
     sigma: f64,
     a_3: f64,
+    third_flattening: f64,
 ) -> f64 {
-    let e_1: f64 = (((1./4. - 1./4.*EARTH_THIRD_FLATTENING) * epsilon_pow_tup.0 // C31
-                    + (1./8. - 1./8.*EARTH_THIRD_FLATTENING.powf(2.)) * epsilon_pow_tup.1
-                    + (3./64. + 3./64.*EARTH_THIRD_FLATTENING - 1./64.*EARTH_THIRD_FLATTENING.powf(2.) - 5./64.*EARTH_THIRD_FLATTENING.powf(3.)) * epsilon_pow_tup.2
-                    + (5./128. + 1./64.*EARTH_THIRD_FLATTENING + 1./64.*EARTH_THIRD_FLATTENING.powf(2.) - 1./64.*EARTH_THIRD_FLATTENING.powf(3.) - 7./128.*EARTH_THIRD_FLATTENING.powf(4.)) * epsilon_pow_tup.3
-                    + (3./128. + 11./512.*EARTH_THIRD_FLATTENING + 3./512.*EARTH_THIRD_FLATTENING.powf(2.) + 1./256.*EARTH_THIRD_FLATTENING.powf(3.) - 7./512.*EARTH_THIRD_FLATTENING.powf(4.)) * epsilon_pow_tup.4
-                    + (21./1024. + 5./512.*EARTH_THIRD_FLATTENING + 13./1024.*EARTH_THIRD_FLATTENING.powf(2.) + 1./512.*EARTH_THIRD_FLATTENING.powf(3.)) * epsilon_pow_tup.5
-                    + (243./16384. + 189./16384.*EARTH_THIRD_FLATTENING + 83./16384.*EARTH_THIRD_FLATTENING.powf(2.)) * epsilon_pow_tup.6
-                    + (435./32768. + 109./16384.*EARTH_THIRD_FLATTENING) * epsilon_pow_tup.7
+    let e_1: f64 = (((1./4. - 1./4.*third_flattening) * epsilon_pow_tup.0 // C31
+                    + (1./8. - 1./8.*third_flattening.powf(2.)) * epsilon_pow_tup.1
+                    + (3./64. + 3./64.*third_flattening - 1./64.*third_flattening.powf(2.) - 5./64.*third_flattening.powf(3.)) * epsilon_pow_tup.2
+                    + (5./128. + 1./64.*third_flattening + 1./64.*third_flattening.powf(2.) - 1./64.*third_flattening.powf(3.) - 7./128.*third_flattening.powf(4.)) * epsilon_pow_tup.3
+                    + (3./128. + 11./512.*third_flattening + 3./512.*third_flattening.powf(2.) + 1./256.*third_flattening.powf(3.) - 7./512.*third_flattening.powf(4.)) * epsilon_pow_tup.4
+                    + (21./1024. + 5./512.*third_flattening + 13./1024.*third_flattening.powf(2.) + 1./512.*third_flattening.powf(3.)) * epsilon_pow_tup.5
+                    + (243./16384. + 189./16384.*third_flattening + 83./16384.*third_flattening.powf(2.)) * epsilon_pow_tup.6
+                    + (435./32768. + 109./16384.*third_flattening) * epsilon_pow_tup.7
                     + (345./32768. * epsilon_pow_tup.8)) * (2. * sigma).sin())
-                + (((1./16. - 3./32.*EARTH_THIRD_FLATTENING + 1./32.*EARTH_THIRD_FLATTENING.powf(2.)) * epsilon_pow_tup.1 // C32
-                    + (3./64. - 1./32.*EARTH_THIRD_FLATTENING - 3./64.*EARTH_THIRD_FLATTENING.powf(2.) + 1./32.*EARTH_THIRD_FLATTENING.powf(3.)) * epsilon_pow_tup.2
-                    + (3./128. + 1./128.*EARTH_THIRD_FLATTENING - 9./256.*EARTH_THIRD_FLATTENING.powf(2.) - 3./128.*EARTH_THIRD_FLATTENING.powf(3.) + 7./256.*EARTH_THIRD_FLATTENING.powf(4.)) * epsilon_pow_tup.3
-                    + (5./256. + 1./256.*EARTH_THIRD_FLATTENING - 1./128.*EARTH_THIRD_FLATTENING.powf(2.) - 7./256.*EARTH_THIRD_FLATTENING.powf(3.) - 3./256.*EARTH_THIRD_FLATTENING.powf(4.)) * epsilon_pow_tup.4
-                    + (27./2048. + 69./8192.*EARTH_THIRD_FLATTENING - 39./8192.*EARTH_THIRD_FLATTENING.powf(2.) - 47./4096.*EARTH_THIRD_FLATTENING.powf(3.)) * epsilon_pow_tup.5
-                    + (187./16384. + 39./8192.*EARTH_THIRD_FLATTENING + 31./16384.*EARTH_THIRD_FLATTENING.powf(2.)) * epsilon_pow_tup.6
-                    + (287./32768. + 47./8192.*EARTH_THIRD_FLATTENING) * epsilon_pow_tup.7
+                + (((1./16. - 3./32.*third_flattening + 1./32.*third_flattening.powf(2.)) * epsilon_pow_tup.1 // C32
+                    + (3./64. - 1./32.*third_flattening - 3./64.*third_flattening.powf(2.) + 1./32.*third_flattening.powf(3.)) * epsilon_pow_tup.2
+                    + (3./128. + 1./128.*third_flattening - 9./256.*third_flattening.powf(2.) - 3./128.*third_flattening.powf(3.) + 7./256.*third_flattening.powf(4.)) * epsilon_pow_tup.3
+                    + (5./256. + 1./256.*third_flattening - 1./128.*third_flattening.powf(2.) - 7./256.*third_flattening.powf(3.) - 3./256.*third_flattening.powf(4.)) * epsilon_pow_tup.4
+                    + (27./2048. + 69./8192.*third_flattening - 39./8192.*third_flattening.powf(2.) - 47./4096.*third_flattening.powf(3.)) * epsilon_pow_tup.5
+                    + (187./16384. + 39./8192.*third_flattening + 31./16384.*third_flattening.powf(2.)) * epsilon_pow_tup.6
+                    + (287./32768. + 47./8192.*third_flattening) * epsilon_pow_tup.7
                     + (255./32768. * epsilon_pow_tup.8)) * (2. * 2. * sigma).sin())
-                + (((5./192. - 3./64.*EARTH_THIRD_FLATTENING + 5./192.*EARTH_THIRD_FLATTENING.powf(2.) - 1./192.*EARTH_THIRD_FLATTENING.powf(3.)) * epsilon_pow_tup.2 // C33
-                    + (3./128. - 5./192.*EARTH_THIRD_FLATTENING - 1./64.*EARTH_THIRD_FLATTENING.powf(2.) + 5./192.*EARTH_THIRD_FLATTENING.powf(3.) - 1./128.*EARTH_THIRD_FLATTENING.powf(4.)) * epsilon_pow_tup.3
-                    + (7./512. - 1./384.*EARTH_THIRD_FLATTENING - 77./3072.*EARTH_THIRD_FLATTENING.powf(2.) + 5./3072.*EARTH_THIRD_FLATTENING.powf(3.) + 65./3072.*EARTH_THIRD_FLATTENING.powf(4.)) * epsilon_pow_tup.4
-                    + (3./256. - 1./1024.*EARTH_THIRD_FLATTENING - 71./6144.*EARTH_THIRD_FLATTENING.powf(2.) - 47./3072.*EARTH_THIRD_FLATTENING.powf(3.)) * epsilon_pow_tup.5
-                    + (139./16384. + 143./49152.*EARTH_THIRD_FLATTENING - 383./49152.*EARTH_THIRD_FLATTENING.powf(2.)) * epsilon_pow_tup.6
-                    + (243./32768. + 95./49152.*EARTH_THIRD_FLATTENING) * epsilon_pow_tup.7
+                + (((5./192. - 3./64.*third_flattening + 5./192.*third_flattening.powf(2.) - 1./192.*third_flattening.powf(3.)) * epsilon_pow_tup.2 // C33
+                    + (3./128. - 5./192.*third_flattening - 1./64.*third_flattening.powf(2.) + 5./192.*third_flattening.powf(3.) - 1./128.*third_flattening.powf(4.)) * epsilon_pow_tup.3
+                    + (7./512. - 1./384.*third_flattening - 77./3072.*third_flattening.powf(2.) + 5./3072.*third_flattening.powf(3.) + 65./3072.*third_flattening.powf(4.)) * epsilon_pow_tup.4
+                    + (3./256. - 1./1024.*third_flattening - 71./6144.*third_flattening.powf(2.) - 47./3072.*third_flattening.powf(3.)) * epsilon_pow_tup.5
+                    + (139./16384. + 143./49152.*third_flattening - 383./49152.*third_flattening.powf(2.)) * epsilon_pow_tup.6
+                    + (243./32768. + 95./49152.*third_flattening) * epsilon_pow_tup.7
                     + (581./98304. * epsilon_pow_tup.8)) * (2. * 3. * sigma).sin())
-                + (((7./512. - 7./256.*EARTH_THIRD_FLATTENING + 5./256.*EARTH_THIRD_FLATTENING.powf(2.) - 7./1024.*EARTH_THIRD_FLATTENING.powf(3.) + 1./1024.*EARTH_THIRD_FLATTENING.powf(4.)) * epsilon_pow_tup.3// C34
-                    + (7./512. - 5./256.*EARTH_THIRD_FLATTENING - 7./2048.*EARTH_THIRD_FLATTENING.powf(2.)+ 9./512.*EARTH_THIRD_FLATTENING.powf(3.) - 21./2048.*EARTH_THIRD_FLATTENING.powf(4.)) * epsilon_pow_tup.4
-                    + (9./1024. - 43./8192.*EARTH_THIRD_FLATTENING - 129./8192.*EARTH_THIRD_FLATTENING.powf(2.) + 39./4096.*EARTH_THIRD_FLATTENING.powf(3.)) * epsilon_pow_tup.5
-                    + (127./16384. - 23./8192.*EARTH_THIRD_FLATTENING - 165./16384.*EARTH_THIRD_FLATTENING.powf(2.)) * epsilon_pow_tup.6
-                    + (193./32768. + 3./8192.*EARTH_THIRD_FLATTENING) * epsilon_pow_tup.7
+                + (((7./512. - 7./256.*third_flattening + 5./256.*third_flattening.powf(2.) - 7./1024.*third_flattening.powf(3.) + 1./1024.*third_flattening.powf(4.)) * epsilon_pow_tup.3// C34
+                    + (7./512. - 5./256.*third_flattening - 7./2048.*third_flattening.powf(2.)+ 9./512.*third_flattening.powf(3.) - 21./2048.*third_flattening.powf(4.)) * epsilon_pow_tup.4
+                    + (9./1024. - 43./8192.*third_flattening - 129./8192.*third_flattening.powf(2.) + 39./4096.*third_flattening.powf(3.)) * epsilon_pow_tup.5
+                    + (127./16384. - 23./8192.*third_flattening - 165./16384.*third_flattening.powf(2.)) * epsilon_pow_tup.6
+                    + (193./32768. + 3./8192.*third_flattening) * epsilon_pow_tup.7
                     + (171./32768. * epsilon_pow_tup.8)) * (2. * 4. * sigma).sin()) // C35
-                + (((21./2560. - 9./512.*EARTH_THIRD_FLATTENING + 15./1024.*EARTH_THIRD_FLATTENING.powf(2.) - 7./1024.*EARTH_THIRD_FLATTENING.powf(3.) + 9./5120.*EARTH_THIRD_FLATTENING.powf(4.)) * epsilon_pow_tup.4
-                    + (9./1024. - 15./1024.*EARTH_THIRD_FLATTENING + 3./2048.*EARTH_THIRD_FLATTENING.powf(2.) + 57./5120.*EARTH_THIRD_FLATTENING.powf(3.)) * epsilon_pow_tup.5
-                    + (99./16384. - 91./16384.*EARTH_THIRD_FLATTENING - 781./81920.*EARTH_THIRD_FLATTENING.powf(2.)) * epsilon_pow_tup.6
-                    + (179./32768. - 55./16384.*EARTH_THIRD_FLATTENING) * epsilon_pow_tup.7
+                + (((21./2560. - 9./512.*third_flattening + 15./1024.*third_flattening.powf(2.) - 7./1024.*third_flattening.powf(3.) + 9./5120.*third_flattening.powf(4.)) * epsilon_pow_tup.4
+                    + (9./1024. - 15./1024.*third_flattening + 3./2048.*third_flattening.powf(2.) + 57./5120.*third_flattening.powf(3.)) * epsilon_pow_tup.5
+                    + (99./16384. - 91./16384.*third_flattening - 781./81920.*third_flattening.powf(2.)) * epsilon_pow_tup.6
+                    + (179./32768. - 55./16384.*third_flattening) * epsilon_pow_tup.7
                     + (141./32768. * epsilon_pow_tup.8)) * (2. * 5. * sigma).sin())
-                + (((11./2048. - 99./8192.*EARTH_THIRD_FLATTENING + 275./24576.*EARTH_THIRD_FLATTENING.powf(2.) - 77./12288.*EARTH_THIRD_FLATTENING.powf(3.)) * epsilon_pow_tup.5 // C36
-                    + (99./16384. - 275./24576.*EARTH_THIRD_FLATTENING + 55./16384.*EARTH_THIRD_FLATTENING.powf(2.)) * epsilon_pow_tup.6
-                    + (143./32768. - 253./49152.*EARTH_THIRD_FLATTENING) * epsilon_pow_tup.7
+                + (((11./2048. - 99./8192.*third_flattening + 275./24576.*third_flattening.powf(2.) - 77./12288.*third_flattening.powf(3.)) * epsilon_pow_tup.5 // C36
+                    + (99./16384. - 275./24576.*third_flattening + 55./16384.*third_flattening.powf(2.)) * epsilon_pow_tup.6
+                    + (143./32768. - 253./49152.*third_flattening) * epsilon_pow_tup.7
                     + (33./8192. * epsilon_pow_tup.8)) * (2. * 6. * sigma).sin())
-                + (((429./114688. - 143./16384.*EARTH_THIRD_FLATTENING + 143./16384.*EARTH_THIRD_FLATTENING.powf(2.)) * epsilon_pow_tup.6 // C37
-                    + (143./32768. - 143./16384.*EARTH_THIRD_FLATTENING) * epsilon_pow_tup.7
+                + (((429./114688. - 143./16384.*third_flattening + 143./16384.*third_flattening.powf(2.)) * epsilon_pow_tup.6 // C37
+                    + (143./32768. - 143./16384.*third_flattening) * epsilon_pow_tup.7
                     + (429./131072. * epsilon_pow_tup.8)) * (2. * 7. * sigma).sin())
-                + (((715./262144. - 429./65536.*EARTH_THIRD_FLATTENING) * epsilon_pow_tup.7 + (429./131072. * epsilon_pow_tup.8)) * (2. * 8. * sigma).sin()) // C38
+                + (((715./262144. - 429./65536.*third_flattening) * epsilon_pow_tup.7 + (429./131072. * epsilon_pow_tup.8)) * (2. * 8. * sigma).sin()) // C38
                 + ((2431./1179648. * epsilon_pow_tup.8) * (2. * 9. * sigma).sin()); //C39
 
     return a_3 * (sigma + e_1);
 }
+
+// Order of the series expansions in the third flattening, as used by GeographicLib
+pub(crate) const GEODESIC_ORDER: usize = 6;
+const N_C3X: usize = (GEODESIC_ORDER * (GEODESIC_ORDER - 1)) / 2;
+const N_C4X: usize = (GEODESIC_ORDER * (GEODESIC_ORDER + 1)) / 2;
+
+// Newton's method is used for the first MAXIT1 iterations of the inverse, then bisection
+const MAXIT1: usize = 20;
+const MAXIT2: usize = MAXIT1 + f64::MANTISSA_DIGITS as usize + 10;
+
+const TOL0: f64 = f64::EPSILON;
+const TOL1: f64 = 200. * TOL0;
+
+// Coefficients of the series in Karney (2013), grouped as polynomials in n (or eps) followed by their divisor
+const COEFF_A3: [f64; 18] = [
+    -3., 128., -2., -3., 64., -1., -3., -1., 16., 3., -1., -2., 8., 1., -1., 2., 1., 1.,
+];
+
+const COEFF_C3: [f64; 45] = [
+    3., 128., 2., 5., 128., -1., 3., 3., 64., -1., 0., 1., 8., -1., 1., 4., 5., 256., 1., 3., 128.,
+    -3., -2., 3., 64., 1., -3., 2., 32., 7., 512., -10., 9., 384., 5., -9., 5., 192., 7., 512.,
+    -14., 7., 512., 21., 2560.,
+];
+
+const COEFF_C4: [f64; 77] = [
+    97., 15015., 1088., 156., 45045., -224., -4784., 1573., 45045., -10656., 14144., -4576., -858.,
+    45045., 64., 624., -4576., 6864., -3003., 15015., 100., 208., 572., 3432., -12012., 30030.,
+    45045., 1., 9009., -2944., 468., 135135., 5792., 1040., -1287., 135135., 5952., -11648., 9152.,
+    -2574., 135135., -64., -624., 4576., -6864., 3003., 135135., 8., 10725., 1856., -936., 225225.,
+    -8448., 4992., -1144., 225225., -1440., 4160., -4576., 1716., 225225., -136., 63063., 1024.,
+    -208., 105105., 3584., -3328., 1144., 315315., -128., 135135., -2560., 832., 405405., 128.,
+    99099.,
+];
+
+const COEFF_A1M1: [f64; 5] = [1., 4., 64., 0., 256.];
+
+const COEFF_C1: [f64; 18] = [
+    -1., 6., -16., 32., -9., 64., -128., 2048., 9., -16., 768., 3., -5., 512., -7., 1280., -7.,
+    2048.,
+];
+
+const COEFF_C1P: [f64; 18] = [
+    205., -432., 768., 1536., 4005., -4736., 3840., 12288., -225., 116., 384., -7173., 2695.,
+    7680., 3467., 7680., 38081., 61440.,
+];
+
+const COEFF_A2M1: [f64; 5] = [-11., -28., -192., 0., 256.];
+
+const COEFF_C2: [f64; 18] = [
+    1., 2., 16., 32., 35., 64., 384., 2048., 15., 80., 768., 7., 35., 512., 63., 1280., 77., 2048.,
+];
+
+#[derive(Debug, Clone, Copy)]
+pub struct GeodesicInverse {
+    // Solution of the inverse problem, named after the quantities in Karney (2013)
+    pub distance: Metres,       // s12
+    pub arc_length: Radians,    // a12, the arc length on the auxiliary sphere
+    pub azimuth1: Radians,      // azi1, forward azimuth at the first point
+    pub azimuth2: Radians,      // azi2, forward azimuth at the second point
+    pub reduced_length: Metres, // m12
+    pub geodesic_scale12: f64,  // M12
+    pub geodesic_scale21: f64,  // M21
+    pub area: SquareMetres,     // S12, area between the geodesic and the equator
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Geodesic {
+    // Everything Karney's algorithms need for one ellipsoid, including the
+    // ellipsoid dependent parts of the A3, C3 and C4 series
+    ellipsoid: Ellipsoid,
+    pub(crate) major: Metres,
+    pub(crate) minor: Metres,
+    pub(crate) flattening: f64,
+    pub(crate) one_minus_flattening: f64,
+    pub(crate) eccentricity_squared: f64,
+    pub(crate) second_eccentricity_squared: f64,
+    third_flattening: f64,
+    pub(crate) authalic_radius_squared: f64,
+    etol2: f64,
+    a3x: [f64; GEODESIC_ORDER],
+    c3x: [f64; N_C3X],
+    c4x: [f64; N_C4X],
+}
+
+struct Lengths {
+    s12b: f64,
+    m12b: f64,
+    m0: f64,
+    scale12: f64,
+    scale21: f64,
+}
+
+struct Lambda12 {
+    lam12: f64,
+    salp2: f64,
+    calp2: f64,
+    sig12: f64,
+    ssig1: f64,
+    csig1: f64,
+    ssig2: f64,
+    csig2: f64,
+    eps: f64,
+    domg12: f64,
+    dlam12: f64,
+}
+
+impl Geodesic {
+    pub fn new(ellipsoid: &Ellipsoid) -> Geodesic {
+        let major = ellipsoid.get_semi_major();
+        let minor = ellipsoid.get_semi_minor();
+        let flattening = ellipsoid.get_flattening();
+        let eccentricity_squared = ellipsoid.get_eccentricity_squared();
+        let third_flattening = ellipsoid.get_third_flattening();
+
+        // Authalic radius squared, c^2 in Karney (2013) eq. 60
+        let authalic_radius_squared = (major.powi(2)
+            + minor.powi(2)
+                * if eccentricity_squared == 0. {
+                    1.
+                } else {
+                    eatanhe(1., eccentricity_squared.abs().sqrt().copysign(flattening))
+                        / eccentricity_squared
+                })
+            / 2.;
+
+        // Threshold below which the short line starting guess is accurate enough to be used as is
+        let etol2 = 0.1 * TOL0.sqrt()
+            / (flattening.abs().max(0.001) * (1. - flattening / 2.).min(1.) / 2.).sqrt();
+
+        let mut a3x = [0_f64; GEODESIC_ORDER];
+        let mut o = 0;
+        for (k, j) in (0..GEODESIC_ORDER).rev().enumerate() {
+            let m = j.min(GEODESIC_ORDER - j - 1);
+            a3x[k] = polyval(&COEFF_A3[o..=o + m], third_flattening) / COEFF_A3[o + m + 1];
+            o += m + 2;
+        }
+
+        let mut c3x = [0_f64; N_C3X];
+        let mut o = 0;
+        let mut k = 0;
+        for l in 1..GEODESIC_ORDER {
+            for j in (l..GEODESIC_ORDER).rev() {
+                let m = j.min(GEODESIC_ORDER - j - 1);
+                c3x[k] = polyval(&COEFF_C3[o..=o + m], third_flattening) / COEFF_C3[o + m + 1];
+                k += 1;
+                o += m + 2;
+            }
+        }
+
+        let mut c4x = [0_f64; N_C4X];
+        let mut o = 0;
+        let mut k = 0;
+        for l in 0..GEODESIC_ORDER {
+            for j in (l..GEODESIC_ORDER).rev() {
+                let m = GEODESIC_ORDER - j - 1;
+                c4x[k] = polyval(&COEFF_C4[o..=o + m], third_flattening) / COEFF_C4[o + m + 1];
+                k += 1;
+                o += m + 2;
+            }
+        }
+
+        return Geodesic {
+            ellipsoid: *ellipsoid,
+            major,
+            minor,
+            flattening,
+            one_minus_flattening: 1. - flattening,
+            eccentricity_squared,
+            second_eccentricity_squared: ellipsoid.get_second_eccentricity_squared(),
+            third_flattening,
+            authalic_radius_squared,
+            etol2,
+            a3x,
+            c3x,
+            c4x,
+        };
+    }
+
+    pub fn get_ellipsoid(&self) -> Ellipsoid {
+        return self.ellipsoid;
+    }
+
+    pub fn direct(
+        &self,
+        lat1: Radians,
+        lon1: Radians,
+        azimuth1: Radians,
+        distance: Metres,
+    ) -> GeodesicPosition {
+        // The direct problem by way of a one-off GeodesicLine, use the line itself when
+        // computing several points from the same start
+        return GeodesicLine::new(self, lat1, lon1, azimuth1).position(distance);
+    }
+
+    pub fn inverse(
+        &self,
+        lat1: Radians,
+        lon1: Radians,
+        lat2: Radians,
+        lon2: Radians,
+    ) -> GeodesicInverse {
+        // Karney's solution of the inverse problem: Newton's method on the azimuth at the first
+        // point, falling back to bisection, with a starting guess good for nearly antipodal points
+        let solution = self.gen_inverse(
+            lat1.to_degrees(),
+            lon1.to_degrees(),
+            lat2.to_degrees(),
+            lon2.to_degrees(),
+        );
+
+        return GeodesicInverse {
+            distance: solution.s12,
+            arc_length: solution.a12.to_radians(),
+            azimuth1: atan2d(solution.salp1, solution.calp1).to_radians(),
+            azimuth2: atan2d(solution.salp2, solution.calp2).to_radians(),
+            reduced_length: solution.m12,
+            geodesic_scale12: solution.scale12,
+            geodesic_scale21: solution.scale21,
+            area: solution.area,
+        };
+    }
+
+    pub(crate) fn a3f(&self, eps: f64) -> f64 {
+        return polyval(&self.a3x, eps);
+    }
+
+    pub(crate) fn c3f(&self, eps: f64, c: &mut [f64; GEODESIC_ORDER]) {
+        // c[0] is unused so the array can be passed straight to sin_cos_series
+        let mut mult = 1.;
+        let mut o = 0;
+        for (l, c_l) in c.iter_mut().enumerate().skip(1) {
+            let m = GEODESIC_ORDER - l - 1;
+            mult *= eps;
+            *c_l = mult * polyval(&self.c3x[o..=o + m], eps);
+            o += m + 1;
+        }
+    }
+
+    pub(crate) fn c4f(&self, eps: f64, c: &mut [f64; GEODESIC_ORDER]) {
+        let mut mult = 1.;
+        let mut o = 0;
+        for (l, c_l) in c.iter_mut().enumerate() {
+            let m = GEODESIC_ORDER - l - 1;
+            *c_l = mult * polyval(&self.c4x[o..=o + m], eps);
+            o += m + 1;
+            mult *= eps;
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn lengths(
+        &self,
+        eps: f64,
+        sig12: f64,
+        ssig1: f64,
+        csig1: f64,
+        dn1: f64,
+        ssig2: f64,
+        csig2: f64,
+        dn2: f64,
+        cbet1: f64,
+        cbet2: f64,
+    ) -> Lengths {
+        // Distance, reduced length and geodesic scales on the unit-minor-axis ellipsoid
+        let mut c1a = [0_f64; GEODESIC_ORDER + 1];
+        let mut c2a = [0_f64; GEODESIC_ORDER + 1];
+
+        let a1 = a1m1f(eps) + 1.;
+        c1f(eps, &mut c1a);
+        let a2 = a2m1f(eps) + 1.;
+        c2f(eps, &mut c2a);
+        let m0 = a1 - a2;
+
+        let b1 =
+            sin_cos_series(true, ssig2, csig2, &c1a) - sin_cos_series(true, ssig1, csig1, &c1a);
+        let b2 =
+            sin_cos_series(true, ssig2, csig2, &c2a) - sin_cos_series(true, ssig1, csig1, &c2a);
+        let j12 = m0 * sig12 + (a1 * b1 - a2 * b2);
+
+        // Missing a factor of the minor axis
+        let m12b = dn2 * (csig1 * ssig2) - dn1 * (ssig1 * csig2) - csig1 * csig2 * j12;
+
+        let csig12 = csig1 * csig2 + ssig1 * ssig2;
+        let t = self.second_eccentricity_squared * (cbet1 - cbet2) * (cbet1 + cbet2) / (dn1 + dn2);
+
+        return Lengths {
+            s12b: a1 * (sig12 + b1),
+            m12b,
+            m0,
+            scale12: csig12 + (t * ssig2 - csig2 * j12) * ssig1 / dn1,
+            scale21: csig12 - (t * ssig1 - csig1 * j12) * ssig2 / dn2,
+        };
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn inverse_start(
+        &self,
+        sbet1: f64,
+        cbet1: f64,
+        dn1: f64,
+        sbet2: f64,
+        cbet2: f64,
+        dn2: f64,
+        lam12: f64,
+        slam12: f64,
+        clam12: f64,
+    ) -> (f64, f64, f64, f64, f64, f64) {
+        // Starting guess for the azimuth at the first point. Returns sig12 >= 0 (along with the
+        // final azimuth and dnm) when the short line approximation is already accurate enough
+        let mut sig12 = -1.;
+        let mut salp2 = f64::NAN;
+        let mut calp2 = f64::NAN;
+        let mut dnm = f64::NAN;
+
+        let sbet12 = sbet2 * cbet1 - cbet2 * sbet1;
+        let cbet12 = cbet2 * cbet1 + sbet2 * sbet1;
+        let sbet12a = sbet2 * cbet1 + cbet2 * sbet1;
+
+        let shortline = cbet12 >= 0. && sbet12 < 0.5 && cbet2 * lam12 < 0.5;
+        let (mut somg12, mut comg12) = if shortline {
+            let mut sbetm2 = (sbet1 + sbet2).powi(2);
+            sbetm2 /= sbetm2 + (cbet1 + cbet2).powi(2);
+            dnm = (1. + self.second_eccentricity_squared * sbetm2).sqrt();
+            let omg12 = lam12 / (self.one_minus_flattening * dnm);
+            (omg12.sin(), omg12.cos())
+        } else {
+            (slam12, clam12)
+        };
+
+        let mut salp1 = cbet2 * somg12;
+        let mut calp1 = if comg12 >= 0. {
+            sbet12 + cbet2 * sbet1 * somg12.powi(2) / (1. + comg12)
+        } else {
+            sbet12a - cbet2 * sbet1 * somg12.powi(2) / (1. - comg12)
+        };
+
+        let ssig12 = salp1.hypot(calp1);
+        let csig12 = sbet1 * sbet2 + cbet1 * cbet2 * comg12;
+
+        if shortline && ssig12 < self.etol2 {
+            // Really short lines
+            (salp2, calp2) = norm(
+                cbet1 * somg12,
+                sbet12
+                    - cbet1
+                        * sbet2
+                        * if comg12 >= 0. {
+                            somg12.powi(2) / (1. + comg12)
+                        } else {
+                            1. - comg12
+                        },
+            );
+            sig12 = ssig12.atan2(csig12);
+        } else if self.third_flattening.abs() > 0.1
+            || csig12 >= 0.
+            || ssig12 >= 6. * self.third_flattening.abs() * PI * cbet1.powi(2)
+        {
+            // Nothing to do, the zeroth order spherical approximation is good enough
+        } else {
+            // Nearly antipodal points: scale onto the astroid problem of Karney (2013) section 5
+            let lam12x = (-slam12).atan2(-clam12);
+            let (x, y, lamscale) = if self.flattening >= 0. {
+                let k2 = sbet1.powi(2) * self.second_eccentricity_squared;
+                let eps = k2 / (2. * (1. + (1. + k2).sqrt()) + k2);
+                let lamscale = self.flattening * cbet1 * self.a3f(eps) * PI;
+                let betscale = lamscale * cbet1;
+                (lam12x / lamscale, sbet12a / betscale, lamscale)
+            } else {
+                let cbet12a = cbet2 * cbet1 - sbet2 * sbet1;
+                let bet12a = sbet12a.atan2(cbet12a);
+                let lengths = self.lengths(
+                    self.third_flattening,
+                    PI + bet12a,
+                    sbet1,
+                    -cbet1,
+                    dn1,
+                    sbet2,
+                    cbet2,
+                    dn2,
+                    cbet1,
+                    cbet2,
+                );
+                let x = -1. + lengths.m12b / (cbet1 * cbet2 * lengths.m0 * PI);
+                let betscale = if x < -0.01 {
+                    sbet12a / x
+                } else {
+                    -self.flattening * cbet1.powi(2) * PI
+                };
+                let lamscale = betscale / cbet1;
+                (x, lam12x / lamscale, lamscale)
+            };
+
+            if y > -TOL1 && x > -1. - 1000. * TOL0.sqrt() {
+                if self.flattening >= 0. {
+                    salp1 = (-x).min(1.);
+                    calp1 = -(1. - salp1.powi(2)).sqrt();
+                } else {
+                    calp1 = x.max(if x > -TOL1 { 0. } else { -1. });
+                    salp1 = (1. - calp1.powi(2)).sqrt();
+                }
+            } else {
+                let k = astroid(x, y);
+                let omg12a = lamscale
+                    * if self.flattening >= 0. {
+                        -x * k / (1. + k)
+                    } else {
+                        -y * (1. + k) / k
+                    };
+                somg12 = omg12a.sin();
+                comg12 = -omg12a.cos();
+                salp1 = cbet2 * somg12;
+                calp1 = sbet12a - cbet2 * sbet1 * somg12.powi(2) / (1. - comg12);
+            }
+        }
+
+        if salp1 > 0. || salp1.is_nan() {
+            (salp1, calp1) = norm(salp1, calp1);
+        } else {
+            salp1 = 1.;
+            calp1 = 0.;
+        }
+
+        return (sig12, salp1, calp1, salp2, calp2, dnm);
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn lambda12(
+        &self,
+        sbet1: f64,
+        cbet1: f64,
+        dn1: f64,
+        sbet2: f64,
+        cbet2: f64,
+        dn2: f64,
+        salp1: f64,
+        calp1: f64,
+        slam120: f64,
+        clam120: f64,
+        diffp: bool,
+    ) -> Lambda12 {
+        // Longitude difference reached by the geodesic leaving at azimuth alp1, relative to the
+        // target lam12 (given as its sine and cosine), and its derivative with respect to alp1
+        let calp1 = if sbet1 == 0. && calp1 == 0. {
+            // Break degeneracy of equatorial line
+            -f64::MIN_POSITIVE.sqrt()
+        } else {
+            calp1
+        };
+
+        let salp0 = salp1 * cbet1;
+        let calp0 = calp1.hypot(salp1 * sbet1);
+
+        let somg1 = salp0 * sbet1;
+        let comg1 = calp1 * cbet1;
+        let (ssig1, csig1) = norm(sbet1, calp1 * cbet1);
+
+        let salp2 = if cbet2 != cbet1 { salp0 / cbet2 } else { salp1 };
+        let calp2 = if cbet2 != cbet1 || sbet2.abs() != -sbet1 {
+            ((calp1 * cbet1).powi(2)
+                + if cbet1 < -sbet1 {
+                    (cbet2 - cbet1) * (cbet1 + cbet2)
+                } else {
+                    (sbet1 - sbet2) * (sbet1 + sbet2)
+                })
+            .sqrt()
+                / cbet2
+        } else {
+            calp1.abs()
+        };
+
+        let somg2 = salp0 * sbet2;
+        let comg2 = calp2 * cbet2;
+        let (ssig2, csig2) = norm(sbet2, calp2 * cbet2);
+
+        let sig12 = (csig1 * ssig2 - ssig1 * csig2)
+            .max(0.)
+            .atan2(csig1 * csig2 + ssig1 * ssig2);
+        let somg12 = (comg1 * somg2 - somg1 * comg2).max(0.);
+        let comg12 = comg1 * comg2 + somg1 * somg2;
+        let eta = (somg12 * clam120 - comg12 * slam120).atan2(comg12 * clam120 + somg12 * slam120);
+
+        let k2 = calp0.powi(2) * self.second_eccentricity_squared;
+        let eps = k2 / (2. * (1. + (1. + k2).sqrt()) + k2);
+        let mut c3a = [0_f64; GEODESIC_ORDER];
+        self.c3f(eps, &mut c3a);
+        let b312 =
+            sin_cos_series(true, ssig2, csig2, &c3a) - sin_cos_series(true, ssig1, csig1, &c3a);
+        let domg12 = -self.flattening * self.a3f(eps) * salp0 * (sig12 + b312);
+
+        let dlam12 = if !diffp {
+            f64::NAN
+        } else if calp2 == 0. {
+            -2. * self.one_minus_flattening * dn1 / sbet1
+        } else {
+            let lengths = self.lengths(
+                eps, sig12, ssig1, csig1, dn1, ssig2, csig2, dn2, cbet1, cbet2,
+            );
+            lengths.m12b * self.one_minus_flattening / (calp2 * cbet2)
+        };
+
+        return Lambda12 {
+            lam12: eta + domg12,
+            salp2,
+            calp2,
+            sig12,
+            ssig1,
+            csig1,
+            ssig2,
+            csig2,
+            eps,
+            domg12,
+            dlam12,
+        };
+    }
+
+    pub(crate) fn gen_inverse(
+        &self,
+        lat1: f64,
+        lon1: f64,
+        lat2: f64,
+        lon2: f64,
+    ) -> InverseSolution {
+        // Inverse problem with angles in degrees, following GeographicLib's Geodesic::GenInverse
+        // Working in degrees lets the symmetry reductions below be done exactly
+        let tiny = f64::MIN_POSITIVE.sqrt();
+
+        let (lon12, lon12s) = ang_diff_degrees(lon1, lon2);
+        // Make longitude difference positive
+        let mut lonsign = if lon12 >= 0. { 1. } else { -1. };
+        let lon12 = lonsign * ang_round(lon12);
+        let lon12s = ang_round((180. - lon12) - lonsign * lon12s);
+        let lam12 = lon12.to_radians();
+        let (slam12, clam12) = if lon12 > 90. {
+            let (s, c) = sincosd(lon12s);
+            (s, -c)
+        } else {
+            sincosd(lon12)
+        };
+
+        let mut lat1 = ang_round(lat_fix(lat1));
+        let mut lat2 = ang_round(lat_fix(lat2));
+
+        // Swap points so that the first has the larger absolute latitude
+        let swapp = if lat1.abs() < lat2.abs() { -1. } else { 1. };
+        if swapp < 0. {
+            lonsign *= -1.;
+            std::mem::swap(&mut lat1, &mut lat2);
+        }
+        // Make lat1 <= -0
+        let latsign = if lat1 < 0. { 1. } else { -1. };
+        lat1 *= latsign;
+        lat2 *= latsign;
+
+        // Reduced latitudes
+        let (sbet1, cbet1) = sincosd(lat1);
+        let (sbet1, cbet1) = norm(self.one_minus_flattening * sbet1, cbet1);
+        let cbet1 = cbet1.max(tiny);
+
+        let (sbet2, cbet2) = sincosd(lat2);
+        let (mut sbet2, cbet2) = norm(self.one_minus_flattening * sbet2, cbet2);
+        let mut cbet2 = cbet2.max(tiny);
+
+        // Make the reduced latitudes agree exactly when the geodetic latitudes are +/- each other
+        if cbet1 < -sbet1 {
+            if cbet2 == cbet1 {
+                sbet2 = sbet1.copysign(sbet2);
+            }
+        } else if sbet2.abs() == -sbet1 {
+            cbet2 = cbet1;
+        }
+
+        let dn1 = (1. + self.second_eccentricity_squared * sbet1.powi(2)).sqrt();
+        let dn2 = (1. + self.second_eccentricity_squared * sbet2.powi(2)).sqrt();
+
+        let mut a12 = f64::NAN;
+        let mut s12x = f64::NAN;
+        let mut m12x = f64::NAN;
+        let mut scale12 = f64::NAN;
+        let mut scale21 = f64::NAN;
+
+        let mut salp1 = 0_f64;
+        let mut calp1 = 0_f64;
+        let mut salp2 = 0_f64;
+        let mut calp2 = 0_f64;
+
+        let mut meridian = lat1 == -90. || slam12 == 0.;
+
+        if meridian {
+            // Endpoints are on a single full meridian, so the geodesic might lie on it
+            calp1 = clam12;
+            salp1 = slam12;
+            calp2 = 1.;
+            salp2 = 0.;
+
+            let (ssig1, csig1) = (sbet1, calp1 * cbet1);
+            let (ssig2, csig2) = (sbet2, calp2 * cbet2);
+
+            let mut sig12 = (csig1 * ssig2 - ssig1 * csig2)
+                .max(0.)
+                .atan2(csig1 * csig2 + ssig1 * ssig2);
+            let lengths = self.lengths(
+                self.third_flattening,
+                sig12,
+                ssig1,
+                csig1,
+                dn1,
+                ssig2,
+                csig2,
+                dn2,
+                cbet1,
+                cbet2,
+            );
+            s12x = lengths.s12b;
+            m12x = lengths.m12b;
+            scale12 = lengths.scale12;
+            scale21 = lengths.scale21;
+
+            // A negative reduced length means the meridian isn't the shortest path (conjugate point)
+            if sig12 < 1. || m12x >= 0. {
+                if sig12 < 3. * tiny {
+                    sig12 = 0.;
+                    m12x = 0.;
+                    s12x = 0.;
+                }
+                m12x *= self.minor;
+                s12x *= self.minor;
+                a12 = sig12.to_degrees();
+            } else {
+                meridian = false;
+            }
+        }
+
+        let mut somg12 = 2_f64;
+        let mut comg12 = 0_f64;
+        let mut omg12 = 0_f64;
+
+        if !meridian && sbet1 == 0. && (self.flattening <= 0. || lon12s >= self.flattening * 180.) {
+            // Geodesic runs along the equator
+            calp1 = 0.;
+            calp2 = 0.;
+            salp1 = 1.;
+            salp2 = 1.;
+            s12x = self.major * lam12;
+            let sig12 = lam12 / self.one_minus_flattening;
+            omg12 = sig12;
+            m12x = self.minor * sig12.sin();
+            scale12 = sig12.cos();
+            scale21 = sig12.cos();
+            a12 = lon12 / self.one_minus_flattening;
+        } else if !meridian {
+            let (sig12, salp1_start, calp1_start, salp2_start, calp2_start, dnm) =
+                self.inverse_start(sbet1, cbet1, dn1, sbet2, cbet2, dn2, lam12, slam12, clam12);
+            salp1 = salp1_start;
+            calp1 = calp1_start;
+            salp2 = salp2_start;
+            calp2 = calp2_start;
+
+            if sig12 >= 0. {
+                // Short lines where the starting guess is already accurate
+                s12x = sig12 * self.minor * dnm;
+                m12x = dnm.powi(2) * self.minor * (sig12 / dnm).sin();
+                scale12 = (sig12 / dnm).cos();
+                scale21 = scale12;
+                a12 = sig12.to_degrees();
+                omg12 = lam12 / (self.one_minus_flattening * dnm);
+            } else {
+                // Solve lambda12(alp1) = lam12 by Newton's method, keeping a bracket [alp1a, alp1b]
+                // on the root so the search can fall back to bisection if Newton misbehaves
+                let mut tripn = false;
+                let mut tripb = false;
+                let mut salp1a = tiny;
+                let mut calp1a = 1.;
+                let mut salp1b = tiny;
+                let mut calp1b = -1.;
+                let mut lambda = self.lambda12(
+                    sbet1, cbet1, dn1, sbet2, cbet2, dn2, salp1, calp1, slam12, clam12, true,
+                );
+
+                for numit in 0..MAXIT2 {
+                    if numit > 0 {
+                        lambda = self.lambda12(
+                            sbet1,
+                            cbet1,
+                            dn1,
+                            sbet2,
+                            cbet2,
+                            dn2,
+                            salp1,
+                            calp1,
+                            slam12,
+                            clam12,
+                            numit < MAXIT1,
+                        );
+                    }
+                    let v = lambda.lam12;
+                    let dv = lambda.dlam12;
+
+                    if tripb || v.abs() < if tripn { 8. } else { 1. } * TOL0 || v.is_nan() {
+                        break;
+                    }
+
+                    // Update bracketing values
+                    if v > 0. && (numit > MAXIT1 || calp1 / salp1 > calp1b / salp1b) {
+                        salp1b = salp1;
+                        calp1b = calp1;
+                    } else if v < 0. && (numit > MAXIT1 || calp1 / salp1 < calp1a / salp1a) {
+                        salp1a = salp1;
+                        calp1a = calp1;
+                    }
+
+                    if numit < MAXIT1 && dv > 0. {
+                        let dalp1 = -v / dv;
+                        let (sdalp1, cdalp1) = (dalp1.sin(), dalp1.cos());
+                        let nsalp1 = salp1 * cdalp1 + calp1 * sdalp1;
+                        if nsalp1 > 0. && dalp1.abs() < PI {
+                            (salp1, calp1) = norm(nsalp1, calp1 * cdalp1 - salp1 * sdalp1);
+                            // In some regimes Newton stalls just above the tolerance, so loosen it
+                            tripn = v.abs() <= 16. * TOL0;
+                            continue;
+                        }
+                    }
+
+                    // Newton's step was unusable so bisect the bracket instead
+                    (salp1, calp1) = norm((salp1a + salp1b) / 2., (calp1a + calp1b) / 2.);
+                    tripn = false;
+                    let tolb = TOL0 * TOL0.sqrt();
+                    tripb = (salp1a - salp1).abs() + (calp1a - calp1) < tolb
+                        || (salp1 - salp1b).abs() + (calp1 - calp1b) < tolb;
+                }
+
+                salp2 = lambda.salp2;
+                calp2 = lambda.calp2;
+
+                let lengths = self.lengths(
+                    lambda.eps,
+                    lambda.sig12,
+                    lambda.ssig1,
+                    lambda.csig1,
+                    dn1,
+                    lambda.ssig2,
+                    lambda.csig2,
+                    dn2,
+                    cbet1,
+                    cbet2,
+                );
+                s12x = lengths.s12b * self.minor;
+                m12x = lengths.m12b * self.minor;
+                scale12 = lengths.scale12;
+                scale21 = lengths.scale21;
+                a12 = lambda.sig12.to_degrees();
+
+                // omg12 = lam12 - domg12
+                let (sdomg12, cdomg12) = (lambda.domg12.sin(), lambda.domg12.cos());
+                somg12 = slam12 * cdomg12 - clam12 * sdomg12;
+                comg12 = clam12 * cdomg12 + slam12 * sdomg12;
+            }
+        }
+
+        // Area between the geodesic and the equator, Karney (2013) section 6
+        let salp0 = salp1 * cbet1;
+        let calp0 = calp1.hypot(salp1 * sbet1);
+        let mut area = if calp0 != 0. && salp0 != 0. {
+            let (ssig1, csig1) = norm(sbet1, calp1 * cbet1);
+            let (ssig2, csig2) = norm(sbet2, calp2 * cbet2);
+            let k2 = calp0.powi(2) * self.second_eccentricity_squared;
+            let eps = k2 / (2. * (1. + (1. + k2).sqrt()) + k2);
+            let a4 = self.major.powi(2) * calp0 * salp0 * self.eccentricity_squared;
+            let mut c4a = [0_f64; GEODESIC_ORDER];
+            self.c4f(eps, &mut c4a);
+            let b41 = sin_cos_series(false, ssig1, csig1, &c4a);
+            let b42 = sin_cos_series(false, ssig2, csig2, &c4a);
+            a4 * (b42 - b41)
+        } else {
+            // Avoid problems with indeterminate sig1, sig2 on the equator
+            0.
+        };
+
+        if !meridian && somg12 > 1. {
+            somg12 = omg12.sin();
+            comg12 = omg12.cos();
+        }
+
+        let alp12 = if !meridian && comg12 > -FRAC_1_SQRT_2 && sbet2 - sbet1 < 1.75 {
+            // Use tan(Gamma/2) = tan(omg12/2) * (tan(bet1/2) + tan(bet2/2)) / (1 + tan(bet1/2) tan(bet2/2))
+            // with tan(x/2) = sin(x) / (1 + cos(x))
+            let domg12 = 1. + comg12;
+            let dbet1 = 1. + cbet1;
+            let dbet2 = 1. + cbet2;
+            2. * (somg12 * (sbet1 * dbet2 + sbet2 * dbet1))
+                .atan2(domg12 * (sbet1 * sbet2 + dbet1 * dbet2))
+        } else {
+            // alp12 = alp2 - alp1, used in atan2 so no need to normalise
+            let mut salp12 = salp2 * calp1 - calp2 * salp1;
+            let mut calp12 = calp2 * calp1 + salp2 * salp1;
+            if salp12 == 0. && calp12 < 0. {
+                salp12 = tiny * calp1;
+                calp12 = -1.;
+            }
+            salp12.atan2(calp12)
+        };
+        area += self.authalic_radius_squared * alp12;
+        area *= swapp * lonsign * latsign;
+        // Convert -0 to 0
+        area += 0.;
+
+        // Undo the symmetry reductions
+        if swapp < 0. {
+            std::mem::swap(&mut salp1, &mut salp2);
+            std::mem::swap(&mut calp1, &mut calp2);
+            std::mem::swap(&mut scale12, &mut scale21);
+        }
+        salp1 *= swapp * lonsign;
+        calp1 *= swapp * latsign;
+        salp2 *= swapp * lonsign;
+        calp2 *= swapp * latsign;
+
+        return InverseSolution {
+            a12,
+            s12: s12x + 0.,
+            salp1,
+            calp1,
+            salp2,
+            calp2,
+            m12: m12x + 0.,
+            scale12,
+            scale21,
+            area,
+        };
+    }
+}
+
+pub(crate) struct InverseSolution {
+    // Raw inverse solution, with the arc length in degrees and azimuths as sine/cosine pairs
+    pub a12: f64,
+    pub s12: Metres,
+    pub salp1: f64,
+    pub calp1: f64,
+    pub salp2: f64,
+    pub calp2: f64,
+    pub m12: Metres,
+    pub scale12: f64,
+    pub scale21: f64,
+    pub area: SquareMetres,
+}
+
+fn lat_fix(lat: f64) -> f64 {
+    // Latitudes beyond the poles are invalid
+    if lat.abs() > 90. {
+        return f64::NAN;
+    }
+    return lat;
+}
+
+pub(crate) fn a1m1f(eps: f64) -> f64 {
+    // A1 - 1, Karney (2013) eq. 17
+    let m = GEODESIC_ORDER / 2;
+    let t = polyval(&COEFF_A1M1[..=m], eps.powi(2)) / COEFF_A1M1[m + 1];
+    return (t + eps) / (1. - eps);
+}
+
+pub(crate) fn a2m1f(eps: f64) -> f64 {
+    // A2 - 1, Karney (2013) eq. 42
+    let m = GEODESIC_ORDER / 2;
+    let t = polyval(&COEFF_A2M1[..=m], eps.powi(2)) / COEFF_A2M1[m + 1];
+    return (t - eps) / (1. + eps);
+}
+
+pub(crate) fn c1f(eps: f64, c: &mut [f64; GEODESIC_ORDER + 1]) {
+    // C1l coefficients, Karney (2013) eq. 18
+    series_coefficients(&COEFF_C1, eps, c);
+}
+
+pub(crate) fn c1pf(eps: f64, c: &mut [f64; GEODESIC_ORDER + 1]) {
+    // C'1l coefficients for the reversion of the distance series, Karney (2013) eq. 21
+    series_coefficients(&COEFF_C1P, eps, c);
+}
+
+pub(crate) fn c2f(eps: f64, c: &mut [f64; GEODESIC_ORDER + 1]) {
+    // C2l coefficients, Karney (2013) eq. 43
+    series_coefficients(&COEFF_C2, eps, c);
+}
+
+fn series_coefficients(coeffs: &[f64], eps: f64, c: &mut [f64; GEODESIC_ORDER + 1]) {
+    // Each coefficient is eps^l times an even polynomial in eps
+    let eps2 = eps.powi(2);
+    let mut d = eps;
+    let mut o = 0;
+    for (l, c_l) in c.iter_mut().enumerate().skip(1) {
+        let m = (GEODESIC_ORDER - l) / 2;
+        *c_l = d * polyval(&coeffs[o..=o + m], eps2) / coeffs[o + m + 1];
+        o += m + 2;
+        d *= eps;
+    }
+}
@@ -1,3 +1,11 @@
+pub mod centroid;
+pub mod circle;
+pub mod cross_track;
+pub mod geodesic_line;
 pub mod haversine;
+pub mod interpolation;
+pub mod intersection;
 pub mod karney;
+pub mod polygon;
+pub mod rhumb;
 pub mod vincenty;
@@ -0,0 +1,242 @@
+use std::f64::consts::PI;
+
+use crate::{
+    ellipsoid::Ellipsoid,
+    geodesics::{geodesic_line::GeodesicLine, karney::Geodesic},
+    reference_frames::wgs84::WGS84Coord,
+    types::{Degrees, Metres, Radians, SquareMetres},
+    utils::math::{ang_diff_degrees, ang_normalize_degrees, sum},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Winding {
+    // Which direction of traversal gives a positive area
+    CounterClockwise,
+    Clockwise,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PolygonResult {
+    pub perimeter: Metres,
+    pub area: SquareMetres,
+    pub num_vertices: usize,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Accumulator {
+    // Running sum carried as a rounded value plus its round-off, so that adding many edges
+    // of alternating sign doesn't lose the area of a small polygon
+    s: f64,
+    t: f64,
+}
+
+impl Accumulator {
+    fn add(&mut self, y: f64) {
+        let (y, u) = sum(y, self.t);
+        (self.s, self.t) = sum(y, self.s);
+        if self.s == 0. {
+            self.s = u;
+        } else {
+            self.t += u;
+        }
+    }
+
+    fn get_sum(&self) -> f64 {
+        return self.s;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GeodesicPolygon {
+    // Accumulates a polygon with geodesic edges one vertex at a time. The area is summed from
+    // the S12 of each edge (Karney 2013 section 6), and the number of times the edges cross the
+    // prime meridian decides whether the polygon encloses a pole
+    geodesic: Geodesic,
+    winding: Winding,
+    num_vertices: usize,
+    perimeter: Accumulator,
+    area: Accumulator,
+    crossings: i64,
+    // Vertices are kept in degrees so that meridian crossings are counted exactly
+    first_lat: Degrees,
+    first_lon: Degrees,
+    last_lat: Degrees,
+    last_lon: Degrees,
+}
+
+impl GeodesicPolygon {
+    pub fn new(ellipsoid: &Ellipsoid, winding: Winding) -> GeodesicPolygon {
+        return GeodesicPolygon {
+            geodesic: Geodesic::new(ellipsoid),
+            winding,
+            num_vertices: 0,
+            perimeter: Accumulator::default(),
+            area: Accumulator::default(),
+            crossings: 0,
+            first_lat: f64::NAN,
+            first_lon: f64::NAN,
+            last_lat: f64::NAN,
+            last_lon: f64::NAN,
+        };
+    }
+
+    pub fn new_from_coords(coords: &[WGS84Coord], winding: Winding) -> GeodesicPolygon {
+        let mut polygon = GeodesicPolygon::new(&Ellipsoid::WGS84, winding);
+        for coord in coords.iter() {
+            polygon.add_point(coord.get_lat_radians(), coord.get_lon_radians());
+        }
+        return polygon;
+    }
+
+    pub fn clear(&mut self) {
+        *self = GeodesicPolygon::new(&self.geodesic.get_ellipsoid(), self.winding);
+    }
+
+    pub fn get_num_vertices(&self) -> usize {
+        return self.num_vertices;
+    }
+
+    pub fn add_point(&mut self, lat: Radians, lon: Radians) {
+        let (lat, lon) = (lat.to_degrees(), lon.to_degrees());
+
+        if self.num_vertices == 0 {
+            self.first_lat = lat;
+            self.first_lon = lon;
+        } else {
+            let edge = self
+                .geodesic
+                .gen_inverse(self.last_lat, self.last_lon, lat, lon);
+            self.perimeter.add(edge.s12);
+            self.area.add(edge.area);
+            self.crossings += transit(self.last_lon, lon);
+        }
+
+        self.last_lat = lat;
+        self.last_lon = lon;
+        self.num_vertices += 1;
+    }
+
+    pub fn add_edge(&mut self, azimuth: Radians, distance: Metres) {
+        // Add the vertex reached by travelling from the last one, does nothing on an empty polygon
+        if self.num_vertices == 0 {
+            return;
+        }
+
+        let line = GeodesicLine::new(
+            &self.geodesic,
+            self.last_lat.to_radians(),
+            self.last_lon.to_radians(),
+            azimuth,
+        );
+        let end = line.position_unrolled(distance);
+        let lon = end.lon.to_degrees();
+
+        self.perimeter.add(distance);
+        self.area.add(end.area);
+        self.crossings += transit_direct(self.last_lon, lon);
+        self.last_lat = end.lat.to_degrees();
+        self.last_lon = lon;
+        self.num_vertices += 1;
+    }
+
+    pub fn compute(&self, signed: bool) -> PolygonResult {
+        // Close the polygon back to the first vertex. With signed set, traversing the polygon
+        // against the winding gives a negative area, otherwise it gives the area of the rest of
+        // the ellipsoid
+        if self.num_vertices < 2 {
+            return PolygonResult {
+                perimeter: 0.,
+                area: 0.,
+                num_vertices: self.num_vertices,
+            };
+        }
+
+        let closing =
+            self.geodesic
+                .gen_inverse(self.last_lat, self.last_lon, self.first_lat, self.first_lon);
+
+        let mut perimeter = self.perimeter;
+        perimeter.add(closing.s12);
+        let mut area = self.area;
+        area.add(closing.area);
+        let crossings = self.crossings + transit(self.last_lon, self.first_lon);
+
+        return PolygonResult {
+            perimeter: perimeter.get_sum(),
+            area: self.reduce_area(area.get_sum(), crossings, signed),
+            num_vertices: self.num_vertices,
+        };
+    }
+
+    pub fn test_point(&self, lat: Radians, lon: Radians, signed: bool) -> PolygonResult {
+        // Result if a vertex were added at (lat, lon), leaving this polygon unchanged
+        let mut polygon = self.clone();
+        polygon.add_point(lat, lon);
+        return polygon.compute(signed);
+    }
+
+    pub fn test_edge(&self, azimuth: Radians, distance: Metres, signed: bool) -> PolygonResult {
+        let mut polygon = self.clone();
+        polygon.add_edge(azimuth, distance);
+        return polygon.compute(signed);
+    }
+
+    fn reduce_area(&self, area: f64, crossings: i64, signed: bool) -> SquareMetres {
+        // The edge areas are measured from the equator, so an odd number of prime meridian
+        // crossings means the polygon encloses a pole and is out by half the ellipsoid
+        let total = 4. * PI * self.geodesic.authalic_radius_squared;
+
+        let mut area = area - total * (area / total).round();
+        if crossings % 2 != 0 {
+            area += if area < 0. { total / 2. } else { -total / 2. };
+        }
+
+        // The edge areas are positive for clockwise traversal
+        if self.winding == Winding::CounterClockwise {
+            area = -area;
+        }
+
+        if signed {
+            if area > total / 2. {
+                area -= total;
+            } else if area <= -total / 2. {
+                area += total;
+            }
+        } else if area >= total {
+            area -= total;
+        } else if area < 0. {
+            area += total;
+        }
+
+        // Convert -0 to 0
+        return area + 0.;
+    }
+}
+
+pub fn area_and_perimeter(coords: &[WGS84Coord]) -> PolygonResult {
+    // Signed area of a polygon on WGS84, positive when the vertices run counter-clockwise
+    return GeodesicPolygon::new_from_coords(coords, Winding::CounterClockwise).compute(true);
+}
+
+fn transit(lon1: Degrees, lon2: Degrees) -> i64 {
+    // 1 or -1 for an edge crossing the prime meridian eastwards or westwards, otherwise 0
+    let (lon12, _) = ang_diff_degrees(lon1, lon2);
+    let lon1 = ang_normalize_degrees(lon1);
+    let lon2 = ang_normalize_degrees(lon2);
+
+    if lon12 > 0. && ((lon1 < 0. && lon2 >= 0.) || (lon1 > 0. && lon2 == 0.)) {
+        return 1;
+    }
+    if lon12 < 0. && lon1 >= 0. && lon2 < 0. {
+        return -1;
+    }
+    return 0;
+}
+
+fn transit_direct(lon1: Degrees, lon2: Degrees) -> i64 {
+    // As transit, for unrolled longitudes where an edge may wrap around more than once
+    // Only the parity matters, and reducing modulo 720 keeps it exactly
+    let lon1 = lon1 % 720.;
+    let lon2 = lon2 % 720.;
+    return (lon2 / 360.).floor() as i64 - (lon1 / 360.).floor() as i64;
+}
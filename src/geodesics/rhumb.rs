@@ -0,0 +1,320 @@
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use crate::{
+    ellipsoid::Ellipsoid,
+    types::{DistBearing, LocBearing, Metres, Radians},
+    utils::math::{ang_normalize, eatanhe, polyval, sin_cos_series},
+};
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::prelude::*;
+
+// Order in the third flattening of the meridian arc series, accurate to well under a micrometre on WGS84
+const RHUMB_ORDER: usize = 6;
+
+// Geodetic latitude -> rectifying latitude, sin(2 l phi) coefficients for l = 1..6,
+// each a polynomial in n (highest order first) with its lowest power of n factored out
+const COEFF_MU: [&[f64]; RHUMB_ORDER] = [
+    &[-3. / 32., 0., 9. / 16., 0., -3. / 2.],
+    &[135. / 2048., 0., -15. / 32., 0., 15. / 16.],
+    &[105. / 256., 0., -35. / 48.],
+    &[-189. / 512., 0., 315. / 512.],
+    &[-693. / 1280.],
+    &[1001. / 2048.],
+];
+
+// Rectifying latitude -> geodetic latitude, laid out as COEFF_MU
+const COEFF_PHI: [&[f64]; RHUMB_ORDER] = [
+    &[269. / 512., 0., -27. / 32., 0., 3. / 2.],
+    &[6759. / 4096., 0., -55. / 32., 0., 21. / 16.],
+    &[-417. / 128., 0., 151. / 96.],
+    &[-15543. / 2560., 0., 1097. / 512.],
+    &[8011. / 2560.],
+    &[293393. / 61440.],
+];
+
+#[derive(Debug, Clone, Copy)]
+pub struct Rhumb {
+    // Constant bearing (loxodrome) solver. Isometric latitude psi makes the line straight in
+    // (lon, psi), and the distance along it is the meridian arc divided by the cosine of the bearing
+    ellipsoid: Ellipsoid,
+    eccentricity_squared: f64,
+    // Signed eccentricity, negative for a prolate ellipsoid as used by eatanhe
+    eccentricity: f64,
+    // Meridian arc is rectifying_radius * mu for rectifying latitude mu
+    rectifying_radius: Metres,
+    // First entry unused, following the layout expected by sin_cos_series
+    mu_coeffs: [f64; RHUMB_ORDER + 1],
+    phi_coeffs: [f64; RHUMB_ORDER + 1],
+}
+
+impl Rhumb {
+    pub fn new(ellipsoid: &Ellipsoid) -> Rhumb {
+        let n = ellipsoid.get_third_flattening();
+        let eccentricity_squared = ellipsoid.get_eccentricity_squared();
+
+        let mut mu_coeffs = [0_f64; RHUMB_ORDER + 1];
+        let mut phi_coeffs = [0_f64; RHUMB_ORDER + 1];
+        let mut d = 1_f64;
+        for l in 0..RHUMB_ORDER {
+            d *= n;
+            mu_coeffs[l + 1] = d * polyval(COEFF_MU[l], n);
+            phi_coeffs[l + 1] = d * polyval(COEFF_PHI[l], n);
+        }
+
+        let n2 = n * n;
+        return Rhumb {
+            ellipsoid: *ellipsoid,
+            eccentricity_squared,
+            eccentricity: eccentricity_squared
+                .abs()
+                .sqrt()
+                .copysign(eccentricity_squared),
+            rectifying_radius: ellipsoid.get_semi_major() / (1. + n)
+                * polyval(&[1. / 256., 1. / 64., 1. / 4., 1.], n2),
+            mu_coeffs,
+            phi_coeffs,
+        };
+    }
+
+    pub fn get_ellipsoid(&self) -> Ellipsoid {
+        return self.ellipsoid;
+    }
+
+    pub fn meridian_arc(&self, lat: Radians) -> Metres {
+        // Distance along the meridian from the equator to a geodetic latitude
+        return self.rectifying_radius * self.rectifying_latitude(lat);
+    }
+
+    pub fn isometric_latitude(&self, lat: Radians) -> f64 {
+        // psi = asinh(tan(chi)) for conformal latitude chi, infinite at the poles
+        let (sin_lat, cos_lat) = pole_exact_sin_cos(lat);
+        return (sin_lat / cos_lat).asinh() - eatanhe(sin_lat, self.eccentricity);
+    }
+
+    pub fn inverse(
+        &self,
+        lat1: Radians,
+        lon1: Radians,
+        lat2: Radians,
+        lon2: Radians,
+    ) -> DistBearing {
+        // Given two points gives the rhumb line distance and the constant bearing in [0, 2pi)
+        // The longitude difference is reduced to [-pi, pi] so the shorter way round the world is taken,
+        // including across the antimeridian
+        let delta_lon = ang_normalize(lon2 - lon1);
+        let delta_psi = self.delta_isometric_latitude(lat1, lat2);
+        let delta_m = self.delta_meridian_arc(lat1, lat2);
+
+        let bearing = delta_lon.atan2(delta_psi);
+
+        let distance = if delta_psi.is_infinite() {
+            // To or from a pole, the line heads straight along the meridian
+            delta_m.abs()
+        } else {
+            delta_lon.hypot(delta_psi) * self.mean_scale(lat1, lat2, delta_m, delta_psi)
+        };
+
+        return DistBearing {
+            distance,
+            bearing: (bearing + 2. * PI) % (2. * PI),
+        };
+    }
+
+    pub fn direct(
+        &self,
+        lat1: Radians,
+        lon1: Radians,
+        bearing: Radians,
+        distance: Metres,
+    ) -> LocBearing {
+        // Given a start point, a constant bearing and a distance gives the end point
+        // A rhumb line spirals into the pole rather than crossing it, so going past the pole gives NaN
+        let (sin_bearing, cos_bearing) = bearing.sin_cos();
+        let mu2 = self.rectifying_latitude(lat1) + distance * cos_bearing / self.rectifying_radius;
+
+        // Allow a few ulps of round-off so that the exact distance to the pole still reaches it
+        if mu2.abs() > FRAC_PI_2 * (1. + 4. * f64::EPSILON) {
+            return LocBearing {
+                lat: f64::NAN,
+                lon: f64::NAN,
+                bearing,
+            };
+        }
+
+        let lat2 = if mu2.abs() >= FRAC_PI_2 {
+            FRAC_PI_2.copysign(mu2)
+        } else {
+            mu2 + sin_cos_series(true, mu2.sin(), mu2.cos(), &self.phi_coeffs)
+        };
+
+        let delta_psi = self.delta_isometric_latitude(lat1, lat2);
+        let delta_lon = if delta_psi.is_infinite() {
+            // Longitude is undefined at the pole, keep the starting meridian
+            0.
+        } else {
+            let delta_m = self.delta_meridian_arc(lat1, lat2);
+            distance * sin_bearing / self.mean_scale(lat1, lat2, delta_m, delta_psi)
+        };
+
+        return LocBearing {
+            lat: lat2,
+            lon: ang_normalize(lon1 + delta_lon),
+            bearing,
+        };
+    }
+
+    fn rectifying_latitude(&self, lat: Radians) -> Radians {
+        let (sin_lat, cos_lat) = pole_exact_sin_cos(lat);
+        return lat + sin_cos_series(true, sin_lat, cos_lat, &self.mu_coeffs);
+    }
+
+    fn delta_meridian_arc(&self, lat1: Radians, lat2: Radians) -> Metres {
+        // m(lat2) - m(lat1) without cancellation for close latitudes, using
+        // sin(2 l x) - sin(2 l y) = 2 cos(l (x + y)) sin(l (x - y))
+        let sum = lat1 + lat2;
+        let diff = lat2 - lat1;
+        let mut delta_mu = diff;
+        for (l, c) in self.mu_coeffs.iter().enumerate().skip(1) {
+            let l = l as f64;
+            delta_mu += c * 2. * (l * sum).cos() * (l * diff).sin();
+        }
+        return self.rectifying_radius * delta_mu;
+    }
+
+    fn delta_isometric_latitude(&self, lat1: Radians, lat2: Radians) -> f64 {
+        // psi(lat2) - psi(lat1) without cancellation for close latitudes, using
+        // asinh(tan x) - asinh(tan y) = asinh((sin x - sin y) / (cos x cos y)) and
+        // atanh(a) - atanh(b) = atanh((a - b) / (1 - a b))
+        if lat1 == lat2 {
+            return 0.;
+        }
+        let (sin1, cos1) = pole_exact_sin_cos(lat1);
+        let (sin2, cos2) = pole_exact_sin_cos(lat2);
+        let delta_sin = 2. * ((lat1 + lat2) / 2.).cos() * ((lat2 - lat1) / 2.).sin();
+
+        return (delta_sin / (cos1 * cos2)).asinh()
+            - eatanhe(
+                delta_sin / (1. - self.eccentricity_squared * sin1 * sin2),
+                self.eccentricity,
+            );
+    }
+
+    fn mean_scale(&self, lat1: Radians, lat2: Radians, delta_m: Metres, delta_psi: f64) -> Metres {
+        // Mean of dm/dpsi between the latitudes, which is the parallel radius on an east-west line
+        if delta_psi == 0. {
+            let lat = (lat1 + lat2) / 2.;
+            return self.ellipsoid.prime_vertical_radius(lat) * lat.cos();
+        }
+        return delta_m / delta_psi;
+    }
+}
+
+fn pole_exact_sin_cos(lat: Radians) -> (f64, f64) {
+    // cos(pi / 2) isn't zero in floating point, which would leave psi finite at the poles
+    if lat.abs() == FRAC_PI_2 {
+        return (lat.signum(), 0.);
+    }
+    return lat.sin_cos();
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn rhumb_distance_and_bearing(
+    lat1: Radians,
+    lon1: Radians,
+    lat2: Radians,
+    lon2: Radians,
+) -> DistBearing {
+    return distance_and_bearing(lat1, lon1, lat2, lon2);
+}
+
+pub fn distance_and_bearing(
+    lat1: Radians,
+    lon1: Radians,
+    lat2: Radians,
+    lon2: Radians,
+) -> DistBearing {
+    return distance_and_bearing_on_ellipsoid(lat1, lon1, lat2, lon2, &Ellipsoid::WGS84);
+}
+
+pub fn distance_and_bearing_on_ellipsoid(
+    lat1: Radians,
+    lon1: Radians,
+    lat2: Radians,
+    lon2: Radians,
+    ellipsoid: &Ellipsoid,
+) -> DistBearing {
+    return Rhumb::new(ellipsoid).inverse(lat1, lon1, lat2, lon2);
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn rhumb_location_and_bearing(
+    lat1: Radians,
+    lon1: Radians,
+    bearing: Radians,
+    distance: Metres,
+) -> LocBearing {
+    return location_and_bearing(lat1, lon1, bearing, distance);
+}
+
+pub fn location_and_bearing(
+    lat1: Radians,
+    lon1: Radians,
+    bearing: Radians,
+    distance: Metres,
+) -> LocBearing {
+    return location_and_bearing_on_ellipsoid(lat1, lon1, bearing, distance, &Ellipsoid::WGS84);
+}
+
+pub fn location_and_bearing_on_ellipsoid(
+    lat1: Radians,
+    lon1: Radians,
+    bearing: Radians,
+    distance: Metres,
+    ellipsoid: &Ellipsoid,
+) -> LocBearing {
+    return Rhumb::new(ellipsoid).direct(lat1, lon1, bearing, distance);
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn rhumb_spherical_distance_and_bearing(
+    lat1: Radians,
+    lon1: Radians,
+    lat2: Radians,
+    lon2: Radians,
+) -> DistBearing {
+    return spherical_distance_and_bearing(lat1, lon1, lat2, lon2);
+}
+
+pub fn spherical_distance_and_bearing(
+    lat1: Radians,
+    lon1: Radians,
+    lat2: Radians,
+    lon2: Radians,
+) -> DistBearing {
+    // On a sphere of the mean radius, the rhumb line counterpart of haversine::distance_and_bearing
+    return distance_and_bearing_on_ellipsoid(lat1, lon1, lat2, lon2, &Ellipsoid::SPHERE);
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn rhumb_spherical_location_and_bearing(
+    lat1: Radians,
+    lon1: Radians,
+    bearing: Radians,
+    distance: Metres,
+) -> LocBearing {
+    return spherical_location_and_bearing(lat1, lon1, bearing, distance);
+}
+
+pub fn spherical_location_and_bearing(
+    lat1: Radians,
+    lon1: Radians,
+    bearing: Radians,
+    distance: Metres,
+) -> LocBearing {
+    return location_and_bearing_on_ellipsoid(lat1, lon1, bearing, distance, &Ellipsoid::SPHERE);
+}
@@ -1,10 +1,49 @@
+use std::{f64::consts::PI, fmt};
+
 use crate::{
-    constants::{EARTH_FLATTENING, EARTH_MAJOR, EARTH_MINOR},
+    ellipsoid::Ellipsoid,
+    geodesics::karney,
     types::{DistBearing, LocBearing, Metres, Radians},
+    utils::math::ang_normalize,
 };
 
+#[cfg(target_arch = "wasm32")]
 use wasm_bindgen::prelude::*;
 
+// Nearly antipodal lines converge very slowly if at all, so give up on the inverse after this many
+pub const MAX_ITERATIONS: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VincentyError {
+    // Lambda had not settled after this many iterations
+    DidNotConverge(usize),
+}
+
+impl fmt::Display for VincentyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VincentyError::DidNotConverge(iterations) => write!(
+                f,
+                "vincenty inverse did not converge after {} iterations",
+                iterations
+            ),
+        }
+    }
+}
+
+impl std::error::Error for VincentyError {}
+
+#[derive(Debug, Clone, Copy)]
+pub struct VincentyInverse {
+    pub distance: Metres,
+    pub initial_bearing: Radians,
+    // Forward azimuth on arrival at the second point, add pi for the back bearing
+    pub final_bearing: Radians,
+    pub iterations: usize,
+    // True when Vincenty didn't converge and the result came from karney::inverse instead
+    pub karney_fallback: bool,
+}
+
 #[cfg(target_arch = "wasm32")]
 #[wasm_bindgen]
 pub fn vincenty_location_and_bearing(
@@ -22,10 +61,24 @@ pub fn location_and_bearing(
     bearing: Radians,
     distance: Metres,
 ) -> LocBearing {
+    return location_and_bearing_on_ellipsoid(lat1, lon1, bearing, distance, &Ellipsoid::WGS84);
+}
+
+pub fn location_and_bearing_on_ellipsoid(
+    lat1: Radians,
+    lon1: Radians,
+    bearing: Radians,
+    distance: Metres,
+    ellipsoid: &Ellipsoid,
+) -> LocBearing {
+    let flattening = ellipsoid.get_flattening();
+    let major = ellipsoid.get_semi_major();
+    let minor = ellipsoid.get_semi_minor();
+
     let sin_alpha1 = bearing.sin();
     let cos_alpha1 = bearing.cos();
 
-    let tan_u1 = (1. - EARTH_FLATTENING) * lat1.tan();
+    let tan_u1 = (1. - flattening) * lat1.tan();
     let cos_u1 = 1. / (1. + (tan_u1 * tan_u1)).sqrt();
     let sin_u1 = tan_u1 * cos_u1;
 
@@ -33,14 +86,13 @@ pub fn location_and_bearing(
     let sin_alpha = cos_u1 * sin_alpha1;
     let cos_s_q_alpha = 1.0 - (sin_alpha * sin_alpha);
 
-    let u_s_q = cos_s_q_alpha * ((EARTH_MAJOR * EARTH_MAJOR) - (EARTH_MINOR * EARTH_MINOR))
-        / (EARTH_MINOR * EARTH_MINOR);
+    let u_s_q = cos_s_q_alpha * ((major * major) - (minor * minor)) / (minor * minor);
 
     let a_vin =
         1. + (u_s_q / 16384.0) * (4096.0 + (u_s_q * (-768.0 + u_s_q * (320.0 - 175.0 * u_s_q))));
     let b_vin = (u_s_q / 1024.0) * (256.0 + u_s_q * (-128.0 + u_s_q * (74.0 - 47.0 * u_s_q)));
 
-    let sigma = distance / (a_vin * EARTH_MINOR);
+    let sigma = distance / (a_vin * minor);
 
     let mut cos2_sigma_m = ((2. * sigma_1) + sigma).cos();
     let mut sin_sigma = sigma.sin();
@@ -58,7 +110,7 @@ pub fn location_and_bearing(
                         * (-3.0 + 4.0 * cos_2_sigma_m_squared)));
 
     let mut sigma_prime = sigma;
-    let mut sigma = (distance / (EARTH_MINOR * a_vin)) + delta_sigma;
+    let mut sigma = (distance / (minor * a_vin)) + delta_sigma;
 
     while (sigma - sigma_prime).abs() > 1e-12 {
         cos2_sigma_m = ((2. * sigma_1) + sigma).cos();
@@ -77,21 +129,20 @@ pub fn location_and_bearing(
                             * (-3.0 + 4.0 * cos_2_sigma_m_squared)));
 
         sigma_prime = sigma;
-        sigma = (distance / (EARTH_MINOR * a_vin)) + delta_sigma;
+        sigma = (distance / (minor * a_vin)) + delta_sigma;
     }
     let x = sin_u1 * sin_sigma - cos_u1 * cos_sigma * cos_alpha1;
     let lat2 = (sin_u1 * cos_sigma + cos_u1 * sin_sigma * cos_alpha1)
-        .atan2((1. - EARTH_FLATTENING) * (sin_alpha.powf(2.) + x.powf(2.)).sqrt());
+        .atan2((1. - flattening) * (sin_alpha.powf(2.) + x.powf(2.)).sqrt());
 
     let lambda =
         (sin_sigma * sin_alpha1).atan2(cos_u1 * cos_sigma - sin_u1 * sin_sigma * cos_alpha1);
 
-    let c = EARTH_FLATTENING
-        / (16. * cos_s_q_alpha * (4.0 + EARTH_FLATTENING * (4.0 - 3.0 * cos_s_q_alpha)));
+    let c = flattening / 16. * cos_s_q_alpha * (4.0 + flattening * (4.0 - 3.0 * cos_s_q_alpha));
 
     let l = lambda
         - ((1.0 - c)
-            * EARTH_FLATTENING
+            * flattening
             * sin_alpha
             * (sigma
                 + c * sin_sigma
@@ -99,7 +150,7 @@ pub fn location_and_bearing(
 
     return LocBearing {
         lat: lat2,
-        lon: lon1 + l,
+        lon: ang_normalize(lon1 + l),
         bearing: sin_alpha.atan2(-x),
     };
 }
@@ -121,71 +172,156 @@ pub fn distance_and_bearing(
     lat2: Radians,
     lon2: Radians,
 ) -> DistBearing {
-    let l = lon2 - lon1;
+    return distance_and_bearing_on_ellipsoid(lat1, lon1, lat2, lon2, &Ellipsoid::WGS84);
+}
+
+pub fn distance_and_bearing_on_ellipsoid(
+    lat1: Radians,
+    lon1: Radians,
+    lat2: Radians,
+    lon2: Radians,
+    ellipsoid: &Ellipsoid,
+) -> DistBearing {
+    let inverse = inverse_on_ellipsoid(lat1, lon1, lat2, lon2, ellipsoid);
+
+    return DistBearing {
+        distance: inverse.distance,
+        bearing: inverse.initial_bearing,
+    };
+}
+
+pub fn inverse(lat1: Radians, lon1: Radians, lat2: Radians, lon2: Radians) -> VincentyInverse {
+    return inverse_on_ellipsoid(lat1, lon1, lat2, lon2, &Ellipsoid::WGS84);
+}
 
-    let tan_u1 = (1. - EARTH_FLATTENING) * lat1.tan();
+pub fn inverse_on_ellipsoid(
+    lat1: Radians,
+    lon1: Radians,
+    lat2: Radians,
+    lon2: Radians,
+    ellipsoid: &Ellipsoid,
+) -> VincentyInverse {
+    // Vincenty's inverse, handing over to Karney's solver when it fails to converge
+    // (which happens for nearly antipodal points)
+    if let Ok(inverse) = try_inverse_on_ellipsoid(lat1, lon1, lat2, lon2, ellipsoid) {
+        return inverse;
+    }
+
+    let karney = karney::inverse_on_ellipsoid(lat1, lon1, lat2, lon2, ellipsoid);
+
+    return VincentyInverse {
+        distance: karney.distance,
+        initial_bearing: karney.azimuth1,
+        final_bearing: karney.azimuth2,
+        iterations: 0,
+        karney_fallback: true,
+    };
+}
+
+pub fn try_inverse(
+    lat1: Radians,
+    lon1: Radians,
+    lat2: Radians,
+    lon2: Radians,
+) -> Result<VincentyInverse, VincentyError> {
+    return try_inverse_on_ellipsoid(lat1, lon1, lat2, lon2, &Ellipsoid::WGS84);
+}
+
+pub fn try_inverse_on_ellipsoid(
+    lat1: Radians,
+    lon1: Radians,
+    lat2: Radians,
+    lon2: Radians,
+    ellipsoid: &Ellipsoid,
+) -> Result<VincentyInverse, VincentyError> {
+    // Vincenty's inverse on its own, reporting an error rather than looping forever when
+    // lambda doesn't settle within MAX_ITERATIONS
+    let flattening = ellipsoid.get_flattening();
+    let major = ellipsoid.get_semi_major();
+    let minor = ellipsoid.get_semi_minor();
+
+    let l = ang_normalize(lon2 - lon1);
+
+    let tan_u1 = (1. - flattening) * lat1.tan();
     let cos_u1 = 1. / (1. + (tan_u1 * tan_u1)).sqrt();
     let sin_u1 = tan_u1 * cos_u1;
 
-    let tan_u2 = (1. - EARTH_FLATTENING) * lat2.tan();
+    let tan_u2 = (1. - flattening) * lat2.tan();
     let cos_u2 = 1. / (1. + (tan_u2 * tan_u2)).sqrt();
     let sin_u2 = tan_u2 * cos_u2;
 
     let mut lambda = l;
+    let mut iterations = 0;
 
-    let mut sin_lambda = lambda.sin();
-    let mut cos_lambda = lambda.cos();
-    let mut sin_s_q_sigma =
-        (cos_u2 * sin_lambda).powf(2.) + (cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda).powf(2.);
-    let mut sin_sigma = sin_s_q_sigma.sqrt();
-    let mut cos_sigma = (sin_u1 * sin_u2) + (cos_u1 * cos_u2 * cos_lambda);
-
-    let mut sigma = sin_sigma.atan2(cos_sigma);
-    let mut sin_alpha = (cos_u1 * cos_u2 * sin_lambda) / sin_sigma;
-    let mut cos_s_q_alpha = 1. - (sin_alpha * sin_alpha);
-    let mut cos_2_sigma_m = cos_sigma - 2. * sin_u1 * sin_u2 / cos_s_q_alpha;
-    let mut c = (EARTH_FLATTENING / 16.)
-        * (cos_s_q_alpha * (4. + EARTH_FLATTENING * (4. - 3. * cos_s_q_alpha)));
-    let mut lambda_prime = lambda;
-
-    lambda = l
-        + (1. - c)
-            * EARTH_FLATTENING
-            * sin_alpha
-            * (sigma
-                + c * sin_sigma
-                    * (cos_2_sigma_m
-                        + c * cos_sigma * (-1. + 2. * (cos_2_sigma_m * cos_2_sigma_m))));
+    let (sin_lambda, cos_lambda, sin_sigma, cos_sigma, sigma, cos_s_q_alpha, cos_2_sigma_m) = loop {
+        if iterations == MAX_ITERATIONS {
+            return Err(VincentyError::DidNotConverge(iterations));
+        }
+        iterations += 1;
 
-    while (lambda - lambda_prime).abs() > 1e-12 {
-        sin_lambda = lambda.sin();
-        cos_lambda = lambda.cos();
-        sin_s_q_sigma = (cos_u2 * sin_lambda).powf(2.)
+        let sin_lambda = lambda.sin();
+        let cos_lambda = lambda.cos();
+        let sin_s_q_sigma = (cos_u2 * sin_lambda).powf(2.)
             + (cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda).powf(2.);
-        sin_sigma = sin_s_q_sigma.sqrt();
-        cos_sigma = sin_u1 * sin_u2 + cos_u1 * cos_u2 * cos_lambda;
-
-        sigma = sin_sigma.atan2(cos_sigma);
-        sin_alpha = (cos_u1 * cos_u2 * sin_lambda) / sin_sigma;
-        cos_s_q_alpha = 1. - (sin_alpha * sin_alpha);
-        cos_2_sigma_m = cos_sigma - ((2. * sin_u1 * sin_u2) / cos_s_q_alpha);
-        c = (EARTH_FLATTENING / 16.)
-            * (cos_s_q_alpha * (4. + EARTH_FLATTENING * (4. - (3. * cos_s_q_alpha))));
-        lambda_prime = lambda;
+        let sin_sigma = sin_s_q_sigma.sqrt();
+        let cos_sigma = sin_u1 * sin_u2 + cos_u1 * cos_u2 * cos_lambda;
+
+        if sin_sigma == 0. {
+            if cos_sigma > 0. {
+                // Coincident points, where sin_alpha below would be 0/0
+                return Ok(VincentyInverse {
+                    distance: 0.,
+                    initial_bearing: 0.,
+                    final_bearing: 0.,
+                    iterations,
+                    karney_fallback: false,
+                });
+            }
+            // Exactly antipodal, every azimuth is a candidate and the iteration can't choose one
+            return Err(VincentyError::DidNotConverge(iterations));
+        }
+
+        let sigma = sin_sigma.atan2(cos_sigma);
+        let sin_alpha = (cos_u1 * cos_u2 * sin_lambda) / sin_sigma;
+        let cos_s_q_alpha = 1. - (sin_alpha * sin_alpha);
+        // On an equatorial line cos_s_q_alpha is 0 and the cos_2_sigma_m term drops out
+        let cos_2_sigma_m = if cos_s_q_alpha != 0. {
+            cos_sigma - ((2. * sin_u1 * sin_u2) / cos_s_q_alpha)
+        } else {
+            0.
+        };
+        let c =
+            (flattening / 16.) * (cos_s_q_alpha * (4. + flattening * (4. - (3. * cos_s_q_alpha))));
+        let lambda_prime = lambda;
 
         lambda = l
             + (1. - c)
-                * EARTH_FLATTENING
+                * flattening
                 * sin_alpha
                 * (sigma
                     + c * sin_sigma
                         * (cos_2_sigma_m
                             + c * cos_sigma * (-1. + 2. * (cos_2_sigma_m * cos_2_sigma_m))));
-    }
 
-    let u_s_q = cos_s_q_alpha
-        * (((EARTH_MAJOR * EARTH_MAJOR) - (EARTH_MINOR * EARTH_MINOR))
-            / (EARTH_MINOR * EARTH_MINOR));
+        // Past the antipode lambda runs away instead of converging
+        if lambda.abs() > PI {
+            return Err(VincentyError::DidNotConverge(iterations));
+        }
+
+        if (lambda - lambda_prime).abs() <= 1e-12 {
+            break (
+                lambda.sin(),
+                lambda.cos(),
+                sin_sigma,
+                cos_sigma,
+                sigma,
+                cos_s_q_alpha,
+                cos_2_sigma_m,
+            );
+        }
+    };
+
+    let u_s_q = cos_s_q_alpha * (((major * major) - (minor * minor)) / (minor * minor));
     let a = 1. + (u_s_q / 16384.0) * (4096.0 + u_s_q * (-768.0 + u_s_q * (320.0 - 175.0 * u_s_q)));
     let b = (u_s_q / 1024.0) * (256.0 + u_s_q * (-128.0 + u_s_q * (74.0 - 47.0 * u_s_q)));
     let delta_sigma = b
@@ -198,12 +334,16 @@ pub fn distance_and_bearing(
                         * (-3.0 + 4.0 * sin_sigma.powf(2.))
                         * (-3.0 + 4.0 * cos_2_sigma_m.powf(2.))));
 
-    let distance = EARTH_MINOR * a * (sigma - delta_sigma);
+    let distance = minor * a * (sigma - delta_sigma);
 
     let alpha1 = (cos_u2 * sin_lambda).atan2(cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda);
+    let alpha2 = (cos_u1 * sin_lambda).atan2(-sin_u1 * cos_u2 + cos_u1 * sin_u2 * cos_lambda);
 
-    return DistBearing {
+    return Ok(VincentyInverse {
         distance,
-        bearing: alpha1,
-    };
+        initial_bearing: alpha1,
+        final_bearing: alpha2,
+        iterations,
+        karney_fallback: false,
+    });
 }
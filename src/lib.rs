@@ -1,6 +1,14 @@
+#![allow(clippy::needless_return)]
+
 pub mod constants;
+pub mod datum;
+pub mod ellipsoid;
 pub mod geodesics;
+pub mod matrix;
+pub mod projections;
 pub mod reference_frames;
+pub mod time;
+pub mod tle;
 pub mod types;
 mod utils;
 
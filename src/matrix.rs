@@ -0,0 +1,242 @@
+// Fixed size 3x3 matrices and 3-vectors for rotating between reference frames
+// Everything is on the stack and Copy, so chains of frame conversions don't allocate
+use std::ops::{Add, Index, Mul, Neg, Sub};
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Vec3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Vec3 {
+    pub fn new(x: f64, y: f64, z: f64) -> Vec3 {
+        return Vec3 { x, y, z };
+    }
+
+    pub fn new_from_array(v: [f64; 3]) -> Vec3 {
+        return Vec3::new(v[0], v[1], v[2]);
+    }
+
+    pub fn to_array(&self) -> [f64; 3] {
+        return [self.x, self.y, self.z];
+    }
+
+    pub fn dot(&self, other: &Vec3) -> f64 {
+        return self.x * other.x + self.y * other.y + self.z * other.z;
+    }
+
+    pub fn cross(&self, other: &Vec3) -> Vec3 {
+        return Vec3 {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
+        };
+    }
+
+    pub fn norm(&self) -> f64 {
+        return self.x.hypot(self.y).hypot(self.z);
+    }
+}
+
+impl Index<usize> for Vec3 {
+    type Output = f64;
+
+    fn index(&self, i: usize) -> &f64 {
+        return match i {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("Vec3 index {} out of range", i),
+        };
+    }
+}
+
+impl Add for Vec3 {
+    type Output = Vec3;
+
+    fn add(self, other: Vec3) -> Vec3 {
+        return Vec3::new(self.x + other.x, self.y + other.y, self.z + other.z);
+    }
+}
+
+impl Sub for Vec3 {
+    type Output = Vec3;
+
+    fn sub(self, other: Vec3) -> Vec3 {
+        return Vec3::new(self.x - other.x, self.y - other.y, self.z - other.z);
+    }
+}
+
+impl Neg for Vec3 {
+    type Output = Vec3;
+
+    fn neg(self) -> Vec3 {
+        return Vec3::new(-self.x, -self.y, -self.z);
+    }
+}
+
+impl Mul<f64> for Vec3 {
+    type Output = Vec3;
+
+    fn mul(self, scale: f64) -> Vec3 {
+        return Vec3::new(self.x * scale, self.y * scale, self.z * scale);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mat3 {
+    // Row major, m[i][j] is row i and column j
+    m: [[f64; 3]; 3],
+}
+
+impl Mat3 {
+    pub fn new_from_rows(rows: [[f64; 3]; 3]) -> Mat3 {
+        return Mat3 { m: rows };
+    }
+
+    pub fn new_from_array(a: [f64; 9]) -> Mat3 {
+        // Nine elements in row major order, as the rotation matrices used to be passed around
+        return Mat3 {
+            m: [[a[0], a[1], a[2]], [a[3], a[4], a[5]], [a[6], a[7], a[8]]],
+        };
+    }
+
+    pub fn identity() -> Mat3 {
+        return Mat3::new_from_rows([[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]]);
+    }
+
+    // Rotations of the coordinate axes (not of the vector) by an angle, as in Vallado's ROT1, ROT2
+    // and ROT3. Positive angles turn the axes anticlockwise looking down the axis towards the origin
+
+    pub fn rotation_x(angle: f64) -> Mat3 {
+        let (s, c) = angle.sin_cos();
+        return Mat3::new_from_rows([[1., 0., 0.], [0., c, s], [0., -s, c]]);
+    }
+
+    pub fn rotation_y(angle: f64) -> Mat3 {
+        let (s, c) = angle.sin_cos();
+        return Mat3::new_from_rows([[c, 0., -s], [0., 1., 0.], [s, 0., c]]);
+    }
+
+    pub fn rotation_z(angle: f64) -> Mat3 {
+        let (s, c) = angle.sin_cos();
+        return Mat3::new_from_rows([[c, s, 0.], [-s, c, 0.], [0., 0., 1.]]);
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> f64 {
+        return self.m[row][column];
+    }
+
+    pub fn get_row(&self, row: usize) -> Vec3 {
+        return Vec3::new_from_array(self.m[row]);
+    }
+
+    pub fn to_array(&self) -> [f64; 9] {
+        let m = &self.m;
+        return [
+            m[0][0], m[0][1], m[0][2], m[1][0], m[1][1], m[1][2], m[2][0], m[2][1], m[2][2],
+        ];
+    }
+
+    pub fn transpose(&self) -> Mat3 {
+        let m = &self.m;
+        return Mat3::new_from_rows([
+            [m[0][0], m[1][0], m[2][0]],
+            [m[0][1], m[1][1], m[2][1]],
+            [m[0][2], m[1][2], m[2][2]],
+        ]);
+    }
+
+    pub fn transpose_times(&self, v: &Vec3) -> Vec3 {
+        // The transpose applied to a vector without forming it, i.e. the inverse of a rotation
+        let m = &self.m;
+        return Vec3 {
+            x: m[0][0] * v.x + m[1][0] * v.y + m[2][0] * v.z,
+            y: m[0][1] * v.x + m[1][1] * v.y + m[2][1] * v.z,
+            z: m[0][2] * v.x + m[1][2] * v.y + m[2][2] * v.z,
+        };
+    }
+
+    pub fn orthonormal_inverse(&self) -> Mat3 {
+        // For rotation matrices the inverse is the transpose, which is cheaper and exact
+        return self.transpose();
+    }
+
+    pub fn determinant(&self) -> f64 {
+        let m = &self.m;
+        return m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+    }
+
+    pub fn inverse(&self) -> Option<Mat3> {
+        // General inverse by the adjugate, None when the matrix is singular
+        let determinant = self.determinant();
+        if determinant == 0. || !determinant.is_finite() {
+            return None;
+        }
+        let inv_det = 1. / determinant;
+        let [[a, b, c], [d, e, f], [g, h, i]] = self.m;
+
+        return Some(Mat3::new_from_rows([
+            [
+                (e * i - f * h) * inv_det,
+                (c * h - b * i) * inv_det,
+                (b * f - c * e) * inv_det,
+            ],
+            [
+                (f * g - d * i) * inv_det,
+                (a * i - c * g) * inv_det,
+                (c * d - a * f) * inv_det,
+            ],
+            [
+                (d * h - e * g) * inv_det,
+                (b * g - a * h) * inv_det,
+                (a * e - b * d) * inv_det,
+            ],
+        ]));
+    }
+}
+
+impl Default for Mat3 {
+    fn default() -> Mat3 {
+        return Mat3::identity();
+    }
+}
+
+impl Index<(usize, usize)> for Mat3 {
+    type Output = f64;
+
+    fn index(&self, (row, column): (usize, usize)) -> &f64 {
+        return &self.m[row][column];
+    }
+}
+
+impl Mul<Vec3> for Mat3 {
+    type Output = Vec3;
+
+    fn mul(self, v: Vec3) -> Vec3 {
+        let m = &self.m;
+        return Vec3 {
+            x: m[0][0] * v.x + m[0][1] * v.y + m[0][2] * v.z,
+            y: m[1][0] * v.x + m[1][1] * v.y + m[1][2] * v.z,
+            z: m[2][0] * v.x + m[2][1] * v.y + m[2][2] * v.z,
+        };
+    }
+}
+
+impl Mul for Mat3 {
+    type Output = Mat3;
+
+    fn mul(self, other: Mat3) -> Mat3 {
+        // Composition, (a * b) * v == a * (b * v)
+        let mut m = [[0.; 3]; 3];
+        for (i, row) in m.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = (0..3).map(|k| self.m[i][k] * other.m[k][j]).sum();
+            }
+        }
+        return Mat3 { m };
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{
+    ellipsoid::Ellipsoid,
+    geodesics::{geodesic_line::GeodesicLine, karney::Geodesic},
+    types::{Metres, Radians},
+};
+
+// Newton's method on the reverse projection converges in 2 or 3 iterations except near the horizon
+const MAX_ITERATIONS: usize = 10;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Gnomonic {
+    // Karney's (2013) ellipsoidal gnomonic projection. Geodesics through the centre are straight
+    // lines, and all other geodesics are very nearly straight, which is what makes it useful for
+    // intercept and intersection problems. Only the hemisphere around the centre can be projected
+    geodesic: Geodesic,
+    major: Metres,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct GnomonicPoint {
+    pub x: Metres,
+    pub y: Metres,
+    // Azimuth of the geodesic from the centre as it arrives at the point
+    pub azimuth: Radians,
+    // Reciprocal of the azimuthal scale, the geodesic scale M12 from the centre
+    pub reciprocal_scale: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct GnomonicGeographic {
+    pub lat: Radians,
+    pub lon: Radians,
+    pub azimuth: Radians,
+    pub reciprocal_scale: f64,
+}
+
+impl Gnomonic {
+    pub fn new(ellipsoid: &Ellipsoid) -> Gnomonic {
+        return Gnomonic {
+            geodesic: Geodesic::new(ellipsoid),
+            major: ellipsoid.get_semi_major(),
+        };
+    }
+
+    pub fn forward(
+        &self,
+        lat0: Radians,
+        lon0: Radians,
+        lat: Radians,
+        lon: Radians,
+    ) -> GnomonicPoint {
+        // Given a centre and a point gives x and y, which are NaN when the point is over the horizon
+        let inverse = self.geodesic.inverse(lat0, lon0, lat, lon);
+        let scale = inverse.geodesic_scale12;
+
+        let (x, y) = if scale > 0. {
+            let rho = inverse.reduced_length / scale;
+            let (sin_azimuth, cos_azimuth) = inverse.azimuth1.sin_cos();
+            (rho * sin_azimuth, rho * cos_azimuth)
+        } else {
+            (f64::NAN, f64::NAN)
+        };
+
+        return GnomonicPoint {
+            x,
+            y,
+            azimuth: inverse.azimuth2,
+            reciprocal_scale: scale,
+        };
+    }
+
+    pub fn reverse(
+        &self,
+        lat0: Radians,
+        lon0: Radians,
+        x: Metres,
+        y: Metres,
+    ) -> GnomonicGeographic {
+        // Solves m12 / M12 = rho for the distance along the geodesic from the centre in the direction
+        // of (x, y). Far from the centre 1 / rho is solved for instead, which is better conditioned
+        let azimuth0 = x.atan2(y);
+        let mut rho = x.hypot(y);
+        let mut distance = self.major * (rho / self.major).atan();
+        let little = rho <= self.major;
+        if !little {
+            rho = 1. / rho;
+        }
+
+        let line = GeodesicLine::new(&self.geodesic, lat0, lon0, azimuth0);
+        let tolerance = 0.01 * f64::EPSILON.sqrt() * self.major;
+
+        let mut position = line.position(distance);
+        for _ in 0..MAX_ITERATIONS {
+            let m = position.reduced_length;
+            let scale = position.geodesic_scale12;
+            // d(rho)/ds = 1 / M^2 and d(1 / rho)/ds = -1 / m^2
+            let delta = if little {
+                (m - rho * scale) * scale
+            } else {
+                (rho * m - scale) * m
+            };
+            distance -= delta;
+            position = line.position(distance);
+
+            if delta.abs() < tolerance || delta.is_nan() {
+                break;
+            }
+        }
+
+        return GnomonicGeographic {
+            lat: position.lat,
+            lon: position.lon,
+            azimuth: position.azimuth,
+            reciprocal_scale: position.geodesic_scale12,
+        };
+    }
+}
@@ -0,0 +1,384 @@
+use std::{fmt, str::FromStr};
+
+use crate::{reference_frames::wgs84::WGS84Coord, types::Metres};
+
+use super::utm::{forward, Hemisphere, UTMCoord, UTMError, UPS_ZONE};
+
+// Letter sets from NGA.SIG.0012 (the same tables GeographicLib's MGRS class uses)
+const LATITUDE_BANDS: &[u8] = b"CDEFGHJKLMNPQRSTUVWX";
+const UPS_BANDS: &[u8] = b"ABYZ";
+const UTM_COLUMNS: [&[u8]; 3] = [b"ABCDEFGH", b"JKLMNPQR", b"STUVWXYZ"];
+const UTM_ROWS: &[u8] = b"ABCDEFGHJKLMNPQRSTUV";
+// Indexed by UPS_BANDS position: A (south west), B (south east), Y (north west), Z (north east)
+const UPS_COLUMNS: [&[u8]; 4] = [b"JKLPQRSTUXYZ", b"ABCFGHJKLPQR", b"RSTUXYZ", b"ABCFGHJ"];
+const UPS_ROWS: [&[u8]; 2] = [b"ABCDEFGHJKLMNPQRSTUVWXYZ", b"ABCDEFGHJKLMNP"];
+
+const TILE: Metres = 100e3;
+const UTM_ROW_PERIOD: i32 = 20;
+const UTM_EVEN_ROW_SHIFT: i32 = 5;
+// Row index (in 100 km tiles) returned by utm_row when the letters don't fit the band
+const INVALID_ROW: i32 = 100;
+// First 100 km column / row index covered by each UPS letter set
+const UPS_EASTING_SPLIT: i32 = 20;
+const UPS_SOUTH_MIN: i32 = 8;
+const UPS_NORTH_MIN: i32 = 13;
+
+pub const MAX_PRECISION: u8 = 5;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MGRSError {
+    // Digits per coordinate must be 0 (100 km) to 5 (1 m)
+    InvalidPrecision(u8),
+    InvalidGridZone(String),
+    InvalidColumn(char),
+    InvalidRow(char),
+    // 100 km square letters that are valid but don't exist in the given grid zone
+    SquareNotInZone(String),
+    InvalidDigits(String),
+    Projection(UTMError),
+}
+
+impl fmt::Display for MGRSError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MGRSError::InvalidPrecision(p) => write!(f, "precision {} not in [0, 5]", p),
+            MGRSError::InvalidGridZone(gzd) => write!(f, "invalid grid zone designator {}", gzd),
+            MGRSError::InvalidColumn(c) => write!(f, "column letter {} not valid here", c),
+            MGRSError::InvalidRow(c) => write!(f, "row letter {} not valid here", c),
+            MGRSError::SquareNotInZone(sq) => write!(f, "100 km square {} not in zone", sq),
+            MGRSError::InvalidDigits(d) => write!(f, "invalid numerical location {}", d),
+            MGRSError::Projection(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for MGRSError {}
+
+impl From<UTMError> for MGRSError {
+    fn from(err: UTMError) -> Self {
+        return MGRSError::Projection(err);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MGRSCoord {
+    // An MGRS reference is a cell, stored as its south west corner and its size
+    corner: UTMCoord,
+    band: char,
+    precision: u8,
+}
+
+impl MGRSCoord {
+    pub fn new_from_wgs84(coord: &WGS84Coord, precision: u8) -> Result<MGRSCoord, MGRSError> {
+        // Given a WGS84Coord gives the cell of the given precision containing it
+        // Precision is digits per coordinate: 0 for 100 km squares up to 5 for 1 m
+        if precision > MAX_PRECISION {
+            return Err(MGRSError::InvalidPrecision(precision));
+        }
+
+        let utm = forward(coord, None)?.utm;
+        let cell = cell_size(precision);
+        // MGRS truncates rather than rounds
+        let easting = (utm.get_easting() / cell).floor() * cell;
+        let northing = (utm.get_northing() / cell).floor() * cell;
+
+        let band = if utm.is_ups() {
+            UPS_BANDS[ups_band_index(&utm)] as char
+        } else {
+            let lat = coord.get_lat_degrees();
+            // Exactly on the equator a south hemisphere point belongs to band M
+            let band = if lat == 0. {
+                if utm.get_hemisphere() == Hemisphere::North {
+                    0
+                } else {
+                    -1
+                }
+            } else {
+                latitude_band(lat)
+            };
+            LATITUDE_BANDS[(band + 10) as usize] as char
+        };
+
+        return Ok(MGRSCoord {
+            corner: UTMCoord::new(utm.get_zone(), utm.get_hemisphere(), easting, northing)?,
+            band,
+            precision,
+        });
+    }
+
+    pub fn new_from_str(mgrs: &str) -> Result<MGRSCoord, MGRSError> {
+        // Accepts MGRS or USNG strings, with or without spaces and leading zero on the zone
+        let mgrs: Vec<u8> = mgrs
+            .bytes()
+            .filter(|c| !c.is_ascii_whitespace())
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+
+        let zone_digits = mgrs.iter().take_while(|c| c.is_ascii_digit()).count();
+        let invalid_gzd = || {
+            let end = (zone_digits + 1).min(mgrs.len());
+            MGRSError::InvalidGridZone(String::from_utf8_lossy(&mgrs[..end]).into_owned())
+        };
+
+        if zone_digits > 2 || mgrs.len() < zone_digits + 1 {
+            return Err(invalid_gzd());
+        }
+        let zone = if zone_digits == 0 {
+            UPS_ZONE
+        } else {
+            let zone: u8 = std::str::from_utf8(&mgrs[..zone_digits])
+                .unwrap()
+                .parse()
+                .unwrap();
+            if !(1..=60).contains(&zone) {
+                return Err(invalid_gzd());
+            }
+            zone
+        };
+
+        let band = mgrs[zone_digits];
+        let rest = &mgrs[zone_digits + 1..];
+        if rest.len() < 2 {
+            return Err(invalid_gzd());
+        }
+        let square = String::from_utf8_lossy(&mgrs[..zone_digits + 3]).into_owned();
+        let (col, row) = (rest[0], rest[1]);
+
+        // The 100 km square gives the south west corner of its tile
+        let (hemisphere, tile_col, tile_row) = if zone == UPS_ZONE {
+            let band_index = UPS_BANDS
+                .iter()
+                .position(|&c| c == band)
+                .ok_or_else(invalid_gzd)?;
+            let north = band_index >= 2;
+            let east = band_index % 2 == 1;
+            let col = UPS_COLUMNS[band_index]
+                .iter()
+                .position(|&c| c == col)
+                .ok_or(MGRSError::InvalidColumn(col as char))? as i32;
+            let row = UPS_ROWS[north as usize]
+                .iter()
+                .position(|&c| c == row)
+                .ok_or(MGRSError::InvalidRow(row as char))? as i32;
+
+            let col_offset = if east {
+                UPS_EASTING_SPLIT
+            } else if north {
+                UPS_NORTH_MIN
+            } else {
+                UPS_SOUTH_MIN
+            };
+            let row_offset = if north { UPS_NORTH_MIN } else { UPS_SOUTH_MIN };
+            let hemisphere = if north {
+                Hemisphere::North
+            } else {
+                Hemisphere::South
+            };
+            (hemisphere, col + col_offset, row + row_offset)
+        } else {
+            let band_index = LATITUDE_BANDS
+                .iter()
+                .position(|&c| c == band)
+                .ok_or_else(invalid_gzd)? as i32
+                - 10;
+            let zone_index = (zone - 1) as usize;
+            let col = UTM_COLUMNS[zone_index % 3]
+                .iter()
+                .position(|&c| c == col)
+                .ok_or(MGRSError::InvalidColumn(col as char))? as i32;
+            let mut row = UTM_ROWS
+                .iter()
+                .position(|&c| c == row)
+                .ok_or(MGRSError::InvalidRow(row as char))? as i32;
+            if zone_index % 2 == 1 {
+                row = (row + UTM_ROW_PERIOD - UTM_EVEN_ROW_SHIFT) % UTM_ROW_PERIOD;
+            }
+
+            let row = utm_row(band_index, col, row);
+            if row == INVALID_ROW {
+                return Err(MGRSError::SquareNotInZone(square));
+            }
+
+            if band_index >= 0 {
+                (Hemisphere::North, col + 1, row)
+            } else {
+                (Hemisphere::South, col + 1, row + 100)
+            }
+        };
+
+        let digits = &rest[2..];
+        if digits.len() % 2 == 1
+            || digits.len() > 2 * MAX_PRECISION as usize
+            || !digits.iter().all(|c| c.is_ascii_digit())
+        {
+            return Err(MGRSError::InvalidDigits(
+                String::from_utf8_lossy(digits).into_owned(),
+            ));
+        }
+
+        let precision = (digits.len() / 2) as u8;
+        let cell = cell_size(precision);
+        let parse = |d: &[u8]| -> f64 {
+            if d.is_empty() {
+                return 0.;
+            }
+            return std::str::from_utf8(d).unwrap().parse::<f64>().unwrap();
+        };
+        let easting = tile_col as f64 * TILE + parse(&digits[..precision as usize]) * cell;
+        let northing = tile_row as f64 * TILE + parse(&digits[precision as usize..]) * cell;
+
+        return Ok(MGRSCoord {
+            corner: UTMCoord::new(zone, hemisphere, easting, northing)?,
+            band: band as char,
+            precision,
+        });
+    }
+
+    pub fn get_precision(&self) -> u8 {
+        return self.precision;
+    }
+
+    pub fn get_cell_size(&self) -> Metres {
+        return cell_size(self.precision);
+    }
+
+    pub fn get_corner(&self) -> UTMCoord {
+        // South west corner of the cell
+        return self.corner;
+    }
+
+    pub fn get_centre(&self) -> UTMCoord {
+        let half = self.get_cell_size() / 2.;
+        // The centre of a cell is always inside the range the corner was validated against
+        return UTMCoord::new(
+            self.corner.get_zone(),
+            self.corner.get_hemisphere(),
+            self.corner.get_easting() + half,
+            self.corner.get_northing() + half,
+        )
+        .unwrap();
+    }
+
+    pub fn to_wgs84_corner(&self) -> WGS84Coord {
+        return WGS84Coord::new_from_utm(&self.corner);
+    }
+
+    pub fn to_wgs84_centre(&self) -> WGS84Coord {
+        return WGS84Coord::new_from_utm(&self.get_centre());
+    }
+}
+
+impl fmt::Display for MGRSCoord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let utm = &self.corner;
+        let col = (utm.get_easting() / TILE).floor() as i32;
+        let row = (utm.get_northing() / TILE).floor() as i32;
+
+        let (col_letter, row_letter) = if utm.is_ups() {
+            let band_index = ups_band_index(utm);
+            let north = utm.get_hemisphere() == Hemisphere::North;
+            let col_offset = if band_index % 2 == 1 {
+                UPS_EASTING_SPLIT
+            } else if north {
+                UPS_NORTH_MIN
+            } else {
+                UPS_SOUTH_MIN
+            };
+            let row_offset = if north { UPS_NORTH_MIN } else { UPS_SOUTH_MIN };
+            (
+                UPS_COLUMNS[band_index][(col - col_offset) as usize],
+                UPS_ROWS[north as usize][(row - row_offset) as usize],
+            )
+        } else {
+            let zone_index = (utm.get_zone() - 1) as i32;
+            let shift = if zone_index % 2 == 1 {
+                UTM_EVEN_ROW_SHIFT
+            } else {
+                0
+            };
+            (
+                UTM_COLUMNS[(zone_index % 3) as usize][(col - 1) as usize],
+                UTM_ROWS[((row + shift) % UTM_ROW_PERIOD) as usize],
+            )
+        };
+
+        if !utm.is_ups() {
+            write!(f, "{:02}", utm.get_zone())?;
+        }
+        write!(
+            f,
+            "{}{}{}",
+            self.band, col_letter as char, row_letter as char
+        )?;
+
+        if self.precision > 0 {
+            let cell = self.get_cell_size();
+            let width = self.precision as usize;
+            let easting = ((utm.get_easting() - col as f64 * TILE) / cell).round() as u32;
+            let northing = ((utm.get_northing() - row as f64 * TILE) / cell).round() as u32;
+            write!(f, "{:0w$}{:0w$}", easting, northing, w = width)?;
+        }
+        return Ok(());
+    }
+}
+
+impl FromStr for MGRSCoord {
+    type Err = MGRSError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return MGRSCoord::new_from_str(s);
+    }
+}
+
+fn cell_size(precision: u8) -> Metres {
+    return 10_f64.powi(5 - precision as i32);
+}
+
+fn latitude_band(lat: f64) -> i32 {
+    // 8 degree bands C to X, with X stretched to 84 degrees
+    return ((lat / 8.).floor() as i32).clamp(-10, 9);
+}
+
+fn ups_band_index(utm: &UTMCoord) -> usize {
+    let east = (utm.get_easting() / TILE).floor() as i32 >= UPS_EASTING_SPLIT;
+    let north = utm.get_hemisphere() == Hemisphere::North;
+    return east as usize + if north { 2 } else { 0 };
+}
+
+fn utm_row(band: i32, col: i32, row: i32) -> i32 {
+    // Given a latitude band, a column index (0 = 100 km easting) and a row letter index in [0, 20)
+    // gives the true row index (100 km northing rows, negative in the south) or INVALID_ROW
+    // Adapted from GeographicLib's MGRS::UTMRow
+    let c = 100. * (8. * band as f64 + 4.) / 90.;
+    let north = if band >= 0 { 1. } else { 0. };
+    let min_row = if band > -10 {
+        (c - 4.3 - 0.1 * north).floor() as i32
+    } else {
+        -90
+    };
+    let max_row = if band < 9 {
+        (c + 4.4 - 0.1 * north).floor() as i32
+    } else {
+        94
+    };
+    let base_row = (min_row + max_row) / 2 - UTM_ROW_PERIOD / 2;
+    // Bring the row as close as possible to the centre of the band
+    let row = (row - base_row + INVALID_ROW) % UTM_ROW_PERIOD + base_row;
+
+    if row >= min_row && row <= max_row {
+        return row;
+    }
+
+    // Band edges aren't parallel to the rows, so a few squares straddle two bands
+    let s_band = if band >= 0 { band } else { -band - 1 };
+    let s_row = if row >= 0 { row } else { -row - 1 };
+    let s_col = if col < 4 { col } else { -col + 7 };
+    if (s_row == 70 && s_band == 8 && s_col >= 2)
+        || (s_row == 71 && s_band == 7 && s_col <= 2)
+        || (s_row == 79 && s_band == 9 && s_col >= 1)
+        || (s_row == 80 && s_band == 8 && s_col <= 1)
+    {
+        return row;
+    }
+    return INVALID_ROW;
+}
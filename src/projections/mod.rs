@@ -0,0 +1,5 @@
+pub mod gnomonic;
+pub mod mgrs;
+pub mod polar_stereographic;
+pub mod transverse_mercator;
+pub mod utm;
@@ -0,0 +1,113 @@
+use std::f64::consts::FRAC_PI_2;
+
+use crate::{
+    ellipsoid::Ellipsoid,
+    types::{Metres, Radians},
+    utils::math::{ang_normalize, eatanhe, tauf, taupf},
+};
+
+#[derive(Debug, Clone)]
+pub struct PolarStereographic {
+    // Ellipsoidal polar stereographic projection with scale k0 at the pole
+    major: Metres,
+    e2: f64,
+    es: f64,
+    e2m: f64,
+    c: f64,
+    k0: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PSPoint {
+    pub x: Metres,
+    pub y: Metres,
+    pub convergence: Radians,
+    pub scale: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PSGeographic {
+    pub lat: Radians,
+    pub lon: Radians,
+    pub convergence: Radians,
+    pub scale: f64,
+}
+
+impl PolarStereographic {
+    pub fn new(ellipsoid: &Ellipsoid, k0: f64) -> PolarStereographic {
+        let e2 = ellipsoid.get_eccentricity_squared();
+        let es = e2.sqrt();
+
+        return PolarStereographic {
+            major: ellipsoid.get_semi_major(),
+            e2,
+            es,
+            e2m: 1. - e2,
+            c: (1. - ellipsoid.get_flattening()) * eatanhe(1., es).exp(),
+            k0,
+        };
+    }
+
+    pub fn forward(&self, north: bool, lat: Radians, lon: Radians) -> PSPoint {
+        // x and y are relative to the pole, without false easting or northing
+        let lat = if north { lat } else { -lat };
+        let tau = lat.tan();
+        let sec_lat = tau.hypot(1.);
+        let taup = taupf(tau, self.es);
+
+        let mut rho = taup.hypot(1.) + taup.abs();
+        rho = if taup >= 0. {
+            if lat < FRAC_PI_2 {
+                1. / rho
+            } else {
+                0.
+            }
+        } else {
+            rho
+        };
+        rho *= 2. * self.k0 * self.major / self.c;
+
+        let scale = if lat < FRAC_PI_2 {
+            (rho / self.major) * sec_lat * (self.e2m + self.e2 / (sec_lat * sec_lat)).sqrt()
+        } else {
+            self.k0
+        };
+
+        let (sin_lon, cos_lon) = lon.sin_cos();
+
+        return PSPoint {
+            x: rho * sin_lon,
+            y: if north { -rho * cos_lon } else { rho * cos_lon },
+            convergence: ang_normalize(if north { lon } else { -lon }),
+            scale,
+        };
+    }
+
+    pub fn reverse(&self, north: bool, x: Metres, y: Metres) -> PSGeographic {
+        let rho = x.hypot(y);
+        let t = if rho != 0. {
+            rho / (2. * self.k0 * self.major / self.c)
+        } else {
+            f64::EPSILON * f64::EPSILON
+        };
+        let taup = (1. / t - t) / 2.;
+        let tau = tauf(taup, self.es);
+        let sec_lat = tau.hypot(1.);
+
+        let scale = if rho != 0. {
+            (rho / self.major) * sec_lat * (self.e2m + self.e2 / (sec_lat * sec_lat)).sqrt()
+        } else {
+            self.k0
+        };
+
+        let lat = tau.atan();
+        let lon = x.atan2(if north { -y } else { y });
+
+        return PSGeographic {
+            lat: if north { lat } else { -lat },
+            lon,
+            convergence: ang_normalize(if north { lon } else { -lon }),
+            scale,
+        };
+    }
+}
@@ -0,0 +1,279 @@
+use std::f64::consts::FRAC_PI_2;
+
+use crate::{
+    ellipsoid::Ellipsoid,
+    types::{Metres, Radians},
+    utils::math::{ang_normalize, eatanhe, polyval, tauf, taupf},
+};
+
+// Krueger's series to sixth order in the third flattening, as in Karney (2011)
+// "Transverse Mercator with an accuracy of a few nanometers", J. Geodesy 85(8)
+// Accurate to about 5 nm within 3900 km of the central meridian
+const ORDER: usize = 6;
+
+const ALPHA_COEFFS: [&[f64]; ORDER] = [
+    &[31564., -66675., 34440., 47250., -100800., 75600., 151200.],
+    &[-1983433., 863232., 748608., -1161216., 524160., 1935360.],
+    &[670412., 406647., -533952., 184464., 725760.],
+    &[6601661., -7732800., 2230245., 7257600.],
+    &[-13675556., 3438171., 7983360.],
+    &[212378941., 319334400.],
+];
+
+const BETA_COEFFS: [&[f64]; ORDER] = [
+    &[
+        384796., -382725., -6720., 932400., -1612800., 1209600., 2419200.,
+    ],
+    &[-1118711., 1695744., -1174656., 258048., 80640., 3870720.],
+    &[22276., -16929., -15984., 12852., 362880.],
+    &[-830251., -158400., 197865., 7257600.],
+    &[-435388., 453717., 15966720.],
+    &[20648693., 638668800.],
+];
+
+#[derive(Debug, Clone)]
+pub struct TransverseMercator {
+    // Series coefficients for one ellipsoid and central scale factor
+    k0: f64,
+    e2: f64,
+    es: f64,
+    e2m: f64,
+    // Rectifying radius * k0
+    a1_k0: Metres,
+    b1: f64,
+    // Scale at the pole
+    c: f64,
+    alpha: [f64; ORDER + 1],
+    beta: [f64; ORDER + 1],
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TMPoint {
+    pub x: Metres,
+    pub y: Metres,
+    // Meridian convergence, clockwise from true north to grid north
+    pub convergence: Radians,
+    pub scale: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TMGeographic {
+    pub lat: Radians,
+    pub lon: Radians,
+    pub convergence: Radians,
+    pub scale: f64,
+}
+
+impl TransverseMercator {
+    pub fn new(ellipsoid: &Ellipsoid, k0: f64) -> TransverseMercator {
+        let n = ellipsoid.get_third_flattening();
+        let e2 = ellipsoid.get_eccentricity_squared();
+        let es = e2.sqrt();
+
+        let n2 = n * n;
+        let b1 = polyval(&[1., 4., 64., 256.], n2) / 256. / (1. + n);
+
+        let mut alpha = [0_f64; ORDER + 1];
+        let mut beta = [0_f64; ORDER + 1];
+        let mut d = n;
+        for l in 0..ORDER {
+            let alpha_coeffs = ALPHA_COEFFS[l];
+            let beta_coeffs = BETA_COEFFS[l];
+            let m = alpha_coeffs.len() - 1;
+            alpha[l + 1] = d * polyval(&alpha_coeffs[..m], n) / alpha_coeffs[m];
+            beta[l + 1] = d * polyval(&beta_coeffs[..m], n) / beta_coeffs[m];
+            d *= n;
+        }
+
+        return TransverseMercator {
+            k0,
+            e2,
+            es,
+            e2m: 1. - e2,
+            a1_k0: b1 * ellipsoid.get_semi_major() * k0,
+            b1,
+            c: (1. - e2).sqrt() * eatanhe(1., es).exp(),
+            alpha,
+            beta,
+        };
+    }
+
+    pub fn forward(&self, lon0: Radians, lat: Radians, lon: Radians) -> TMPoint {
+        // Given a central meridian, a latitude and a longitude gives x (easting) and y (northing)
+        // without false easting or northing
+        let mut lat_sign = if lat.is_sign_negative() { -1. } else { 1. };
+        let mut lon = ang_normalize(lon - lon0);
+        let lon_sign = if lon.is_sign_negative() { -1. } else { 1. };
+        let lat = lat * lat_sign;
+        lon *= lon_sign;
+
+        let backside = lon > FRAC_PI_2;
+        if backside {
+            if lat == 0. {
+                lat_sign = -1.;
+            }
+            lon = std::f64::consts::PI - lon;
+        }
+
+        let (sin_lat, cos_lat) = lat.sin_cos();
+        let (sin_lon, cos_lon) = lon.sin_cos();
+
+        let xip;
+        let etap;
+        let mut gamma;
+        let mut k;
+        if lat < FRAC_PI_2 {
+            let tau = sin_lat / cos_lat;
+            let taup = taupf(tau, self.es);
+            xip = taup.atan2(cos_lon);
+            etap = (sin_lon / taup.hypot(cos_lon)).asinh();
+            gamma = (sin_lon * taup).atan2(cos_lon * taup.hypot(1.));
+            k = (self.e2m + self.e2 * cos_lat * cos_lat).sqrt() * tau.hypot(1.)
+                / taup.hypot(cos_lon);
+        } else {
+            xip = FRAC_PI_2;
+            etap = 0.;
+            gamma = lon;
+            k = self.c;
+        }
+
+        // Clenshaw summation of the complex series in zeta' = xi' + i eta'
+        let c0 = (2. * xip).cos();
+        let ch0 = (2. * etap).cosh();
+        let s0 = (2. * xip).sin();
+        let sh0 = (2. * etap).sinh();
+        let a = (2. * c0 * ch0, -2. * s0 * sh0);
+
+        let mut y0 = (0_f64, 0_f64);
+        let mut y1 = (0_f64, 0_f64);
+        let mut z0 = (0_f64, 0_f64);
+        let mut z1 = (0_f64, 0_f64);
+        let mut j = ORDER;
+        while j > 0 {
+            y1 = complex_sub(complex_mul(a, y0), y1);
+            y1.0 += self.alpha[j];
+            z1 = complex_sub(complex_mul(a, z0), z1);
+            z1.0 += 2. * j as f64 * self.alpha[j];
+            j -= 1;
+            y0 = complex_sub(complex_mul(a, y1), y0);
+            y0.0 += self.alpha[j];
+            z0 = complex_sub(complex_mul(a, z1), z0);
+            z0.0 += 2. * j as f64 * self.alpha[j];
+            j -= 1;
+        }
+
+        let a = (a.0 / 2., a.1 / 2.);
+        let z1 = complex_sub((1., 0.), complex_sub(z1, complex_mul(a, z0)));
+        let a = (s0 * ch0, c0 * sh0);
+        let y1 = complex_mul(a, y0);
+        let xi = xip + y1.0;
+        let eta = etap + y1.1;
+
+        gamma -= z1.1.atan2(z1.0);
+        k *= self.b1 * z1.0.hypot(z1.1);
+
+        let northing = if backside {
+            std::f64::consts::PI - xi
+        } else {
+            xi
+        };
+        if backside {
+            gamma = std::f64::consts::PI - gamma;
+        }
+
+        return TMPoint {
+            x: self.a1_k0 * eta * lon_sign,
+            y: self.a1_k0 * northing * lat_sign,
+            convergence: ang_normalize(gamma * lat_sign * lon_sign),
+            scale: k * self.k0,
+        };
+    }
+
+    pub fn reverse(&self, lon0: Radians, x: Metres, y: Metres) -> TMGeographic {
+        // Inverse of forward; x and y must already have any false easting and northing removed
+        let mut xi = y / self.a1_k0;
+        let mut eta = x / self.a1_k0;
+        let xi_sign = if xi.is_sign_negative() { -1. } else { 1. };
+        let eta_sign = if eta.is_sign_negative() { -1. } else { 1. };
+        xi *= xi_sign;
+        eta *= eta_sign;
+
+        let backside = xi > FRAC_PI_2;
+        if backside {
+            xi = std::f64::consts::PI - xi;
+        }
+
+        let c0 = (2. * xi).cos();
+        let ch0 = (2. * eta).cosh();
+        let s0 = (2. * xi).sin();
+        let sh0 = (2. * eta).sinh();
+        let a = (2. * c0 * ch0, -2. * s0 * sh0);
+
+        let mut y0 = (0_f64, 0_f64);
+        let mut y1 = (0_f64, 0_f64);
+        let mut z0 = (0_f64, 0_f64);
+        let mut z1 = (0_f64, 0_f64);
+        let mut j = ORDER;
+        while j > 0 {
+            y1 = complex_sub(complex_mul(a, y0), y1);
+            y1.0 -= self.beta[j];
+            z1 = complex_sub(complex_mul(a, z0), z1);
+            z1.0 -= 2. * j as f64 * self.beta[j];
+            j -= 1;
+            y0 = complex_sub(complex_mul(a, y1), y0);
+            y0.0 -= self.beta[j];
+            z0 = complex_sub(complex_mul(a, z1), z0);
+            z0.0 -= 2. * j as f64 * self.beta[j];
+            j -= 1;
+        }
+
+        let a = (a.0 / 2., a.1 / 2.);
+        let z1 = complex_sub((1., 0.), complex_sub(z1, complex_mul(a, z0)));
+        let a = (s0 * ch0, c0 * sh0);
+        let y1 = complex_mul(a, y0);
+        let xip = xi + y1.0;
+        let etap = eta + y1.1;
+
+        let mut gamma = z1.1.atan2(z1.0);
+        let mut k = self.b1 / z1.0.hypot(z1.1);
+
+        let s = etap.sinh();
+        let c = xip.cos().max(0.);
+        let r = s.hypot(c);
+
+        let lat;
+        let mut lon;
+        if r != 0. {
+            lon = s.atan2(c);
+            let sin_xip = xip.sin();
+            let tau = tauf(sin_xip / r, self.es);
+            gamma += (sin_xip * etap.tanh()).atan2(c);
+            lat = tau.atan();
+            k *= (self.e2m + self.e2 / (1. + tau * tau)).sqrt() * tau.hypot(1.) * r;
+        } else {
+            lat = FRAC_PI_2;
+            lon = 0.;
+            k *= self.c;
+        }
+
+        if backside {
+            lon = std::f64::consts::PI - lon;
+            gamma = std::f64::consts::PI - gamma;
+        }
+
+        return TMGeographic {
+            lat: lat * xi_sign,
+            lon: ang_normalize(lon * eta_sign + lon0),
+            convergence: ang_normalize(gamma * xi_sign * eta_sign),
+            scale: k * self.k0,
+        };
+    }
+}
+
+fn complex_mul(a: (f64, f64), b: (f64, f64)) -> (f64, f64) {
+    return (a.0 * b.0 - a.1 * b.1, a.0 * b.1 + a.1 * b.0);
+}
+
+fn complex_sub(a: (f64, f64), b: (f64, f64)) -> (f64, f64) {
+    return (a.0 - b.0, a.1 - b.1);
+}
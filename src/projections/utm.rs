@@ -0,0 +1,258 @@
+use std::fmt;
+
+use crate::{
+    ellipsoid::Ellipsoid,
+    reference_frames::wgs84::WGS84Coord,
+    types::{Degrees, Metres, Radians},
+};
+
+use super::{polar_stereographic::PolarStereographic, transverse_mercator::TransverseMercator};
+
+pub const UTM_SCALE: f64 = 0.9996;
+pub const UPS_SCALE: f64 = 0.994;
+pub const UTM_FALSE_EASTING: Metres = 500e3;
+pub const UTM_FALSE_NORTHING_SOUTH: Metres = 10000e3;
+pub const UPS_FALSE_EASTING_NORTHING: Metres = 2000e3;
+
+// Zone number used for Universal Polar Stereographic
+pub const UPS_ZONE: u8 = 0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hemisphere {
+    North,
+    South,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UTMError {
+    InvalidLatitude(Degrees),
+    InvalidZone(u8),
+    EastingOutOfRange(Metres),
+    NorthingOutOfRange(Metres),
+}
+
+impl fmt::Display for UTMError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UTMError::InvalidLatitude(lat) => write!(f, "latitude {} not in [-90, 90]", lat),
+            UTMError::InvalidZone(zone) => write!(f, "zone {} not in [0, 60]", zone),
+            UTMError::EastingOutOfRange(e) => write!(f, "easting {} out of range", e),
+            UTMError::NorthingOutOfRange(n) => write!(f, "northing {} out of range", n),
+        }
+    }
+}
+
+impl std::error::Error for UTMError {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UTMCoord {
+    // Zone 1-60 for UTM, UPS_ZONE for the polar regions
+    zone: u8,
+    hemisphere: Hemisphere,
+    easting: Metres,
+    northing: Metres,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct UTMForward {
+    pub utm: UTMCoord,
+    // Clockwise angle from true north to grid north
+    pub convergence: Radians,
+    pub scale: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct UTMReverse {
+    pub coord: WGS84Coord,
+    pub convergence: Radians,
+    pub scale: f64,
+}
+
+impl UTMCoord {
+    pub fn new(
+        zone: u8,
+        hemisphere: Hemisphere,
+        easting: Metres,
+        northing: Metres,
+    ) -> Result<UTMCoord, UTMError> {
+        // Ranges are the usual UTM / UPS limits with 100 km of slop for points just over a zone edge
+        if zone > 60 {
+            return Err(UTMError::InvalidZone(zone));
+        }
+
+        let (easting_range, northing_range) = if zone == UPS_ZONE {
+            ((0., 4000e3), (0., 4000e3))
+        } else if hemisphere == Hemisphere::North {
+            ((0., 1000e3), (-100e3, 9600e3))
+        } else {
+            ((0., 1000e3), (900e3, 10000e3))
+        };
+
+        if !(easting >= easting_range.0 && easting <= easting_range.1) {
+            return Err(UTMError::EastingOutOfRange(easting));
+        }
+        if !(northing >= northing_range.0 && northing <= northing_range.1) {
+            return Err(UTMError::NorthingOutOfRange(northing));
+        }
+
+        return Ok(UTMCoord {
+            zone,
+            hemisphere,
+            easting,
+            northing,
+        });
+    }
+
+    pub fn new_from_wgs84(coord: &WGS84Coord) -> Result<UTMCoord, UTMError> {
+        // Uses the standard zone, including the Norway and Svalbard exceptions and UPS near the poles
+        return Ok(forward(coord, None)?.utm);
+    }
+
+    pub fn new_from_wgs84_zone(coord: &WGS84Coord, zone: u8) -> Result<UTMCoord, UTMError> {
+        // Forces a zone, e.g. to keep a survey that straddles a zone boundary on one grid
+        return Ok(forward(coord, Some(zone))?.utm);
+    }
+
+    pub fn get_zone(&self) -> u8 {
+        return self.zone;
+    }
+
+    pub fn get_hemisphere(&self) -> Hemisphere {
+        return self.hemisphere;
+    }
+
+    pub fn get_easting(&self) -> Metres {
+        return self.easting;
+    }
+
+    pub fn get_northing(&self) -> Metres {
+        return self.northing;
+    }
+
+    pub fn is_ups(&self) -> bool {
+        return self.zone == UPS_ZONE;
+    }
+}
+
+pub fn standard_zone(coord: &WGS84Coord) -> Result<u8, UTMError> {
+    // Given a WGS84Coord gives the zone it would conventionally be projected in
+    let lat = coord.get_lat_degrees();
+    if !(-90. ..=90.).contains(&lat) {
+        return Err(UTMError::InvalidLatitude(lat));
+    }
+    if !(-80. ..84.).contains(&lat) {
+        return Ok(UPS_ZONE);
+    }
+
+    let mut lon = coord.get_lon_degrees().floor() as i32 % 360;
+    if lon >= 180 {
+        lon -= 360;
+    } else if lon < -180 {
+        lon += 360;
+    }
+    let mut zone = (lon + 186) / 6;
+
+    // 8 degree latitude bands as used by MGRS, C = -80 to -72 up to X = 72 to 84
+    let band = ((lat / 8.).floor() as i32).clamp(-10, 9);
+    if band == 7 && zone == 31 && lon >= 3 {
+        // Norway: 32V is widened west to cover the whole south west coast
+        zone = 32;
+    } else if band == 9 && (0..42).contains(&lon) {
+        // Svalbard: only 31X, 33X, 35X and 37X are used
+        zone = 2 * ((lon + 183) / 12) + 1;
+    }
+
+    return Ok(zone as u8);
+}
+
+pub fn forward(coord: &WGS84Coord, zone: Option<u8>) -> Result<UTMForward, UTMError> {
+    // Given a WGS84Coord (and optionally a zone to force) projects onto UTM or UPS
+    let lat = coord.get_lat_radians();
+    let lat_degrees = coord.get_lat_degrees();
+    if !(-90. ..=90.).contains(&lat_degrees) {
+        return Err(UTMError::InvalidLatitude(lat_degrees));
+    }
+
+    let zone = match zone {
+        Some(zone) => zone,
+        None => standard_zone(coord)?,
+    };
+    if zone > 60 {
+        return Err(UTMError::InvalidZone(zone));
+    }
+
+    let hemisphere = if lat_degrees >= 0. {
+        Hemisphere::North
+    } else {
+        Hemisphere::South
+    };
+
+    let (easting, northing, convergence, scale) = if zone == UPS_ZONE {
+        let ups = PolarStereographic::new(&Ellipsoid::WGS84, UPS_SCALE);
+        let point = ups.forward(
+            hemisphere == Hemisphere::North,
+            lat,
+            coord.get_lon_radians(),
+        );
+        (
+            point.x + UPS_FALSE_EASTING_NORTHING,
+            point.y + UPS_FALSE_EASTING_NORTHING,
+            point.convergence,
+            point.scale,
+        )
+    } else {
+        let tm = TransverseMercator::new(&Ellipsoid::WGS84, UTM_SCALE);
+        let point = tm.forward(central_meridian(zone), lat, coord.get_lon_radians());
+        let false_northing = if hemisphere == Hemisphere::North {
+            0.
+        } else {
+            UTM_FALSE_NORTHING_SOUTH
+        };
+        (
+            point.x + UTM_FALSE_EASTING,
+            point.y + false_northing,
+            point.convergence,
+            point.scale,
+        )
+    };
+
+    return Ok(UTMForward {
+        utm: UTMCoord::new(zone, hemisphere, easting, northing)?,
+        convergence,
+        scale,
+    });
+}
+
+pub fn reverse(utm: &UTMCoord) -> UTMReverse {
+    // Given a UTM or UPS coordinate gives the WGS84Coord (at zero altitude) with the grid convergence and scale
+    let north = utm.hemisphere == Hemisphere::North;
+
+    let (lat, lon, convergence, scale) = if utm.is_ups() {
+        let ups = PolarStereographic::new(&Ellipsoid::WGS84, UPS_SCALE);
+        let point = ups.reverse(
+            north,
+            utm.easting - UPS_FALSE_EASTING_NORTHING,
+            utm.northing - UPS_FALSE_EASTING_NORTHING,
+        );
+        (point.lat, point.lon, point.convergence, point.scale)
+    } else {
+        let tm = TransverseMercator::new(&Ellipsoid::WGS84, UTM_SCALE);
+        let false_northing = if north { 0. } else { UTM_FALSE_NORTHING_SOUTH };
+        let point = tm.reverse(
+            central_meridian(utm.zone),
+            utm.easting - UTM_FALSE_EASTING,
+            utm.northing - false_northing,
+        );
+        (point.lat, point.lon, point.convergence, point.scale)
+    };
+
+    return UTMReverse {
+        coord: WGS84Coord::new_from_radians(lat, lon, 0.),
+        convergence,
+        scale,
+    };
+}
+
+pub fn central_meridian(zone: u8) -> Radians {
+    return (6. * zone as f64 - 183.).to_radians();
+}
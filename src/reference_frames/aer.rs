@@ -0,0 +1,178 @@
+use std::f64::consts::PI;
+
+use crate::{
+    matrix::Vec3,
+    types::{Metres, Radians},
+    utils::math::ang_normalize,
+};
+
+use super::{
+    ecef::{generate_ecef_to_enu_matrix, ECEFVel, ECEF},
+    enu::ENU,
+    ned::NED,
+    wgs84::WGS84Coord,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AzimuthWrap {
+    // Range azimuths are reported in, both measured clockwise from north
+    ZeroToTwoPi,
+    MinusPiToPi,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AER {
+    // Look angles and slant range from an observer to a target, in the observer's ENU frame
+    // Azimuth is clockwise from north in [0, 2pi), elevation is above the plane normal to the ellipsoid
+    pub azimuth: Radians,
+    pub elevation: Radians,
+    pub range: Metres,
+}
+
+impl AER {
+    pub fn new_from_raw(azimuth: Radians, elevation: Radians, range: Metres) -> AER {
+        return AER {
+            azimuth: wrap_azimuth(azimuth, AzimuthWrap::ZeroToTwoPi),
+            elevation,
+            range,
+        };
+    }
+
+    pub fn new_from_enu(enu: &ENU) -> AER {
+        // Straight up (or a target at the observer) has no defined azimuth and gets zero
+        let horizontal = enu.e.hypot(enu.n);
+
+        return AER {
+            azimuth: wrap_azimuth(enu.e.atan2(enu.n), AzimuthWrap::ZeroToTwoPi),
+            elevation: enu.u.atan2(horizontal),
+            range: horizontal.hypot(enu.u),
+        };
+    }
+
+    pub fn new_from_ned(ned: &NED) -> AER {
+        return AER::new_from_enu(&ENU::new_from_ned(ned));
+    }
+
+    pub fn new_from_ecef(ecef: &ECEF, observer: &WGS84Coord) -> AER {
+        return AER::new_from_enu(&ENU::new_from_ecef(ecef, observer));
+    }
+
+    pub fn new_from_wgs84(target: &WGS84Coord, observer: &WGS84Coord) -> AER {
+        return AER::new_from_enu(&ENU::new_from_wgs84(target, observer));
+    }
+
+    pub fn get_azimuth(&self, wrap: AzimuthWrap) -> Radians {
+        return wrap_azimuth(self.azimuth, wrap);
+    }
+
+    pub fn is_above(&self, mask_elevation: Radians) -> bool {
+        // True when the target clears a fixed elevation mask, e.g. 10 degrees for a ground station
+        return self.elevation >= mask_elevation;
+    }
+
+    pub fn is_above_mask(&self, mask: &ElevationMask) -> bool {
+        return self.elevation >= mask.get_elevation(self.azimuth);
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AERRate {
+    // Rates of change of the look angles and range, for an observer fixed to the Earth
+    pub azimuth_rate: f64,
+    pub elevation_rate: f64,
+    // Positive when the target is moving away
+    pub range_rate: f64,
+}
+
+impl AERRate {
+    pub fn new_from_ecef_vel(
+        target: &ECEF,
+        target_vel: &ECEFVel,
+        observer: &WGS84Coord,
+    ) -> AERRate {
+        // Given a target's ECEF position and velocity returns the rates seen by the observer, in
+        // radians and metres per second. The angular rates are infinite directly overhead
+        let rotation = generate_ecef_to_enu_matrix(observer);
+        let enu = ENU::new_from_ecef_rot(target, &rotation, &ECEF::new_from_wgs84(observer));
+        let vel = rotation * Vec3::new(target_vel.x_vel, target_vel.y_vel, target_vel.z_vel);
+        let (e_vel, n_vel, u_vel) = (vel.x, vel.y, vel.z);
+
+        let horizontal_squared = enu.e * enu.e + enu.n * enu.n;
+        let horizontal = horizontal_squared.sqrt();
+        let range_squared = horizontal_squared + enu.u * enu.u;
+        let horizontal_rate = (enu.e * e_vel + enu.n * n_vel) / horizontal;
+
+        return AERRate {
+            azimuth_rate: (enu.n * e_vel - enu.e * n_vel) / horizontal_squared,
+            elevation_rate: (horizontal * u_vel - enu.u * horizontal_rate) / range_squared,
+            range_rate: (enu.e * e_vel + enu.n * n_vel + enu.u * u_vel) / range_squared.sqrt(),
+        };
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ElevationMask {
+    // A horizon profile as (azimuth, elevation) pairs sorted by azimuth in [0, 2pi), interpolated
+    // linearly between them and wrapping round through north. Terrain or buildings around a
+    // ground station are described this way
+    points: Vec<(Radians, Radians)>,
+}
+
+impl ElevationMask {
+    pub fn new(points: &[(Radians, Radians)]) -> ElevationMask {
+        let mut points: Vec<(Radians, Radians)> = points
+            .iter()
+            .map(|(azimuth, elevation)| {
+                (wrap_azimuth(*azimuth, AzimuthWrap::ZeroToTwoPi), *elevation)
+            })
+            .collect();
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        return ElevationMask { points };
+    }
+
+    pub fn new_constant(elevation: Radians) -> ElevationMask {
+        return ElevationMask {
+            points: vec![(0., elevation)],
+        };
+    }
+
+    pub fn get_elevation(&self, azimuth: Radians) -> Radians {
+        // Minimum elevation for a target at the azimuth. An empty mask is the horizon itself
+        let azimuth = wrap_azimuth(azimuth, AzimuthWrap::ZeroToTwoPi);
+        let (first, last) = match (self.points.first(), self.points.last()) {
+            (Some(first), Some(last)) => (*first, *last),
+            _ => return 0.,
+        };
+
+        let (before, after) = if azimuth < first.0 {
+            ((last.0 - 2. * PI, last.1), first)
+        } else if azimuth >= last.0 {
+            (last, (first.0 + 2. * PI, first.1))
+        } else {
+            let i = self.points.partition_point(|point| point.0 <= azimuth);
+            (self.points[i - 1], self.points[i])
+        };
+
+        let span = after.0 - before.0;
+        if span <= 0. {
+            return before.1;
+        }
+        return before.1 + (after.1 - before.1) * (azimuth - before.0) / span;
+    }
+}
+
+pub fn wrap_azimuth(azimuth: Radians, wrap: AzimuthWrap) -> Radians {
+    let signed = ang_normalize(azimuth);
+    return match wrap {
+        AzimuthWrap::MinusPiToPi => signed,
+        AzimuthWrap::ZeroToTwoPi => {
+            if signed < 0. {
+                // Tiny negative angles would round to exactly 2pi
+                (signed + 2. * PI) % (2. * PI)
+            } else {
+                signed
+            }
+        }
+    };
+}
@@ -0,0 +1,565 @@
+// The IAU 2006/2000 CIO based reduction between the Earth fixed ITRS (ECEF) and the inertial GCRS
+// (IERS Conventions 2010, chapter 5, following SOFA)
+// GCRF --X, Y and s--> CIRS --Earth rotation angle--> TIRS --polar motion and s'--> ECEF
+// The nutation is the 77 term IAU 2000B series, within a milliarcsecond of 2000A, adjusted to the
+// IAU 2006 precession. The EOP dX and dY corrections take the pole the rest of the way
+use std::f64::consts::TAU;
+
+use crate::{
+    constants::{ARCSEC_TO_RAD, EARTH_ROTATION_RATE},
+    matrix::{Mat3, Vec3},
+    time::{
+        eop::{get_eop, EarthOrientation},
+        epoch::{Epoch, TimeScale},
+        julian_date::JulianDate,
+        sidereal::earth_rotation_angle,
+    },
+    types::Radians,
+};
+
+use super::{
+    ecef::{ECEFVel, ECEF},
+    gcrf::{GCRFVel, GCRF},
+};
+
+const ARCSECONDS_PER_TURN: f64 = 1296000.;
+
+// Multipliers of l, l', F, D and Omega, then the longitude sine, its rate and cosine, and the
+// obliquity cosine, its rate and sine, in 0.1 uas
+type NutationTerm = ([i8; 5], f64, f64, f64, f64, f64, f64);
+
+// The luni-solar part of the IAU 2000B nutation
+#[rustfmt::skip]
+const NUTATION_2000B: [NutationTerm; 77] = [
+    ([ 0,  0,  0,  0,  1], -172064161.0, -174666.0,  33386.0, 92052331.0,  9086.0, 15377.0),
+    ([ 0,  0,  2, -2,  2],  -13170906.0,   -1675.0, -13696.0,  5730336.0, -3015.0, -4587.0),
+    ([ 0,  0,  2,  0,  2],   -2276413.0,    -234.0,   2796.0,   978459.0,  -485.0,  1374.0),
+    ([ 0,  0,  0,  0,  2],    2074554.0,     207.0,   -698.0,  -897492.0,   470.0,  -291.0),
+    ([ 0,  1,  0,  0,  0],    1475877.0,   -3633.0,  11817.0,    73871.0,  -184.0, -1924.0),
+    ([ 0,  1,  2, -2,  2],    -516821.0,    1226.0,   -524.0,   224386.0,  -677.0,  -174.0),
+    ([ 1,  0,  0,  0,  0],     711159.0,      73.0,   -872.0,    -6750.0,     0.0,   358.0),
+    ([ 0,  0,  2,  0,  1],    -387298.0,    -367.0,    380.0,   200728.0,    18.0,   318.0),
+    ([ 1,  0,  2,  0,  2],    -301461.0,     -36.0,    816.0,   129025.0,   -63.0,   367.0),
+    ([ 0, -1,  2, -2,  2],     215829.0,    -494.0,    111.0,   -95929.0,   299.0,   132.0),
+    ([ 0,  0,  2, -2,  1],     128227.0,     137.0,    181.0,   -68982.0,    -9.0,    39.0),
+    ([-1,  0,  2,  0,  2],     123457.0,      11.0,     19.0,   -53311.0,    32.0,    -4.0),
+    ([-1,  0,  0,  2,  0],     156994.0,      10.0,   -168.0,    -1235.0,     0.0,    82.0),
+    ([ 1,  0,  0,  0,  1],      63110.0,      63.0,     27.0,   -33228.0,     0.0,    -9.0),
+    ([-1,  0,  0,  0,  1],     -57976.0,     -63.0,   -189.0,    31429.0,     0.0,   -75.0),
+    ([-1,  0,  2,  2,  2],     -59641.0,     -11.0,    149.0,    25543.0,   -11.0,    66.0),
+    ([ 1,  0,  2,  0,  1],     -51613.0,     -42.0,    129.0,    26366.0,     0.0,    78.0),
+    ([-2,  0,  2,  0,  1],      45893.0,      50.0,     31.0,   -24236.0,   -10.0,    20.0),
+    ([ 0,  0,  0,  2,  0],      63384.0,      11.0,   -150.0,    -1220.0,     0.0,    29.0),
+    ([ 0,  0,  2,  2,  2],     -38571.0,      -1.0,    158.0,    16452.0,   -11.0,    68.0),
+    ([ 0, -2,  2, -2,  2],      32481.0,       0.0,      0.0,   -13870.0,     0.0,     0.0),
+    ([-2,  0,  0,  2,  0],     -47722.0,       0.0,    -18.0,      477.0,     0.0,   -25.0),
+    ([ 2,  0,  2,  0,  2],     -31046.0,      -1.0,    131.0,    13238.0,   -11.0,    59.0),
+    ([ 1,  0,  2, -2,  2],      28593.0,       0.0,     -1.0,   -12338.0,    10.0,    -3.0),
+    ([-1,  0,  2,  0,  1],      20441.0,      21.0,     10.0,   -10758.0,     0.0,    -3.0),
+    ([ 2,  0,  0,  0,  0],      29243.0,       0.0,    -74.0,     -609.0,     0.0,    13.0),
+    ([ 0,  0,  2,  0,  0],      25887.0,       0.0,    -66.0,     -550.0,     0.0,    11.0),
+    ([ 0,  1,  0,  0,  1],     -14053.0,     -25.0,     79.0,     8551.0,    -2.0,   -45.0),
+    ([-1,  0,  0,  2,  1],      15164.0,      10.0,     11.0,    -8001.0,     0.0,    -1.0),
+    ([ 0,  2,  2, -2,  2],     -15794.0,      72.0,    -16.0,     6850.0,   -42.0,    -5.0),
+    ([ 0,  0, -2,  2,  0],      21783.0,       0.0,     13.0,     -167.0,     0.0,    13.0),
+    ([ 1,  0,  0, -2,  1],     -12873.0,     -10.0,    -37.0,     6953.0,     0.0,   -14.0),
+    ([ 0, -1,  0,  0,  1],     -12654.0,      11.0,     63.0,     6415.0,     0.0,    26.0),
+    ([-1,  0,  2,  2,  1],     -10204.0,       0.0,     25.0,     5222.0,     0.0,    15.0),
+    ([ 0,  2,  0,  0,  0],      16707.0,     -85.0,    -10.0,      168.0,    -1.0,    10.0),
+    ([ 1,  0,  2,  2,  2],      -7691.0,       0.0,     44.0,     3268.0,     0.0,    19.0),
+    ([-2,  0,  2,  0,  0],     -11024.0,       0.0,    -14.0,      104.0,     0.0,     2.0),
+    ([ 0,  1,  2,  0,  2],       7566.0,     -21.0,    -11.0,    -3250.0,     0.0,    -5.0),
+    ([ 0,  0,  2,  2,  1],      -6637.0,     -11.0,     25.0,     3353.0,     0.0,    14.0),
+    ([ 0, -1,  2,  0,  2],      -7141.0,      21.0,      8.0,     3070.0,     0.0,     4.0),
+    ([ 0,  0,  0,  2,  1],      -6302.0,     -11.0,      2.0,     3272.0,     0.0,     4.0),
+    ([ 1,  0,  2, -2,  1],       5800.0,      10.0,      2.0,    -3045.0,     0.0,    -1.0),
+    ([ 2,  0,  2, -2,  2],       6443.0,       0.0,     -7.0,    -2768.0,     0.0,    -4.0),
+    ([-2,  0,  0,  2,  1],      -5774.0,     -11.0,    -15.0,     3041.0,     0.0,    -5.0),
+    ([ 2,  0,  2,  0,  1],      -5350.0,       0.0,     21.0,     2695.0,     0.0,    12.0),
+    ([ 0, -1,  2, -2,  1],      -4752.0,     -11.0,     -3.0,     2719.0,     0.0,    -3.0),
+    ([ 0,  0,  0, -2,  1],      -4940.0,     -11.0,    -21.0,     2720.0,     0.0,    -9.0),
+    ([-1, -1,  0,  2,  0],       7350.0,       0.0,     -8.0,      -51.0,     0.0,     4.0),
+    ([ 2,  0,  0, -2,  1],       4065.0,       0.0,      6.0,    -2206.0,     0.0,     1.0),
+    ([ 1,  0,  0,  2,  0],       6579.0,       0.0,    -24.0,     -199.0,     0.0,     2.0),
+    ([ 0,  1,  2, -2,  1],       3579.0,       0.0,      5.0,    -1900.0,     0.0,     1.0),
+    ([ 1, -1,  0,  0,  0],       4725.0,       0.0,     -6.0,      -41.0,     0.0,     3.0),
+    ([-2,  0,  2,  0,  2],      -3075.0,       0.0,     -2.0,     1313.0,     0.0,    -1.0),
+    ([ 3,  0,  2,  0,  2],      -2904.0,       0.0,     15.0,     1233.0,     0.0,     7.0),
+    ([ 0, -1,  0,  2,  0],       4348.0,       0.0,    -10.0,      -81.0,     0.0,     2.0),
+    ([ 1, -1,  2,  0,  2],      -2878.0,       0.0,      8.0,     1232.0,     0.0,     4.0),
+    ([ 0,  0,  0,  1,  0],      -4230.0,       0.0,      5.0,      -20.0,     0.0,    -2.0),
+    ([-1, -1,  2,  2,  2],      -2819.0,       0.0,      7.0,     1207.0,     0.0,     3.0),
+    ([-1,  0,  2,  0,  0],      -4056.0,       0.0,      5.0,       40.0,     0.0,    -2.0),
+    ([ 0, -1,  2,  2,  2],      -2647.0,       0.0,     11.0,     1129.0,     0.0,     5.0),
+    ([-2,  0,  0,  0,  1],      -2294.0,       0.0,    -10.0,     1266.0,     0.0,    -4.0),
+    ([ 1,  1,  2,  0,  2],       2481.0,       0.0,     -7.0,    -1062.0,     0.0,    -3.0),
+    ([ 2,  0,  0,  0,  1],       2179.0,       0.0,     -2.0,    -1129.0,     0.0,    -2.0),
+    ([-1,  1,  0,  1,  0],       3276.0,       0.0,      1.0,       -9.0,     0.0,     0.0),
+    ([ 1,  1,  0,  0,  0],      -3389.0,       0.0,      5.0,       35.0,     0.0,    -2.0),
+    ([ 1,  0,  2,  0,  0],       3339.0,       0.0,    -13.0,     -107.0,     0.0,     1.0),
+    ([-1,  0,  2, -2,  1],      -1987.0,       0.0,     -6.0,     1073.0,     0.0,    -2.0),
+    ([ 1,  0,  0,  0,  2],      -1981.0,       0.0,      0.0,      854.0,     0.0,     0.0),
+    ([-1,  0,  0,  1,  0],       4026.0,       0.0,   -353.0,     -553.0,     0.0,  -139.0),
+    ([ 0,  0,  2,  1,  2],       1660.0,       0.0,     -5.0,     -710.0,     0.0,    -2.0),
+    ([-1,  0,  2,  4,  2],      -1521.0,       0.0,      9.0,      647.0,     0.0,     4.0),
+    ([-1,  1,  0,  1,  1],       1314.0,       0.0,      0.0,     -700.0,     0.0,     0.0),
+    ([ 0, -2,  2, -2,  1],      -1283.0,       0.0,      0.0,      672.0,     0.0,     0.0),
+    ([ 1,  0,  2,  2,  1],      -1331.0,       0.0,      8.0,      663.0,     0.0,     4.0),
+    ([-2,  0,  2,  2,  2],       1383.0,       0.0,     -2.0,     -594.0,     0.0,    -2.0),
+    ([-1,  0,  0,  0,  2],       1405.0,       0.0,      4.0,     -610.0,     0.0,     2.0),
+    ([ 1,  1,  2, -2,  2],       1290.0,       0.0,      0.0,     -556.0,     0.0,     0.0),
+];
+
+// Fixed offsets standing in for the planetary terms of 2000A, in mas
+const NUTATION_2000B_PLANETARY: (f64, f64) = (-0.135, 0.388);
+
+// The polynomial part of s + XY/2, in uas
+const CIO_LOCATOR_POLYNOMIAL: [f64; 6] = [94.00, 3808.65, -122.68, -72574.11, 27.98, 15.62];
+
+// The periodic part of s + XY/2 (IERS Conventions 2010, table 5.2d). The power of t each term is
+// multiplied by, multipliers of l, l', F, D, Omega, and the mean longitudes of Venus and the Earth
+// and the general precession in longitude, then the sine and cosine coefficients in uas
+#[rustfmt::skip]
+const CIO_LOCATOR_SERIES: [(i32, [i8; 8], f64, f64); 66] = [
+    (0, [ 0,  0,  0,  0,  1,  0,  0,  0], -2640.73,   0.39),
+    (0, [ 0,  0,  0,  0,  2,  0,  0,  0],   -63.53,   0.02),
+    (0, [ 0,  0,  2, -2,  3,  0,  0,  0],   -11.75,  -0.01),
+    (0, [ 0,  0,  2, -2,  1,  0,  0,  0],   -11.21,  -0.01),
+    (0, [ 0,  0,  2, -2,  2,  0,  0,  0],     4.57,   0.00),
+    (0, [ 0,  0,  2,  0,  3,  0,  0,  0],    -2.02,   0.00),
+    (0, [ 0,  0,  2,  0,  1,  0,  0,  0],    -1.98,   0.00),
+    (0, [ 0,  0,  0,  0,  3,  0,  0,  0],     1.72,   0.00),
+    (0, [ 0,  1,  0,  0,  1,  0,  0,  0],     1.41,   0.01),
+    (0, [ 0,  1,  0,  0, -1,  0,  0,  0],     1.26,   0.01),
+    (0, [ 1,  0,  0,  0, -1,  0,  0,  0],     0.63,   0.00),
+    (0, [ 1,  0,  0,  0,  1,  0,  0,  0],     0.63,   0.00),
+    (0, [ 0,  1,  2, -2,  3,  0,  0,  0],    -0.46,   0.00),
+    (0, [ 0,  1,  2, -2,  1,  0,  0,  0],    -0.45,   0.00),
+    (0, [ 0,  0,  4, -4,  4,  0,  0,  0],    -0.36,   0.00),
+    (0, [ 0,  0,  1, -1,  1, -8, 12,  0],     0.24,   0.12),
+    (0, [ 0,  0,  2,  0,  0,  0,  0,  0],    -0.32,   0.00),
+    (0, [ 0,  0,  2,  0,  2,  0,  0,  0],    -0.28,   0.00),
+    (0, [ 1,  0,  2,  0,  3,  0,  0,  0],    -0.27,   0.00),
+    (0, [ 1,  0,  2,  0,  1,  0,  0,  0],    -0.26,   0.00),
+    (0, [ 0,  0,  2, -2,  0,  0,  0,  0],     0.21,   0.00),
+    (0, [ 0,  1, -2,  2, -3,  0,  0,  0],    -0.19,   0.00),
+    (0, [ 0,  1, -2,  2, -1,  0,  0,  0],    -0.18,   0.00),
+    (0, [ 0,  0,  0,  0,  0,  8,-13, -1],     0.10,  -0.05),
+    (0, [ 0,  0,  0,  2,  0,  0,  0,  0],    -0.15,   0.00),
+    (0, [ 2,  0, -2,  0, -1,  0,  0,  0],     0.14,   0.00),
+    (0, [ 0,  1,  2, -2,  2,  0,  0,  0],     0.14,   0.00),
+    (0, [ 1,  0,  0, -2,  1,  0,  0,  0],    -0.14,   0.00),
+    (0, [ 1,  0,  0, -2, -1,  0,  0,  0],    -0.14,   0.00),
+    (0, [ 0,  0,  4, -2,  4,  0,  0,  0],    -0.13,   0.00),
+    (0, [ 0,  0,  2, -2,  4,  0,  0,  0],     0.11,   0.00),
+    (0, [ 1,  0, -2,  0, -3,  0,  0,  0],    -0.11,   0.00),
+    (0, [ 1,  0, -2,  0, -1,  0,  0,  0],    -0.11,   0.00),
+    (1, [ 0,  0,  0,  0,  2,  0,  0,  0],    -0.07,   3.57),
+    (1, [ 0,  0,  0,  0,  1,  0,  0,  0],     1.73,  -0.03),
+    (1, [ 0,  0,  2, -2,  3,  0,  0,  0],     0.00,   0.48),
+    (2, [ 0,  0,  0,  0,  1,  0,  0,  0],   743.52,  -0.17),
+    (2, [ 0,  0,  2, -2,  2,  0,  0,  0],    56.91,   0.06),
+    (2, [ 0,  0,  2,  0,  2,  0,  0,  0],     9.84,  -0.01),
+    (2, [ 0,  0,  0,  0,  2,  0,  0,  0],    -8.85,   0.01),
+    (2, [ 0,  1,  0,  0,  0,  0,  0,  0],    -6.38,  -0.05),
+    (2, [ 1,  0,  0,  0,  0,  0,  0,  0],    -3.07,   0.00),
+    (2, [ 0,  1,  2, -2,  2,  0,  0,  0],     2.23,   0.00),
+    (2, [ 0,  0,  2,  0,  1,  0,  0,  0],     1.67,   0.00),
+    (2, [ 1,  0,  2,  0,  2,  0,  0,  0],     1.30,   0.00),
+    (2, [ 0,  1, -2,  2, -2,  0,  0,  0],     0.93,   0.00),
+    (2, [ 1,  0,  0, -2,  0,  0,  0,  0],     0.68,   0.00),
+    (2, [ 0,  0,  2, -2,  1,  0,  0,  0],    -0.55,   0.00),
+    (2, [ 1,  0, -2,  0, -2,  0,  0,  0],     0.53,   0.00),
+    (2, [ 0,  0,  0,  2,  0,  0,  0,  0],    -0.27,   0.00),
+    (2, [ 1,  0,  0,  0,  1,  0,  0,  0],    -0.27,   0.00),
+    (2, [ 1,  0, -2, -2, -2,  0,  0,  0],    -0.26,   0.00),
+    (2, [ 1,  0,  0,  0, -1,  0,  0,  0],    -0.25,   0.00),
+    (2, [ 1,  0,  2,  0,  1,  0,  0,  0],     0.22,   0.00),
+    (2, [ 2,  0,  0, -2,  0,  0,  0,  0],    -0.21,   0.00),
+    (2, [ 2,  0, -2,  0, -1,  0,  0,  0],     0.20,   0.00),
+    (2, [ 0,  0,  2,  2,  2,  0,  0,  0],     0.17,   0.00),
+    (2, [ 2,  0,  2,  0,  2,  0,  0,  0],     0.13,   0.00),
+    (2, [ 2,  0,  0,  0,  0,  0,  0,  0],    -0.13,   0.00),
+    (2, [ 1,  0,  2, -2,  2,  0,  0,  0],    -0.12,   0.00),
+    (2, [ 0,  0,  2,  0,  0,  0,  0,  0],    -0.11,   0.00),
+    (3, [ 0,  0,  0,  0,  1,  0,  0,  0],     0.30, -23.42),
+    (3, [ 0,  0,  2, -2,  2,  0,  0,  0],    -0.03,  -1.46),
+    (3, [ 0,  0,  2,  0,  2,  0,  0,  0],    -0.01,  -0.25),
+    (3, [ 0,  0,  0,  0,  2,  0,  0,  0],     0.00,   0.23),
+    (4, [ 0,  0,  0,  0,  1,  0,  0,  0],    -0.26,  -0.01),
+];
+
+fn fundamental_arguments(tt_centuries: f64) -> [Radians; 8] {
+    // IERS Conventions 2003 fundamental arguments: the Delaunay arguments l, l', F, D and Omega,
+    // the mean longitudes of Venus and the Earth, and the general precession in longitude
+    let t = tt_centuries;
+    let delaunay = |c: [f64; 5]| -> Radians {
+        let arcseconds = c[0] + (c[1] + (c[2] + (c[3] + c[4] * t) * t) * t) * t;
+        return arcseconds.rem_euclid(ARCSECONDS_PER_TURN) * ARCSEC_TO_RAD;
+    };
+    return [
+        delaunay([
+            485868.249036,
+            1717915923.2178,
+            31.8792,
+            0.051635,
+            -0.0002447,
+        ]),
+        delaunay([
+            1287104.793048,
+            129596581.0481,
+            -0.5532,
+            0.000136,
+            -0.00001149,
+        ]),
+        delaunay([
+            335779.526232,
+            1739527262.8478,
+            -12.7512,
+            -0.001037,
+            0.00000417,
+        ]),
+        delaunay([
+            1072260.703692,
+            1602961601.2090,
+            -6.3706,
+            0.006593,
+            -0.00003169,
+        ]),
+        delaunay([450160.398036, -6962890.5431, 7.4722, 0.007702, -0.00005939]),
+        (3.176146697 + 1021.3285546211 * t).rem_euclid(TAU),
+        (1.753470314 + 628.3075849991 * t).rem_euclid(TAU),
+        (0.02438175 + 0.00000538691 * t) * t,
+    ];
+}
+
+pub fn nutation_iau2000b(tt_centuries: f64) -> (Radians, Radians) {
+    // Nutation in longitude and obliquity from the IAU 2000B series
+    let t = tt_centuries;
+
+    // Only the linear parts of the Delaunay arguments are used by 2000B
+    let argument = |c0: f64, c1: f64| -> Radians {
+        return (c0 + c1 * t).rem_euclid(ARCSECONDS_PER_TURN) * ARCSEC_TO_RAD;
+    };
+    let l = argument(485868.249036, 1717915923.2178);
+    let lp = argument(1287104.79305, 129596581.0481);
+    let f = argument(335779.526232, 1739527262.8478);
+    let d = argument(1072260.70369, 1602961601.2090);
+    let om = argument(450160.398036, -6962890.5431);
+
+    // Smallest terms first
+    let (mut dpsi, mut deps) = (0., 0.);
+    for (k, ps, pst, pc, ec, ect, es) in NUTATION_2000B.iter().rev() {
+        let k = k.map(|k| k as f64);
+        let (s, c) = (k[0] * l + k[1] * lp + k[2] * f + k[3] * d + k[4] * om).sin_cos();
+        dpsi += (ps + pst * t) * s + pc * c;
+        deps += (ec + ect * t) * c + es * s;
+    }
+
+    let (dpsi_planetary, deps_planetary) = NUTATION_2000B_PLANETARY;
+    return (
+        (dpsi * 1e-7 + dpsi_planetary * 1e-3) * ARCSEC_TO_RAD,
+        (deps * 1e-7 + deps_planetary * 1e-3) * ARCSEC_TO_RAD,
+    );
+}
+
+pub fn mean_obliquity_iau2006(tt_centuries: f64) -> Radians {
+    let t = tt_centuries;
+    let arcseconds = 84381.406
+        + (-46.836769 + (-0.0001831 + (0.0020034 + (-0.000000576 - 0.0000000434 * t) * t) * t) * t)
+            * t;
+    return arcseconds * ARCSEC_TO_RAD;
+}
+
+pub fn get_bias_precession_nutation_matrix(tt: &JulianDate) -> Mat3 {
+    // GCRS to the true equator and equinox of date, from the IAU 2006 Fukushima-Williams angles
+    // and the 2000B nutation with the adjustments for the 2006 precession
+    let t = tt.get_julian_centuries();
+    let polynomial = |c: [f64; 6]| -> Radians {
+        let arcseconds = c[0] + (c[1] + (c[2] + (c[3] + (c[4] + c[5] * t) * t) * t) * t) * t;
+        return arcseconds * ARCSEC_TO_RAD;
+    };
+    let gamma = polynomial([
+        -0.052928,
+        10.556378,
+        0.4932044,
+        -0.00031238,
+        -2.788e-6,
+        2.6e-8,
+    ]);
+    let phi = polynomial([
+        84381.412819,
+        -46.811016,
+        0.0511268,
+        0.00053289,
+        -4.4e-7,
+        -1.76e-8,
+    ]);
+    let psi = polynomial([
+        -0.041775,
+        5038.481484,
+        1.5584175,
+        -0.00018522,
+        -2.6452e-5,
+        -1.48e-8,
+    ]);
+
+    let (dpsi, deps) = nutation_iau2000b(t);
+    let j2 = -2.7774e-6 * t;
+    let dpsi = dpsi * (1. + 0.4697e-6 + j2);
+    let deps = deps * (1. + j2);
+
+    return Mat3::rotation_x(-(mean_obliquity_iau2006(t) + deps))
+        * Mat3::rotation_z(-(psi + dpsi))
+        * Mat3::rotation_x(phi)
+        * Mat3::rotation_z(gamma);
+}
+
+pub fn get_cip_xy(tt: &JulianDate) -> (Radians, Radians) {
+    // The celestial intermediate pole in the GCRS, before the EOP dX and dY corrections
+    let matrix = get_bias_precession_nutation_matrix(tt);
+    return (matrix.get(2, 0), matrix.get(2, 1));
+}
+
+pub fn cio_locator(tt: &JulianDate, x: Radians, y: Radians) -> Radians {
+    // s, placing the CIO on the CIP equator, given the CIP coordinates
+    let t = tt.get_julian_centuries();
+    let arguments = fundamental_arguments(t);
+
+    let mut powers = CIO_LOCATOR_POLYNOMIAL;
+    for (power, k, s, c) in CIO_LOCATOR_SERIES.iter().rev() {
+        let angle: f64 = k.iter().zip(arguments).map(|(k, a)| *k as f64 * a).sum();
+        powers[*power as usize] += s * angle.sin() + c * angle.cos();
+    }
+    let microarcseconds = powers.iter().rev().fold(0., |sum, c| sum * t + c);
+
+    return microarcseconds * 1e-6 * ARCSEC_TO_RAD - x * y / 2.;
+}
+
+pub fn tio_locator(tt: &JulianDate) -> Radians {
+    // s', placing the TIO on the CIP equator, from the mean rate of polar motion
+    return -47e-6 * tt.get_julian_centuries() * ARCSEC_TO_RAD;
+}
+
+pub fn get_celestial_to_intermediate_matrix(x: Radians, y: Radians, s: Radians) -> Mat3 {
+    // GCRS to CIRS from the CIP coordinates and the CIO locator
+    let r2 = x * x + y * y;
+    let e = if r2 > 0. { y.atan2(x) } else { 0. };
+    let d = (r2 / (1. - r2)).sqrt().atan();
+    return Mat3::rotation_z(-(e + s)) * Mat3::rotation_y(d) * Mat3::rotation_z(e);
+}
+
+pub fn get_polar_motion_matrix_iau2000(xp: Radians, yp: Radians, sp: Radians) -> Mat3 {
+    // TIRS to ITRS, including the TIO locator
+    return Mat3::rotation_x(-yp) * Mat3::rotation_y(-xp) * Mat3::rotation_z(sp);
+}
+
+#[derive(Debug, Clone)]
+pub struct CIRS {
+    // Celestial intermediate, the CIP equator with the CIO as its origin of right ascension
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl CIRS {
+    pub fn new_from_gcrf(gcrf: &GCRF, time: impl Into<Epoch>) -> CIRS {
+        return CIO::new(time).cirs_from_gcrf(gcrf);
+    }
+
+    pub fn new_from_tirs(tirs: &TIRS, time: impl Into<Epoch>) -> CIRS {
+        return CIO::new(time).cirs_from_tirs(tirs);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TIRS {
+    // Terrestrial intermediate, the CIP equator with the TIO as its origin of longitude. The CIO
+    // based counterpart of PEF
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl TIRS {
+    pub fn new_from_cirs(cirs: &CIRS, time: impl Into<Epoch>) -> TIRS {
+        return CIO::new(time).tirs_from_cirs(cirs);
+    }
+
+    pub fn new_from_ecef(ecef: &ECEF, time: impl Into<Epoch>) -> TIRS {
+        return CIO::new(time).tirs_from_ecef(ecef);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CIO {
+    // Every rotation of the reduction for one instant, so that converting many states at the same
+    // time only evaluates the series once. dX, dY, polar motion, UT1 - UTC and LOD come from the
+    // EOP table
+    x: Radians,
+    y: Radians,
+    s: Radians,
+    celestial_to_intermediate: Mat3,
+    era: Radians,
+    polar_motion: Mat3,
+    omega_earth: f64,
+}
+
+impl CIO {
+    pub fn new(time: impl Into<Epoch>) -> CIO {
+        let epoch: Epoch = time.into();
+        return CIO::new_from_eop(epoch, &get_eop(&epoch));
+    }
+
+    pub fn new_from_eop(time: impl Into<Epoch>, eop: &EarthOrientation) -> CIO {
+        // With the Earth orientation given rather than taken from the table. UT1 still comes from
+        // the epoch, so set its UT1 - UTC to match
+        let epoch: Epoch = time.into();
+        let tt = JulianDate::new_from_epoch(&epoch, TimeScale::TT);
+        let ut1 = JulianDate::new_from_epoch(&epoch, TimeScale::UT1);
+        let (x, y) = get_cip_xy(&tt);
+        let (x, y) = (x + eop.dx, y + eop.dy);
+        let s = cio_locator(&tt, x, y);
+        return CIO {
+            x,
+            y,
+            s,
+            celestial_to_intermediate: get_celestial_to_intermediate_matrix(x, y, s),
+            era: earth_rotation_angle(&ut1),
+            polar_motion: get_polar_motion_matrix_iau2000(eop.xp, eop.yp, tio_locator(&tt)),
+            omega_earth: EARTH_ROTATION_RATE * (1. - eop.lod / 86400.),
+        };
+    }
+
+    pub fn get_cip(&self) -> (Radians, Radians) {
+        return (self.x, self.y);
+    }
+
+    pub fn get_cio_locator(&self) -> Radians {
+        return self.s;
+    }
+
+    pub fn get_era(&self) -> Radians {
+        return self.era;
+    }
+
+    pub fn get_celestial_to_intermediate_matrix(&self) -> &Mat3 {
+        return &self.celestial_to_intermediate;
+    }
+
+    pub fn get_celestial_to_terrestrial_matrix(&self) -> Mat3 {
+        // GCRS to ITRS in one
+        return self.polar_motion * Mat3::rotation_z(self.era) * self.celestial_to_intermediate;
+    }
+
+    // Positions
+
+    pub fn cirs_from_gcrf(&self, gcrf: &GCRF) -> CIRS {
+        let v = self.celestial_to_intermediate * Vec3::new(gcrf.x, gcrf.y, gcrf.z);
+        return CIRS {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        };
+    }
+
+    pub fn gcrf_from_cirs(&self, cirs: &CIRS) -> GCRF {
+        let v = self
+            .celestial_to_intermediate
+            .transpose_times(&Vec3::new(cirs.x, cirs.y, cirs.z));
+        return GCRF {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        };
+    }
+
+    pub fn tirs_from_cirs(&self, cirs: &CIRS) -> TIRS {
+        let v = Mat3::rotation_z(self.era) * Vec3::new(cirs.x, cirs.y, cirs.z);
+        return TIRS {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        };
+    }
+
+    pub fn cirs_from_tirs(&self, tirs: &TIRS) -> CIRS {
+        let v = Mat3::rotation_z(self.era).transpose_times(&Vec3::new(tirs.x, tirs.y, tirs.z));
+        return CIRS {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        };
+    }
+
+    pub fn ecef_from_tirs(&self, tirs: &TIRS) -> ECEF {
+        let v = self.polar_motion * Vec3::new(tirs.x, tirs.y, tirs.z);
+        return ECEF {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        };
+    }
+
+    pub fn tirs_from_ecef(&self, ecef: &ECEF) -> TIRS {
+        let v = self
+            .polar_motion
+            .transpose_times(&Vec3::new(ecef.x, ecef.y, ecef.z));
+        return TIRS {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        };
+    }
+
+    pub fn gcrf_from_ecef(&self, ecef: &ECEF) -> GCRF {
+        return self.gcrf_from_cirs(&self.cirs_from_tirs(&self.tirs_from_ecef(ecef)));
+    }
+
+    pub fn ecef_from_gcrf(&self, gcrf: &GCRF) -> ECEF {
+        return self.ecef_from_tirs(&self.tirs_from_cirs(&self.cirs_from_gcrf(gcrf)));
+    }
+
+    // Velocities, which also pick up the Earth's rotation between TIRS and CIRS
+
+    pub fn gcrf_vel_from_ecef_vel(&self, ecef: &ECEF, ecef_vel: &ECEFVel) -> GCRFVel {
+        // v_cirs = R3(-era) (v_tirs + w x r_tirs)
+        let tirs = self.tirs_from_ecef(ecef);
+        let v = self.polar_motion.transpose_times(&Vec3::new(
+            ecef_vel.x_vel,
+            ecef_vel.y_vel,
+            ecef_vel.z_vel,
+        ));
+        let v = Vec3::new(
+            v.x - self.omega_earth * tirs.y,
+            v.y + self.omega_earth * tirs.x,
+            v.z,
+        );
+        let v = self
+            .celestial_to_intermediate
+            .transpose_times(&Mat3::rotation_z(self.era).transpose_times(&v));
+        return GCRFVel {
+            x_vel: v.x,
+            y_vel: v.y,
+            z_vel: v.z,
+        };
+    }
+
+    pub fn ecef_vel_from_gcrf_vel(&self, gcrf: &GCRF, gcrf_vel: &GCRFVel) -> ECEFVel {
+        // v_tirs = R3(era) v_cirs - w x r_tirs
+        let tirs = self.tirs_from_cirs(&self.cirs_from_gcrf(gcrf));
+        let v = Vec3::new(gcrf_vel.x_vel, gcrf_vel.y_vel, gcrf_vel.z_vel);
+        let v = Mat3::rotation_z(self.era) * (self.celestial_to_intermediate * v);
+        let v = self.polar_motion
+            * Vec3::new(
+                v.x + self.omega_earth * tirs.y,
+                v.y - self.omega_earth * tirs.x,
+                v.z,
+            );
+        return ECEFVel {
+            x_vel: v.x,
+            y_vel: v.y,
+            z_vel: v.z,
+        };
+    }
+}
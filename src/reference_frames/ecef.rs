@@ -1,18 +1,22 @@
-use chrono::NaiveDateTime;
-
 use crate::{
-    constants::{EARTH_ECCENTRICITY_SQUARED, EARTH_MAJOR},
-    utils::{get_polar_motion_matrix, old_maybe_broken_jday, transpose_times_vec},
+    ellipsoid::Ellipsoid,
+    matrix::{Mat3, Vec3},
+    time::{eop::get_eop, epoch::Epoch},
+    utils::get_polar_motion_matrix,
 };
 
 use super::{
+    aer::AER,
+    cio::{CIO, TIRS},
+    enu::ENU,
+    gcrf::{GCRFVel, GCRF},
     ned::{NEDVel, NED},
     pef::{PEFVel, PEF},
     teme::{TEMEVel, TEME},
     wgs84::WGS84Coord,
 };
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct ECEF {
     // Wrapper for ECEF coordinates
     pub x: f64,
@@ -21,99 +25,94 @@ pub struct ECEF {
 }
 
 impl ECEF {
-    pub fn default() -> Self {
-        Self {
-            x: 0.0,
-            y: 0.0,
-            z: 0.0,
-        }
-    }
     pub fn new_from_wgs84(wgs84: &WGS84Coord) -> ECEF {
         // Given a reference to a WGS84Coord produces an ECEF coord
-        let lat = wgs84.get_lat_radians();
-        let lon = wgs84.get_lon_radians();
-        let alt = wgs84.get_altitude();
+        return ECEF::new_from_geodetic(wgs84, &Ellipsoid::WGS84);
+    }
+
+    pub fn new_from_geodetic(geodetic: &WGS84Coord, ellipsoid: &Ellipsoid) -> ECEF {
+        // Given a latitude, longitude and height relative to an arbitrary ellipsoid produces an ECEF coord
+        // The result is only as "earth centred" as the datum the ellipsoid belongs to
+        let lat = geodetic.get_lat_radians();
+        let lon = geodetic.get_lon_radians();
+        let alt = geodetic.get_altitude();
 
         let lat_cos = lat.cos();
         let lat_sin = lat.sin();
+        let eccentricity_squared = ellipsoid.get_eccentricity_squared();
 
-        let nutation =
-            EARTH_MAJOR / (1_f64 - (EARTH_ECCENTRICITY_SQUARED * lat_sin.powf(2_f64))).sqrt();
+        let nutation = ellipsoid.prime_vertical_radius(lat);
         let x = (nutation + alt) * lat_cos * lon.cos();
         let y = (nutation + alt) * lat_cos * lon.sin();
-        let z = (((1_f64 - EARTH_ECCENTRICITY_SQUARED) * nutation) + alt) * lat_sin;
+        let z = (((1_f64 - eccentricity_squared) * nutation) + alt) * lat_sin;
 
         return ECEF { x, y, z };
     }
 
-    pub fn new_from_pef(pef: &PEF, utc_time: &NaiveDateTime) -> ECEF {
-        // Given a reference to a PEF coordinate and a reference to a NaiveDateTime produces an ECEF
+    pub fn new_from_pef(pef: &PEF, time: impl Into<Epoch>) -> ECEF {
+        // Given a reference to a PEF coordinate and a time produces an ECEF
         // Since PEF and ECEF rotate with respect to one another, a time is necessary
-        // Be careful of the NaiveDateTime time zones - I am assuming this is using UNIX seconds
-        // In general, you are best of making all NaiveDateTimes from UNIX timestamps
-        let (jday, jfrac) = old_maybe_broken_jday(utc_time);
-        let julian = jday + jfrac;
-        let polar_motion_matrix = get_polar_motion_matrix(julian);
+        // A NaiveDateTime is taken as UTC, use an Epoch for other time scales
+        let epoch: Epoch = time.into();
+        let eop = get_eop(&epoch);
+        let polar_motion_matrix = get_polar_motion_matrix(eop.xp, eop.yp);
 
         // polar_motion_matrix^T * pef
+        let ecef = polar_motion_matrix.transpose_times(&Vec3::new(pef.x, pef.y, pef.z));
+
+        return ECEF {
+            x: ecef.x,
+            y: ecef.y,
+            z: ecef.z,
+        };
+    }
+
+    pub fn new_from_gcrf(gcrf: &GCRF, time: impl Into<Epoch>) -> ECEF {
+        // Through the IAU 2006/2000 reduction, see CIO for reusing it across many states at one time
+        return CIO::new(time).ecef_from_gcrf(gcrf);
+    }
+
+    pub fn new_from_tirs(tirs: &TIRS, time: impl Into<Epoch>) -> ECEF {
+        return CIO::new(time).ecef_from_tirs(tirs);
+    }
+
+    pub fn new_from_enu(enu: &ENU, reference_point: &WGS84Coord) -> ECEF {
+        // Given an ENU offset and the WGS84 origin of the local frame produces an ECEF coord
+        let rotation = generate_ecef_to_enu_matrix(reference_point);
+        return ECEF::new_from_enu_rot(enu, &rotation, &ECEF::new_from_wgs84(reference_point));
+    }
 
-        let x = polar_motion_matrix[0] * pef.x
-            + polar_motion_matrix[3] * pef.y
-            + polar_motion_matrix[6] * pef.z;
-        let y = polar_motion_matrix[1] * pef.x
-            + polar_motion_matrix[4] * pef.y
-            + polar_motion_matrix[7] * pef.z;
-        let z = polar_motion_matrix[2] * pef.x
-            + polar_motion_matrix[5] * pef.y
-            + polar_motion_matrix[8] * pef.z;
+    pub fn new_from_enu_rot(enu: &ENU, rotation_matrix: &Mat3, reference_point: &ECEF) -> ECEF {
+        // rotation_matrix^T * enu
+
+        let tmp = rotation_matrix.transpose_times(&enu.to_vec3());
+
+        let x = tmp.x + reference_point.x;
+        let y = tmp.y + reference_point.y;
+        let z = tmp.z + reference_point.z;
 
         return ECEF { x, y, z };
     }
 
-    // ! Broken
-    // pub fn new_from_enu(enu: &ENU, wgs84_ref: &WGS84Coord) -> ECEF {
-    //     // wgs84_ref is a constant reference point - MUST NOT VARY OVER THE COURSE OF THE PROGRAM
-    //     let e = enu.e;
-    //     let n = enu.n;
-    //     let u = enu.u;
-
-    //     let lambda = wgs84_ref.lat;
-    //     let phi = wgs84_ref.lon;
-
-    //     let sin_lambda = lambda.sin();
-    //     let cos_lambda = lambda.cos();
-
-    //     let sin_phi = phi.sin();
-    //     let cos_phi = phi.cos();
-
-    //     let x = -sin_lambda * e + cos_lambda * n;
-    //     let y = -cos_lambda * sin_phi * e
-    //         + -sin_lambda * sin_phi * n
-    //         + cos_phi * u;
-    //     let z =
-    //         cos_lambda * cos_phi * e + sin_lambda * cos_phi * n + sin_phi * u;
-
-    //     return ECEF { x, y, z };
-    // }
-
-    // ! Broken!
-    // pub fn new_from_ned(ned: &NED, wgs84_ref: &WGS84Coord) -> ECEF {
-    //     // wgs84_ref is a constant reference point - MUST NOT VARY OVER THE COURSE OF THE PROGRAM
-    //     let enu = ENU::new_from_ned(ned);
-    //     let ecef = ECEF::new_from_enu(&enu, wgs84_ref);
-    //     return ecef;
-    // }
-
-    pub fn new_from_ned_rot(ned: &NED, rotation_matrix: &Vec<f64>, reference_point: &ECEF) -> ECEF {
-        // rotation_matrix^T * ned
+    pub fn new_from_aer(aer: &AER, observer: &WGS84Coord) -> ECEF {
+        // Given look angles and range from an observer produces the target's ECEF coord
+        return ECEF::new_from_enu(&ENU::new_from_aer(aer), observer);
+    }
+
+    pub fn new_from_ned(ned: &NED, reference_point: &WGS84Coord) -> ECEF {
+        // Given a NED offset and the WGS84 origin of the local frame produces an ECEF coord
+        let rotation = generate_ecef_to_ned_matrix(reference_point);
+        return ECEF::new_from_ned_rot(ned, &rotation, &ECEF::new_from_wgs84(reference_point));
+    }
 
-        let ned_v = vec![ned.n, ned.e, ned.d];
+    pub fn new_from_ned_rot(ned: &NED, rotation_matrix: &Mat3, reference_point: &ECEF) -> ECEF {
+        // rotation_matrix^T * ned
 
-        let tmp = transpose_times_vec(&rotation_matrix, &ned_v);
+        let tmp = rotation_matrix.transpose_times(&ned.to_vec3());
 
-        let x = tmp[0] + reference_point.x;
-        let y = tmp[1] + reference_point.y;
-        let z = tmp[2] + reference_point.z;
+        let x = tmp.x + reference_point.x;
+        let y = tmp.y + reference_point.y;
+        let z = tmp.z + reference_point.z;
 
         return ECEF { x, y, z };
     }
@@ -128,38 +127,42 @@ pub struct ECEFVel {
 }
 
 impl ECEFVel {
-    pub fn new_from_teme_vel(teme: &TEME, teme_vel: &TEMEVel, utc_time: &NaiveDateTime) -> ECEFVel {
-        // Given a reference to a TEME coordinate, a reference to a TEME velocity and a reference to a NaiveDateTime produces an ECEF velocity
+    pub fn new_from_teme_vel(teme: &TEME, teme_vel: &TEMEVel, time: impl Into<Epoch>) -> ECEFVel {
+        // Given a reference to a TEME coordinate, a reference to a TEME velocity and a time produces an ECEF velocity
         // ECEF and TEME rotate with respect to one another so both a location and a velocity are needed
-        // Be careful of the NaiveDateTime time zones - I am assuming this is using UNIX seconds
-        // In general, you are best of making all NaiveDateTimes from UNIX timestamps
-        let (jday, frac) = old_maybe_broken_jday(utc_time);
-        let polar_motion_matrix = get_polar_motion_matrix(jday + frac);
-        let velocity_pef = PEFVel::new_from_teme_vel(teme, teme_vel, utc_time);
-        let velocity_pef = vec![velocity_pef.x_vel, velocity_pef.y_vel, velocity_pef.z_vel];
+        // A NaiveDateTime is taken as UTC, use an Epoch for other time scales or to give UT1 - UTC
+        let epoch: Epoch = time.into();
+        let eop = get_eop(&epoch);
+        let polar_motion_matrix = get_polar_motion_matrix(eop.xp, eop.yp);
+        let velocity_pef = PEFVel::new_from_teme_vel(teme, teme_vel, epoch);
+        let velocity_pef = Vec3::new(velocity_pef.x_vel, velocity_pef.y_vel, velocity_pef.z_vel);
 
-        let v_ecef = transpose_times_vec(&polar_motion_matrix, &velocity_pef);
+        let v_ecef = polar_motion_matrix.transpose_times(&velocity_pef);
 
         return ECEFVel {
-            x_vel: v_ecef[0],
-            y_vel: v_ecef[1],
-            z_vel: v_ecef[2],
+            x_vel: v_ecef.x,
+            y_vel: v_ecef.y,
+            z_vel: v_ecef.z,
         };
     }
 
+    pub fn new_from_gcrf_vel(gcrf: &GCRF, gcrf_vel: &GCRFVel, time: impl Into<Epoch>) -> ECEFVel {
+        // The Earth's rotation is taken off the inertial velocity, so the position is needed too
+        return CIO::new(time).ecef_vel_from_gcrf_vel(gcrf, gcrf_vel);
+    }
+
     pub fn new_from_wgs84(
         point1: &WGS84Coord,
-        point1_time: &NaiveDateTime,
+        point1_time: impl Into<Epoch>,
         point2: &WGS84Coord,
-        point2_time: &NaiveDateTime,
+        point2_time: impl Into<Epoch>,
     ) -> ECEFVel {
-        // Given a reference to two WGS84 points and a reference to a NaiveDateTime corresponding to both those locations produces an ECEF velocity
-        // Be careful of the NaiveDateTime time zones - I am assuming this is using UNIX seconds
-        // In general, you are best of making all NaiveDateTimes from UNIX timestamps
+        // Given a reference to two WGS84 points and the times corresponding to both those locations produces an ECEF velocity
+        // A NaiveDateTime is taken as UTC, the elapsed time counts any leap second in between
         // Note this assumes no acceleration between the points
         let ecef_1 = ECEF::new_from_wgs84(point1);
         let ecef_2 = ECEF::new_from_wgs84(point2);
-        let seconds_diff = (point2_time.timestamp() - point1_time.timestamp()) as f64;
+        let seconds_diff = point2_time.into().seconds_since(&point1_time.into());
         let x_vel = (ecef_2.x - ecef_1.x) / seconds_diff;
         let y_vel = (ecef_2.y - ecef_1.y) / seconds_diff;
         let z_vel = (ecef_2.z - ecef_1.z) / seconds_diff;
@@ -191,14 +194,13 @@ impl ECEFVel {
 
     pub fn new_from_ecef(
         point1: &ECEF,
-        point1_time: &NaiveDateTime,
+        point1_time: impl Into<Epoch>,
         point2: &ECEF,
-        point2_time: &NaiveDateTime,
+        point2_time: impl Into<Epoch>,
     ) -> ECEFVel {
-        // Given a reference to two ECEF points and a reference to a NaiveDateTime corresponding to both those locations produces an ECEF velocity
-        // Be careful of the NaiveDateTime time zones - I am assuming this is using UNIX seconds
-        // In general, you are best of making all NaiveDateTimes from UNIX timestamps
-        let seconds_diff = (point2_time.timestamp() - point1_time.timestamp()) as f64;
+        // Given a reference to two ECEF points and the times corresponding to both those locations produces an ECEF velocity
+        // A NaiveDateTime is taken as UTC, the elapsed time counts any leap second in between
+        let seconds_diff = point2_time.into().seconds_since(&point1_time.into());
         let x_vel = (point2.x - point1.x) / seconds_diff;
         let y_vel = (point2.y - point1.y) / seconds_diff;
         let z_vel = (point2.z - point1.z) / seconds_diff;
@@ -211,9 +213,7 @@ impl ECEFVel {
     }
 
     pub fn new_from_ecef_timestamp(point1: &ECEF, point2: &ECEF, seconds_diff: f64) -> ECEFVel {
-        // Given a reference to two ECEF points and a reference to a NaiveDateTime corresponding to both those locations produces an ECEF velocity
-        // Be careful of the NaiveDateTime time zones - I am assuming this is using UNIX seconds
-        // In general, you are best of making all NaiveDateTimes from UNIX timestamps
+        // Given a reference to two ECEF points and the number of seconds between them produces an ECEF velocity
         let x_vel = (point2.x - point1.x) / seconds_diff;
         let y_vel = (point2.y - point1.y) / seconds_diff;
         let z_vel = (point2.z - point1.z) / seconds_diff;
@@ -225,17 +225,17 @@ impl ECEFVel {
         };
     }
 
-    pub fn new_from_ned_rot(ned: &NEDVel, rotation_matrix: &Vec<f64>) -> ECEFVel {
+    pub fn new_from_ned_rot(ned: &NEDVel, rotation_matrix: &Mat3) -> ECEFVel {
         // rotation_matrix^T * ned_vel
 
-        let ned_v = vec![ned.n_vel, ned.e_vel, ned.d_vel];
+        let ned_v = Vec3::new(ned.n_vel, ned.e_vel, ned.d_vel);
 
-        let tmp = transpose_times_vec(rotation_matrix, &ned_v);
+        let tmp = rotation_matrix.transpose_times(&ned_v);
 
         return ECEFVel {
-            x_vel: tmp[0],
-            y_vel: tmp[1],
-            z_vel: tmp[2],
+            x_vel: tmp.x,
+            y_vel: tmp.y,
+            z_vel: tmp.z,
         };
     }
 
@@ -244,14 +244,29 @@ impl ECEFVel {
     }
 }
 
-pub fn generate_ecef_to_ned_matrix(initial_point: &WGS84Coord) -> Vec<f64> {
+#[derive(Debug, Clone, Default)]
+pub struct ECEFAccel {
+    // Wrapper for ECEF accelerations
+    pub x_accel: f64,
+    pub y_accel: f64,
+    pub z_accel: f64,
+}
+
+impl ECEFAccel {
+    pub fn magnitude(&self) -> f64 {
+        return (self.x_accel.powf(2_f64) + self.y_accel.powf(2_f64) + self.z_accel.powf(2_f64))
+            .sqrt();
+    }
+}
+
+pub fn generate_ecef_to_ned_matrix(initial_point: &WGS84Coord) -> Mat3 {
     let sin_lon = initial_point.get_lon_radians().sin();
     let cos_lon = initial_point.get_lon_radians().cos();
 
     let sin_lat = initial_point.get_lat_radians().sin();
     let cos_lat = initial_point.get_lat_radians().cos();
 
-    let rotation = vec![
+    let rotation = Mat3::new_from_array([
         -sin_lat * cos_lon,
         -sin_lat * sin_lon,
         cos_lat,
@@ -261,28 +276,43 @@ pub fn generate_ecef_to_ned_matrix(initial_point: &WGS84Coord) -> Vec<f64> {
         -cos_lat * cos_lon,
         -cos_lat * sin_lon,
         -sin_lat,
-    ];
+    ]);
+
+    return rotation;
+}
+
+pub fn generate_ecef_to_enu_matrix(initial_point: &WGS84Coord) -> Mat3 {
+    // The same axes as generate_ecef_to_ned_matrix, with north and east swapped and down flipped to up
+    let sin_lon = initial_point.get_lon_radians().sin();
+    let cos_lon = initial_point.get_lon_radians().cos();
+
+    let sin_lat = initial_point.get_lat_radians().sin();
+    let cos_lat = initial_point.get_lat_radians().cos();
+
+    let rotation = Mat3::new_from_array([
+        -sin_lon,
+        cos_lon,
+        0_f64,
+        -sin_lat * cos_lon,
+        -sin_lat * sin_lon,
+        cos_lat,
+        cos_lat * cos_lon,
+        cos_lat * sin_lon,
+        sin_lat,
+    ]);
 
     return rotation;
 }
 
-pub fn construct_ecef_to_ned_jacobian(ecef_to_ned: &Vec<f64>) -> Vec<f64> {
-    let jacobian_vec = vec![
-        vec![ecef_to_ned[0], ecef_to_ned[1], ecef_to_ned[2]],
-        vec![0.0; 3],
-        vec![ecef_to_ned[3], ecef_to_ned[4], ecef_to_ned[5]],
-        vec![0.0; 3],
-        vec![ecef_to_ned[6], ecef_to_ned[7], ecef_to_ned[8]],
-        vec![0.0; 3],
-        vec![0.0; 3],
-        vec![ecef_to_ned[0], ecef_to_ned[1], ecef_to_ned[2]],
-        vec![0.0; 3],
-        vec![ecef_to_ned[3], ecef_to_ned[4], ecef_to_ned[5]],
-        vec![0.0; 3],
-        vec![ecef_to_ned[6], ecef_to_ned[7], ecef_to_ned[8]],
-    ];
-
-    let jacobian_matrix = jacobian_vec.concat();
-
-    return jacobian_matrix;
+pub fn construct_ecef_to_ned_jacobian(ecef_to_ned: &Mat3) -> [f64; 36] {
+    // Block diagonal 6x6 in row major order, rotating a stacked position and velocity
+    let mut jacobian = [0_f64; 36];
+    for row in 0..3 {
+        for column in 0..3 {
+            jacobian[row * 6 + column] = ecef_to_ned[(row, column)];
+            jacobian[(row + 3) * 6 + column + 3] = ecef_to_ned[(row, column)];
+        }
+    }
+
+    return jacobian;
 }
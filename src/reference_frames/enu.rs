@@ -1,7 +1,15 @@
-use super::{ecef::ECEF, ned::NED, wgs84::WGS84Coord};
+use crate::matrix::{Mat3, Vec3};
 
-#[derive(Debug, Clone)]
+use super::{
+    aer::AER,
+    ecef::{generate_ecef_to_enu_matrix, ECEF},
+    ned::NED,
+    wgs84::WGS84Coord,
+};
+
+#[derive(Debug, Clone, Default)]
 pub struct ENU {
+    // Wrapper for ENU coordinates, always relative to some reference point
     pub e: f64,
     pub n: f64,
     pub u: f64,
@@ -12,25 +20,42 @@ impl ENU {
         return ENU { e, n, u };
     }
 
-    pub fn new_from_ecef(ecef: &ECEF) -> ENU {
-        let x = ecef.x;
-        let y = ecef.y;
-        let z = ecef.z;
-        let wgs84 = WGS84Coord::new_from_ecef_struct(ecef);
-        let lambda = wgs84.get_lat_radians();
-        let phi = wgs84.get_lon_radians();
+    pub fn new_from_vec3(enu_vec: &Vec3) -> ENU {
+        return ENU::new_from_raw(enu_vec.x, enu_vec.y, enu_vec.z);
+    }
+
+    pub fn new_from_ecef(ecef: &ECEF, reference_point: &WGS84Coord) -> ENU {
+        // Given an ECEF coord and the WGS84 origin of the local frame returns the ENU offset from the origin
+        // The axes are east, north and up (along the ellipsoid normal) at the reference point
+        let rotation = generate_ecef_to_enu_matrix(reference_point);
+        return ENU::new_from_ecef_rot(ecef, &rotation, &ECEF::new_from_wgs84(reference_point));
+    }
+
+    pub fn new_from_ecef_rot(ecef: &ECEF, rotation_matrix: &Mat3, reference_point: &ECEF) -> ENU {
+        // As new_from_ecef with the rotation from generate_ecef_to_enu_matrix and the origin in ECEF
+        // worked out once, for converting many points relative to the same origin
+        let x = ecef.x - reference_point.x;
+        let y = ecef.y - reference_point.y;
+        let z = ecef.z - reference_point.z;
+        let ecef_vec = Vec3::new(x, y, z);
 
-        let sin_lambda = lambda.sin();
-        let cos_lambda = lambda.cos();
+        return ENU::new_from_vec3(&(*rotation_matrix * ecef_vec));
+    }
 
-        let sin_phi = phi.sin();
-        let cos_phi = phi.cos();
+    pub fn new_from_wgs84(wgs84: &WGS84Coord, reference_point: &WGS84Coord) -> ENU {
+        return ENU::new_from_ecef(&ECEF::new_from_wgs84(wgs84), reference_point);
+    }
 
-        let e = -sin_lambda * x + cos_lambda * y;
-        let n = -cos_lambda * sin_phi * x + -sin_lambda * sin_phi * y + cos_phi * z;
-        let u = cos_lambda * cos_phi * x + sin_lambda * cos_phi * y + sin_phi * z;
+    pub fn new_from_aer(aer: &AER) -> ENU {
+        let (sin_elevation, cos_elevation) = aer.elevation.sin_cos();
+        let (sin_azimuth, cos_azimuth) = aer.azimuth.sin_cos();
+        let horizontal = aer.range * cos_elevation;
 
-        return ENU { e, n, u };
+        return ENU {
+            e: horizontal * sin_azimuth,
+            n: horizontal * cos_azimuth,
+            u: aer.range * sin_elevation,
+        };
     }
 
     pub fn new_from_ned(ned: &NED) -> ENU {
@@ -40,4 +65,22 @@ impl ENU {
             u: -ned.d,
         };
     }
+
+    pub fn to_vec3(&self) -> Vec3 {
+        return Vec3::new(self.e, self.n, self.u);
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ENUVel {
+    pub e_vel: f64,
+    pub n_vel: f64,
+    pub u_vel: f64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ENUAccel {
+    pub e_accel: f64,
+    pub n_accel: f64,
+    pub u_accel: f64,
 }
@@ -0,0 +1,469 @@
+// The IAU-76/FK5 reduction between the Earth fixed PEF and the inertial GCRF (Vallado, chapter 3)
+// GCRF --precession--> MOD --nutation--> TOD --sidereal time--> PEF
+use std::f64::consts::TAU;
+
+use crate::{
+    constants::{ARCSEC_TO_RAD, EARTH_ROTATION_RATE},
+    matrix::{Mat3, Vec3},
+    time::{
+        eop::get_eop,
+        epoch::{Epoch, TimeScale},
+        julian_date::JulianDate,
+        sidereal::gast_iau82,
+    },
+    types::Radians,
+    utils::get_polar_motion_matrix,
+};
+
+use super::{
+    ecef::{ECEFVel, ECEF},
+    gcrf::{GCRFVel, GCRF},
+    pef::{PEFVel, PEF},
+};
+
+// The IAU 1980 nutation series. Multipliers of the Delaunay arguments l, l', F, D and Omega, then
+// the longitude and obliquity coefficients and their rates per Julian century, in 0.1 mas
+#[rustfmt::skip]
+const NUTATION_1980: [([i8; 5], f64, f64, f64, f64); 106] = [
+    ([ 0,  0,  0,  0,  1], -171996.0, -174.2,  92025.0,  8.9),
+    ([ 0,  0,  0,  0,  2],    2062.0,    0.2,   -895.0,  0.5),
+    ([-2,  0,  2,  0,  1],      46.0,    0.0,    -24.0,  0.0),
+    ([ 2,  0, -2,  0,  0],      11.0,    0.0,      0.0,  0.0),
+    ([-2,  0,  2,  0,  2],      -3.0,    0.0,      1.0,  0.0),
+    ([ 1, -1,  0, -1,  0],      -3.0,    0.0,      0.0,  0.0),
+    ([ 0, -2,  2, -2,  1],      -2.0,    0.0,      1.0,  0.0),
+    ([ 2,  0, -2,  0,  1],       1.0,    0.0,      0.0,  0.0),
+    ([ 0,  0,  2, -2,  2],  -13187.0,   -1.6,   5736.0, -3.1),
+    ([ 0,  1,  0,  0,  0],    1426.0,   -3.4,     54.0, -0.1),
+    ([ 0,  1,  2, -2,  2],    -517.0,    1.2,    224.0, -0.6),
+    ([ 0, -1,  2, -2,  2],     217.0,   -0.5,    -95.0,  0.3),
+    ([ 0,  0,  2, -2,  1],     129.0,    0.1,    -70.0,  0.0),
+    ([ 2,  0,  0, -2,  0],      48.0,    0.0,      1.0,  0.0),
+    ([ 0,  0,  2, -2,  0],     -22.0,    0.0,      0.0,  0.0),
+    ([ 0,  2,  0,  0,  0],      17.0,   -0.1,      0.0,  0.0),
+    ([ 0,  1,  0,  0,  1],     -15.0,    0.0,      9.0,  0.0),
+    ([ 0,  2,  2, -2,  2],     -16.0,    0.1,      7.0,  0.0),
+    ([ 0, -1,  0,  0,  1],     -12.0,    0.0,      6.0,  0.0),
+    ([-2,  0,  0,  2,  1],      -6.0,    0.0,      3.0,  0.0),
+    ([ 0, -1,  2, -2,  1],      -5.0,    0.0,      3.0,  0.0),
+    ([ 2,  0,  0, -2,  1],       4.0,    0.0,     -2.0,  0.0),
+    ([ 0,  1,  2, -2,  1],       4.0,    0.0,     -2.0,  0.0),
+    ([ 1,  0,  0, -1,  0],      -4.0,    0.0,      0.0,  0.0),
+    ([ 2,  1,  0, -2,  0],       1.0,    0.0,      0.0,  0.0),
+    ([ 0,  0, -2,  2,  1],       1.0,    0.0,      0.0,  0.0),
+    ([ 0,  1, -2,  2,  0],      -1.0,    0.0,      0.0,  0.0),
+    ([ 0,  1,  0,  0,  2],       1.0,    0.0,      0.0,  0.0),
+    ([-1,  0,  0,  1,  1],       1.0,    0.0,      0.0,  0.0),
+    ([ 0,  1,  2, -2,  0],      -1.0,    0.0,      0.0,  0.0),
+    ([ 0,  0,  2,  0,  2],   -2274.0,   -0.2,    977.0, -0.5),
+    ([ 1,  0,  0,  0,  0],     712.0,    0.1,     -7.0,  0.0),
+    ([ 0,  0,  2,  0,  1],    -386.0,   -0.4,    200.0,  0.0),
+    ([ 1,  0,  2,  0,  2],    -301.0,    0.0,    129.0, -0.1),
+    ([ 1,  0,  0, -2,  0],    -158.0,    0.0,     -1.0,  0.0),
+    ([-1,  0,  2,  0,  2],     123.0,    0.0,    -53.0,  0.0),
+    ([ 0,  0,  0,  2,  0],      63.0,    0.0,     -2.0,  0.0),
+    ([ 1,  0,  0,  0,  1],      63.0,    0.1,    -33.0,  0.0),
+    ([-1,  0,  0,  0,  1],     -58.0,   -0.1,     32.0,  0.0),
+    ([-1,  0,  2,  2,  2],     -59.0,    0.0,     26.0,  0.0),
+    ([ 1,  0,  2,  0,  1],     -51.0,    0.0,     27.0,  0.0),
+    ([ 0,  0,  2,  2,  2],     -38.0,    0.0,     16.0,  0.0),
+    ([ 2,  0,  0,  0,  0],      29.0,    0.0,     -1.0,  0.0),
+    ([ 1,  0,  2, -2,  2],      29.0,    0.0,    -12.0,  0.0),
+    ([ 2,  0,  2,  0,  2],     -31.0,    0.0,     13.0,  0.0),
+    ([ 0,  0,  2,  0,  0],      26.0,    0.0,     -1.0,  0.0),
+    ([-1,  0,  2,  0,  1],      21.0,    0.0,    -10.0,  0.0),
+    ([-1,  0,  0,  2,  1],      16.0,    0.0,     -8.0,  0.0),
+    ([ 1,  0,  0, -2,  1],     -13.0,    0.0,      7.0,  0.0),
+    ([-1,  0,  2,  2,  1],     -10.0,    0.0,      5.0,  0.0),
+    ([ 1,  1,  0, -2,  0],      -7.0,    0.0,      0.0,  0.0),
+    ([ 0,  1,  2,  0,  2],       7.0,    0.0,     -3.0,  0.0),
+    ([ 0, -1,  2,  0,  2],      -7.0,    0.0,      3.0,  0.0),
+    ([ 1,  0,  2,  2,  2],      -8.0,    0.0,      3.0,  0.0),
+    ([ 1,  0,  0,  2,  0],       6.0,    0.0,      0.0,  0.0),
+    ([ 2,  0,  2, -2,  2],       6.0,    0.0,     -3.0,  0.0),
+    ([ 0,  0,  0,  2,  1],      -6.0,    0.0,      3.0,  0.0),
+    ([ 0,  0,  2,  2,  1],      -7.0,    0.0,      3.0,  0.0),
+    ([ 1,  0,  2, -2,  1],       6.0,    0.0,     -3.0,  0.0),
+    ([ 0,  0,  0, -2,  1],      -5.0,    0.0,      3.0,  0.0),
+    ([ 1, -1,  0,  0,  0],       5.0,    0.0,      0.0,  0.0),
+    ([ 2,  0,  2,  0,  1],      -5.0,    0.0,      3.0,  0.0),
+    ([ 0,  1,  0, -2,  0],      -4.0,    0.0,      0.0,  0.0),
+    ([ 1,  0, -2,  0,  0],       4.0,    0.0,      0.0,  0.0),
+    ([ 0,  0,  0,  1,  0],      -4.0,    0.0,      0.0,  0.0),
+    ([ 1,  1,  0,  0,  0],      -3.0,    0.0,      0.0,  0.0),
+    ([ 1,  0,  2,  0,  0],       3.0,    0.0,      0.0,  0.0),
+    ([ 1, -1,  2,  0,  2],      -3.0,    0.0,      1.0,  0.0),
+    ([-1, -1,  2,  2,  2],      -3.0,    0.0,      1.0,  0.0),
+    ([-2,  0,  0,  0,  1],      -2.0,    0.0,      1.0,  0.0),
+    ([ 3,  0,  2,  0,  2],      -3.0,    0.0,      1.0,  0.0),
+    ([ 0, -1,  2,  2,  2],      -3.0,    0.0,      1.0,  0.0),
+    ([ 1,  1,  2,  0,  2],       2.0,    0.0,     -1.0,  0.0),
+    ([-1,  0,  2, -2,  1],      -2.0,    0.0,      1.0,  0.0),
+    ([ 2,  0,  0,  0,  1],       2.0,    0.0,     -1.0,  0.0),
+    ([ 1,  0,  0,  0,  2],      -2.0,    0.0,      1.0,  0.0),
+    ([ 3,  0,  0,  0,  0],       2.0,    0.0,      0.0,  0.0),
+    ([ 0,  0,  2,  1,  2],       2.0,    0.0,     -1.0,  0.0),
+    ([-1,  0,  0,  0,  2],       1.0,    0.0,     -1.0,  0.0),
+    ([ 1,  0,  0, -4,  0],      -1.0,    0.0,      0.0,  0.0),
+    ([-2,  0,  2,  2,  2],       1.0,    0.0,     -1.0,  0.0),
+    ([-1,  0,  2,  4,  2],      -2.0,    0.0,      1.0,  0.0),
+    ([ 2,  0,  0, -4,  0],      -1.0,    0.0,      0.0,  0.0),
+    ([ 1,  1,  2, -2,  2],       1.0,    0.0,     -1.0,  0.0),
+    ([ 1,  0,  2,  2,  1],      -1.0,    0.0,      1.0,  0.0),
+    ([-2,  0,  2,  4,  2],      -1.0,    0.0,      1.0,  0.0),
+    ([-1,  0,  4,  0,  2],       1.0,    0.0,      0.0,  0.0),
+    ([ 1, -1,  0, -2,  0],       1.0,    0.0,      0.0,  0.0),
+    ([ 2,  0,  2, -2,  1],       1.0,    0.0,     -1.0,  0.0),
+    ([ 2,  0,  2,  2,  2],      -1.0,    0.0,      0.0,  0.0),
+    ([ 1,  0,  0,  2,  1],      -1.0,    0.0,      0.0,  0.0),
+    ([ 0,  0,  4, -2,  2],       1.0,    0.0,      0.0,  0.0),
+    ([ 3,  0,  2, -2,  2],       1.0,    0.0,      0.0,  0.0),
+    ([ 1,  0,  2, -2,  0],      -1.0,    0.0,      0.0,  0.0),
+    ([ 0,  1,  2,  0,  1],       1.0,    0.0,      0.0,  0.0),
+    ([-1, -1,  0,  2,  1],       1.0,    0.0,      0.0,  0.0),
+    ([ 0,  0, -2,  0,  1],      -1.0,    0.0,      0.0,  0.0),
+    ([ 0,  0,  2, -1,  2],      -1.0,    0.0,      0.0,  0.0),
+    ([ 0,  1,  0,  2,  0],      -1.0,    0.0,      0.0,  0.0),
+    ([ 1,  0, -2, -2,  0],      -1.0,    0.0,      0.0,  0.0),
+    ([ 0, -1,  2,  0,  1],      -1.0,    0.0,      0.0,  0.0),
+    ([ 1,  1,  0, -2,  1],      -1.0,    0.0,      0.0,  0.0),
+    ([ 1,  0, -2,  2,  0],      -1.0,    0.0,      0.0,  0.0),
+    ([ 2,  0,  0,  2,  0],       1.0,    0.0,      0.0,  0.0),
+    ([ 0,  0,  2,  4,  2],      -1.0,    0.0,      0.0,  0.0),
+    ([ 0,  1,  0,  1,  0],       1.0,    0.0,      0.0,  0.0),
+];
+
+// The two extra terms in the equation of the equinoxes were adopted from 1997-02-27
+const EQUINOX_TERMS_START_MJD: f64 = 50506.;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Nutation {
+    // Nutation in longitude and obliquity, the mean obliquity of the ecliptic and the longitude of
+    // the Moon's ascending node, all for one TT instant
+    pub dpsi: Radians,
+    pub deps: Radians,
+    pub mean_obliquity: Radians,
+    pub moon_node: Radians,
+    tt_mjd: f64,
+}
+
+impl Nutation {
+    pub fn new_from_epoch(time: impl Into<Epoch>) -> Nutation {
+        // The 106 term IAU 1980 series
+        let tt = JulianDate::new_from_epoch(&time.into(), TimeScale::TT);
+        let t = tt.get_julian_centuries();
+
+        // Delaunay arguments, the whole revolutions per century are kept apart to save precision
+        let argument = |c0: f64, c1: f64, c2: f64, c3: f64, revolutions: f64| -> Radians {
+            let arcseconds = c0 + (c1 + (c2 + c3 * t) * t) * t;
+            return arcseconds * ARCSEC_TO_RAD + (revolutions * t).rem_euclid(1.) * TAU;
+        };
+        let l = argument(485866.733, 715922.633, 31.310, 0.064, 1325.);
+        let lp = argument(1287099.804, 1292581.224, -0.577, -0.012, 99.);
+        let f = argument(335778.877, 295263.137, -13.257, 0.011, 1342.);
+        let d = argument(1072261.307, 1105601.328, -6.891, 0.019, 1236.);
+        let om = argument(450160.280, -482890.539, 7.455, 0.008, -5.);
+
+        // Smallest terms first
+        let (mut dpsi, mut deps) = (0., 0.);
+        for (k, sp, spt, ce, cet) in NUTATION_1980.iter().rev() {
+            let k = k.map(|k| k as f64);
+            let angle = k[0] * l + k[1] * lp + k[2] * f + k[3] * d + k[4] * om;
+            dpsi += (sp + spt * t) * angle.sin();
+            deps += (ce + cet * t) * angle.cos();
+        }
+
+        return Nutation {
+            dpsi: dpsi * 1e-4 * ARCSEC_TO_RAD,
+            deps: deps * 1e-4 * ARCSEC_TO_RAD,
+            mean_obliquity: mean_obliquity(t),
+            moon_node: om,
+            tt_mjd: tt.get_mjd(),
+        };
+    }
+
+    pub fn add_corrections(&mut self, ddpsi: Radians, ddeps: Radians) {
+        // The IERS published corrections to the 1980 series (dPsi and dEps in finals.all), which
+        // take the result from the FK5 J2000 frame to the GCRF
+        self.dpsi += ddpsi;
+        self.deps += ddeps;
+    }
+
+    pub fn get_true_obliquity(&self) -> Radians {
+        return self.mean_obliquity + self.deps;
+    }
+
+    pub fn get_equation_of_equinoxes(&self) -> Radians {
+        // The difference between apparent and mean sidereal time
+        let mut equation = self.dpsi * self.mean_obliquity.cos();
+        if self.tt_mjd > EQUINOX_TERMS_START_MJD {
+            equation += (0.00264 * self.moon_node.sin() + 0.000063 * (2. * self.moon_node).sin())
+                * ARCSEC_TO_RAD;
+        }
+        return equation;
+    }
+
+    pub fn get_matrix(&self) -> Mat3 {
+        // MOD to TOD
+        return Mat3::rotation_x(-self.get_true_obliquity())
+            * Mat3::rotation_z(-self.dpsi)
+            * Mat3::rotation_x(self.mean_obliquity);
+    }
+}
+
+pub fn mean_obliquity(tt_centuries: f64) -> Radians {
+    // IAU 1980 obliquity of the ecliptic
+    let t = tt_centuries;
+    return (84381.448 + (-46.8150 + (-0.00059 + 0.001813 * t) * t) * t) * ARCSEC_TO_RAD;
+}
+
+pub fn get_precession_matrix(time: impl Into<Epoch>) -> Mat3 {
+    // GCRF to MOD, IAU 1976 precession from J2000
+    let tt = JulianDate::new_from_epoch(&time.into(), TimeScale::TT);
+    let t = tt.get_julian_centuries();
+
+    let zeta = (2306.2181 + (0.30188 + 0.017998 * t) * t) * t * ARCSEC_TO_RAD;
+    let theta = (2004.3109 + (-0.42665 - 0.041833 * t) * t) * t * ARCSEC_TO_RAD;
+    let z = (2306.2181 + (1.09468 + 0.018203 * t) * t) * t * ARCSEC_TO_RAD;
+
+    return Mat3::rotation_z(-z) * Mat3::rotation_y(theta) * Mat3::rotation_z(-zeta);
+}
+
+#[derive(Debug, Clone)]
+pub struct MOD {
+    // Mean of date, the equator and equinox moved on from J2000 by precession only
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl MOD {
+    pub fn new_from_gcrf(gcrf: &GCRF, time: impl Into<Epoch>) -> MOD {
+        return FK5::new(time).mod_from_gcrf(gcrf);
+    }
+
+    pub fn new_from_tod(tod: &TOD, time: impl Into<Epoch>) -> MOD {
+        return FK5::new(time).mod_from_tod(tod);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TOD {
+    // True of date, the equator and equinox of date including nutation
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl TOD {
+    pub fn new_from_mod(mean_of_date: &MOD, time: impl Into<Epoch>) -> TOD {
+        return FK5::new(time).tod_from_mod(mean_of_date);
+    }
+
+    pub fn new_from_pef(pef: &PEF, time: impl Into<Epoch>) -> TOD {
+        return FK5::new(time).tod_from_pef(pef);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FK5 {
+    // Every rotation of the reduction for one instant, so that converting many states at the same
+    // time only evaluates the series once. Polar motion, UT1 - UTC and LOD come from the EOP table
+    precession: Mat3,
+    nutation: Nutation,
+    nutation_matrix: Mat3,
+    gast: Radians,
+    polar_motion: Mat3,
+    omega_earth: f64,
+}
+
+impl FK5 {
+    pub fn new(time: impl Into<Epoch>) -> FK5 {
+        // Without nutation corrections the inertial end is strictly the FK5 J2000 frame
+        let epoch: Epoch = time.into();
+        return FK5::new_from_nutation(epoch, &Nutation::new_from_epoch(epoch));
+    }
+
+    pub fn new_from_nutation(time: impl Into<Epoch>, nutation: &Nutation) -> FK5 {
+        // With a nutation that has had the IERS corrections added the inertial end is the GCRF
+        let epoch: Epoch = time.into();
+        let eop = get_eop(&epoch);
+        return FK5 {
+            precession: get_precession_matrix(epoch),
+            nutation: *nutation,
+            nutation_matrix: nutation.get_matrix(),
+            gast: gast_iau82(
+                &JulianDate::new_from_epoch(&epoch, TimeScale::UT1),
+                nutation,
+            ),
+            polar_motion: get_polar_motion_matrix(eop.xp, eop.yp),
+            omega_earth: EARTH_ROTATION_RATE * (1. - eop.lod / 86400.),
+        };
+    }
+
+    pub fn get_precession_matrix(&self) -> &Mat3 {
+        return &self.precession;
+    }
+
+    pub fn get_nutation(&self) -> &Nutation {
+        return &self.nutation;
+    }
+
+    pub fn get_gast(&self) -> Radians {
+        return self.gast;
+    }
+
+    // Positions
+
+    pub fn mod_from_gcrf(&self, gcrf: &GCRF) -> MOD {
+        let v = self.precession * Vec3::new(gcrf.x, gcrf.y, gcrf.z);
+        return MOD {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        };
+    }
+
+    pub fn gcrf_from_mod(&self, mean_of_date: &MOD) -> GCRF {
+        let v = self.precession.transpose_times(&Vec3::new(
+            mean_of_date.x,
+            mean_of_date.y,
+            mean_of_date.z,
+        ));
+        return GCRF {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        };
+    }
+
+    pub fn tod_from_mod(&self, mean_of_date: &MOD) -> TOD {
+        let v = self.nutation_matrix * Vec3::new(mean_of_date.x, mean_of_date.y, mean_of_date.z);
+        return TOD {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        };
+    }
+
+    pub fn mod_from_tod(&self, tod: &TOD) -> MOD {
+        let v = self
+            .nutation_matrix
+            .transpose_times(&Vec3::new(tod.x, tod.y, tod.z));
+        return MOD {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        };
+    }
+
+    pub fn pef_from_tod(&self, tod: &TOD) -> PEF {
+        let v = Mat3::rotation_z(self.gast) * Vec3::new(tod.x, tod.y, tod.z);
+        return PEF {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        };
+    }
+
+    pub fn tod_from_pef(&self, pef: &PEF) -> TOD {
+        let v = Mat3::rotation_z(self.gast).transpose_times(&Vec3::new(pef.x, pef.y, pef.z));
+        return TOD {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        };
+    }
+
+    pub fn gcrf_from_pef(&self, pef: &PEF) -> GCRF {
+        return self.gcrf_from_mod(&self.mod_from_tod(&self.tod_from_pef(pef)));
+    }
+
+    pub fn pef_from_gcrf(&self, gcrf: &GCRF) -> PEF {
+        return self.pef_from_tod(&self.tod_from_mod(&self.mod_from_gcrf(gcrf)));
+    }
+
+    pub fn gcrf_from_ecef(&self, ecef: &ECEF) -> GCRF {
+        let pef = self.polar_motion * Vec3::new(ecef.x, ecef.y, ecef.z);
+        return self.gcrf_from_pef(&PEF {
+            x: pef.x,
+            y: pef.y,
+            z: pef.z,
+        });
+    }
+
+    pub fn ecef_from_gcrf(&self, gcrf: &GCRF) -> ECEF {
+        let pef = self.pef_from_gcrf(gcrf);
+        let v = self
+            .polar_motion
+            .transpose_times(&Vec3::new(pef.x, pef.y, pef.z));
+        return ECEF {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        };
+    }
+
+    // Velocities, which also pick up the Earth's rotation between PEF and TOD
+
+    pub fn gcrf_vel_from_pef_vel(&self, pef: &PEF, pef_vel: &PEFVel) -> GCRFVel {
+        // v_tod = R3(-gast) (v_pef + w x r_pef)
+        let v = Vec3::new(
+            pef_vel.x_vel - self.omega_earth * pef.y,
+            pef_vel.y_vel + self.omega_earth * pef.x,
+            pef_vel.z_vel,
+        );
+        let tod = Mat3::rotation_z(self.gast).transpose_times(&v);
+        let v = self
+            .precession
+            .transpose_times(&self.nutation_matrix.transpose_times(&tod));
+        return GCRFVel {
+            x_vel: v.x,
+            y_vel: v.y,
+            z_vel: v.z,
+        };
+    }
+
+    pub fn pef_vel_from_gcrf_vel(&self, gcrf: &GCRF, gcrf_vel: &GCRFVel) -> PEFVel {
+        // v_pef = R3(gast) v_tod - w x r_pef
+        let pef = self.pef_from_gcrf(gcrf);
+        let v = Vec3::new(gcrf_vel.x_vel, gcrf_vel.y_vel, gcrf_vel.z_vel);
+        let v = Mat3::rotation_z(self.gast) * (self.nutation_matrix * (self.precession * v));
+        return PEFVel {
+            x_vel: v.x + self.omega_earth * pef.y,
+            y_vel: v.y - self.omega_earth * pef.x,
+            z_vel: v.z,
+        };
+    }
+
+    pub fn gcrf_vel_from_ecef_vel(&self, ecef: &ECEF, ecef_vel: &ECEFVel) -> GCRFVel {
+        let pef = self.polar_motion * Vec3::new(ecef.x, ecef.y, ecef.z);
+        let pef_vel = self.polar_motion * Vec3::new(ecef_vel.x_vel, ecef_vel.y_vel, ecef_vel.z_vel);
+        return self.gcrf_vel_from_pef_vel(
+            &PEF {
+                x: pef.x,
+                y: pef.y,
+                z: pef.z,
+            },
+            &PEFVel {
+                x_vel: pef_vel.x,
+                y_vel: pef_vel.y,
+                z_vel: pef_vel.z,
+            },
+        );
+    }
+
+    pub fn ecef_vel_from_gcrf_vel(&self, gcrf: &GCRF, gcrf_vel: &GCRFVel) -> ECEFVel {
+        let pef_vel = self.pef_vel_from_gcrf_vel(gcrf, gcrf_vel);
+        let v = self.polar_motion.transpose_times(&Vec3::new(
+            pef_vel.x_vel,
+            pef_vel.y_vel,
+            pef_vel.z_vel,
+        ));
+        return ECEFVel {
+            x_vel: v.x,
+            y_vel: v.y,
+            z_vel: v.z,
+        };
+    }
+}
@@ -0,0 +1,47 @@
+use crate::time::epoch::Epoch;
+
+use super::{
+    cio::{CIO, CIRS},
+    ecef::{ECEFVel, ECEF},
+    fk5::{FK5, MOD},
+};
+
+#[derive(Debug, Clone)]
+pub struct GCRF {
+    // The inertial frame, aligned with J2000 to within a few tens of milliarcseconds
+    // Through the FK5 reduction without nutation corrections this is strictly FK5 J2000
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl GCRF {
+    pub fn new_from_mod(mean_of_date: &MOD, time: impl Into<Epoch>) -> GCRF {
+        return FK5::new(time).gcrf_from_mod(mean_of_date);
+    }
+
+    pub fn new_from_cirs(cirs: &CIRS, time: impl Into<Epoch>) -> GCRF {
+        return CIO::new(time).gcrf_from_cirs(cirs);
+    }
+
+    pub fn new_from_ecef(ecef: &ECEF, time: impl Into<Epoch>) -> GCRF {
+        // Through the IAU 2006/2000 reduction, as FrameGraph does by default. For many states at
+        // the same time build a CIO once and use it directly, or use FK5 for the older reduction
+        return CIO::new(time).gcrf_from_ecef(ecef);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GCRFVel {
+    // Wrapper for GCRF velocities
+    pub x_vel: f64,
+    pub y_vel: f64,
+    pub z_vel: f64,
+}
+
+impl GCRFVel {
+    pub fn new_from_ecef_vel(ecef: &ECEF, ecef_vel: &ECEFVel, time: impl Into<Epoch>) -> GCRFVel {
+        // The inertial velocity includes the Earth's rotation, so the position is needed too
+        return CIO::new(time).gcrf_vel_from_ecef_vel(ecef, ecef_vel);
+    }
+}
@@ -0,0 +1,239 @@
+use crate::matrix::{Mat3, Vec3};
+
+use super::{
+    aer::AER,
+    ecef::{generate_ecef_to_enu_matrix, generate_ecef_to_ned_matrix, ECEFAccel, ECEFVel, ECEF},
+    enu::{ENUAccel, ENUVel, ENU},
+    ned::{NEDAccel, NEDVel, NED},
+    wgs84::WGS84Coord,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TangentPlaneAxes {
+    // Which set of local axes get_rotation hands out for use with the *_rot functions
+    NED,
+    ENU,
+}
+
+#[derive(Debug, Clone)]
+pub struct LocalTangentPlane {
+    // A local frame fixed to the Earth at a reference point, holding the point in both WGS84 and
+    // ECEF along with the rotation from ECEF so that they can't get out of step with each other
+    // Positions are offsets from the reference point, velocities and accelerations are only rotated
+    reference_point: WGS84Coord,
+    reference_ecef: ECEF,
+    axes: TangentPlaneAxes,
+    ned_rotation: Mat3,
+    enu_rotation: Mat3,
+}
+
+impl LocalTangentPlane {
+    pub fn new(reference_point: &WGS84Coord, axes: TangentPlaneAxes) -> LocalTangentPlane {
+        return LocalTangentPlane {
+            reference_point: reference_point.clone(),
+            reference_ecef: ECEF::new_from_wgs84(reference_point),
+            axes,
+            ned_rotation: generate_ecef_to_ned_matrix(reference_point),
+            enu_rotation: generate_ecef_to_enu_matrix(reference_point),
+        };
+    }
+
+    pub fn new_ned(reference_point: &WGS84Coord) -> LocalTangentPlane {
+        return LocalTangentPlane::new(reference_point, TangentPlaneAxes::NED);
+    }
+
+    pub fn new_enu(reference_point: &WGS84Coord) -> LocalTangentPlane {
+        return LocalTangentPlane::new(reference_point, TangentPlaneAxes::ENU);
+    }
+
+    pub fn get_reference_point(&self) -> &WGS84Coord {
+        return &self.reference_point;
+    }
+
+    pub fn get_reference_ecef(&self) -> &ECEF {
+        return &self.reference_ecef;
+    }
+
+    pub fn get_axes(&self) -> TangentPlaneAxes {
+        return self.axes;
+    }
+
+    pub fn get_rotation(&self) -> &Mat3 {
+        // The ECEF to local rotation matching the axes, as generate_ecef_to_ned_matrix or
+        // generate_ecef_to_enu_matrix would give for the reference point
+        return match self.axes {
+            TangentPlaneAxes::NED => &self.ned_rotation,
+            TangentPlaneAxes::ENU => &self.enu_rotation,
+        };
+    }
+
+    // Positions
+
+    pub fn ned_from_ecef(&self, ecef: &ECEF) -> NED {
+        return NED::new_from_ecef_rot(ecef, &self.ned_rotation, &self.reference_ecef);
+    }
+
+    pub fn ecef_from_ned(&self, ned: &NED) -> ECEF {
+        return ECEF::new_from_ned_rot(ned, &self.ned_rotation, &self.reference_ecef);
+    }
+
+    pub fn enu_from_ecef(&self, ecef: &ECEF) -> ENU {
+        return ENU::new_from_ecef_rot(ecef, &self.enu_rotation, &self.reference_ecef);
+    }
+
+    pub fn ecef_from_enu(&self, enu: &ENU) -> ECEF {
+        return ECEF::new_from_enu_rot(enu, &self.enu_rotation, &self.reference_ecef);
+    }
+
+    pub fn ned_from_wgs84(&self, wgs84: &WGS84Coord) -> NED {
+        return self.ned_from_ecef(&ECEF::new_from_wgs84(wgs84));
+    }
+
+    pub fn wgs84_from_ned(&self, ned: &NED) -> WGS84Coord {
+        return WGS84Coord::new_from_ecef_struct(&self.ecef_from_ned(ned));
+    }
+
+    pub fn enu_from_wgs84(&self, wgs84: &WGS84Coord) -> ENU {
+        return self.enu_from_ecef(&ECEF::new_from_wgs84(wgs84));
+    }
+
+    pub fn wgs84_from_enu(&self, enu: &ENU) -> WGS84Coord {
+        return WGS84Coord::new_from_ecef_struct(&self.ecef_from_enu(enu));
+    }
+
+    pub fn aer_from_ecef(&self, ecef: &ECEF) -> AER {
+        return AER::new_from_enu(&self.enu_from_ecef(ecef));
+    }
+
+    pub fn ecef_from_aer(&self, aer: &AER) -> ECEF {
+        return self.ecef_from_enu(&ENU::new_from_aer(aer));
+    }
+
+    pub fn aer_from_wgs84(&self, wgs84: &WGS84Coord) -> AER {
+        return self.aer_from_ecef(&ECEF::new_from_wgs84(wgs84));
+    }
+
+    pub fn wgs84_from_aer(&self, aer: &AER) -> WGS84Coord {
+        return WGS84Coord::new_from_ecef_struct(&self.ecef_from_aer(aer));
+    }
+
+    // Velocities
+
+    pub fn ned_vel_from_ecef_vel(&self, ecef_vel: &ECEFVel) -> NEDVel {
+        return NEDVel::new_from_ecef_rot(ecef_vel, &self.ned_rotation);
+    }
+
+    pub fn ecef_vel_from_ned_vel(&self, ned_vel: &NEDVel) -> ECEFVel {
+        return ECEFVel::new_from_ned_rot(ned_vel, &self.ned_rotation);
+    }
+
+    pub fn enu_vel_from_ecef_vel(&self, ecef_vel: &ECEFVel) -> ENUVel {
+        let v = rotate(
+            &self.enu_rotation,
+            ecef_vel.x_vel,
+            ecef_vel.y_vel,
+            ecef_vel.z_vel,
+        );
+        return ENUVel {
+            e_vel: v.x,
+            n_vel: v.y,
+            u_vel: v.z,
+        };
+    }
+
+    pub fn ecef_vel_from_enu_vel(&self, enu_vel: &ENUVel) -> ECEFVel {
+        let v = unrotate(
+            &self.enu_rotation,
+            enu_vel.e_vel,
+            enu_vel.n_vel,
+            enu_vel.u_vel,
+        );
+        return ECEFVel {
+            x_vel: v.x,
+            y_vel: v.y,
+            z_vel: v.z,
+        };
+    }
+
+    pub fn enu_vel_from_ned_vel(&self, ned_vel: &NEDVel) -> ENUVel {
+        return ENUVel {
+            e_vel: ned_vel.e_vel,
+            n_vel: ned_vel.n_vel,
+            u_vel: -ned_vel.d_vel,
+        };
+    }
+
+    pub fn ned_vel_from_enu_vel(&self, enu_vel: &ENUVel) -> NEDVel {
+        return NEDVel {
+            n_vel: enu_vel.n_vel,
+            e_vel: enu_vel.e_vel,
+            d_vel: -enu_vel.u_vel,
+        };
+    }
+
+    // Accelerations
+
+    pub fn ned_accel_from_ecef_accel(&self, ecef_accel: &ECEFAccel) -> NEDAccel {
+        let a = rotate(
+            &self.ned_rotation,
+            ecef_accel.x_accel,
+            ecef_accel.y_accel,
+            ecef_accel.z_accel,
+        );
+        return NEDAccel {
+            n_accel: a.x,
+            e_accel: a.y,
+            d_accel: a.z,
+        };
+    }
+
+    pub fn ecef_accel_from_ned_accel(&self, ned_accel: &NEDAccel) -> ECEFAccel {
+        let a = unrotate(
+            &self.ned_rotation,
+            ned_accel.n_accel,
+            ned_accel.e_accel,
+            ned_accel.d_accel,
+        );
+        return ECEFAccel {
+            x_accel: a.x,
+            y_accel: a.y,
+            z_accel: a.z,
+        };
+    }
+
+    pub fn enu_accel_from_ecef_accel(&self, ecef_accel: &ECEFAccel) -> ENUAccel {
+        let a = rotate(
+            &self.enu_rotation,
+            ecef_accel.x_accel,
+            ecef_accel.y_accel,
+            ecef_accel.z_accel,
+        );
+        return ENUAccel {
+            e_accel: a.x,
+            n_accel: a.y,
+            u_accel: a.z,
+        };
+    }
+
+    pub fn ecef_accel_from_enu_accel(&self, enu_accel: &ENUAccel) -> ECEFAccel {
+        let a = unrotate(
+            &self.enu_rotation,
+            enu_accel.e_accel,
+            enu_accel.n_accel,
+            enu_accel.u_accel,
+        );
+        return ECEFAccel {
+            x_accel: a.x,
+            y_accel: a.y,
+            z_accel: a.z,
+        };
+    }
+}
+
+fn rotate(rotation: &Mat3, x: f64, y: f64, z: f64) -> Vec3 {
+    return *rotation * Vec3::new(x, y, z);
+}
+
+fn unrotate(rotation: &Mat3, a: f64, b: f64, c: f64) -> Vec3 {
+    return rotation.transpose_times(&Vec3::new(a, b, c));
+}
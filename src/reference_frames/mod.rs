@@ -1,6 +1,12 @@
+pub mod aer;
+pub mod cio;
 pub mod ecef;
 pub mod enu;
+pub mod fk5;
+pub mod gcrf;
+pub mod local_tangent_plane;
 pub mod ned;
 pub mod pef;
 pub mod teme;
+pub mod transform;
 pub mod wgs84;
@@ -1,11 +1,13 @@
-use crate::utils::matrix_times_vec;
+use crate::matrix::{Mat3, Vec3};
 
 use super::{
-    ecef::{ECEFVel, ECEF},
+    aer::AER,
+    ecef::{generate_ecef_to_ned_matrix, ECEFVel, ECEF},
     enu::ENU,
+    wgs84::WGS84Coord,
 };
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct NED {
     // Wrapper for NED coordinates
     pub n: f64,
@@ -14,47 +16,34 @@ pub struct NED {
 }
 
 impl NED {
-    pub fn default() -> NED {
-        return NED {
-            n: 0_f64,
-            e: 0_f64,
-            d: 0_f64,
-        };
-    }
     pub fn new_from_raw(n: f64, e: f64, d: f64) -> NED {
         return NED { n, e, d };
     }
-    pub fn new_from_vec(ned_vec: Vec<f64>) -> Self {
-        return NED::new_from_raw(ned_vec[0], ned_vec[1], ned_vec[2]);
+    pub fn new_from_vec3(ned_vec: &Vec3) -> NED {
+        return NED::new_from_raw(ned_vec.x, ned_vec.y, ned_vec.z);
     }
 
     pub fn new_from_enu(enu: &ENU) -> NED {
         // Given a reference to a ENU returns a NED
         // Z axis is flipped; x and y swap
         return NED {
-            n: enu.e,
-            e: enu.n,
+            n: enu.n,
+            e: enu.e,
             d: -enu.u,
         };
     }
 
-    pub fn new_from_ecef_rot(
-        ecef: &ECEF,
-        rotation_matrix: &Vec<f64>,
-        reference_point: &ECEF,
-    ) -> NED {
+    pub fn new_from_aer(aer: &AER) -> NED {
+        return NED::new_from_enu(&ENU::new_from_aer(aer));
+    }
+
+    pub fn new_from_ecef_rot(ecef: &ECEF, rotation_matrix: &Mat3, reference_point: &ECEF) -> NED {
         let x = ecef.x - reference_point.x;
         let y = ecef.y - reference_point.y;
         let z = ecef.z - reference_point.z;
-        let ecef_vec = vec![x, y, z];
+        let ecef_vec = Vec3::new(x, y, z);
 
-        let ned_vec = matrix_times_vec(rotation_matrix, &ecef_vec);
-
-        return NED {
-            n: ned_vec[0],
-            e: ned_vec[1],
-            d: ned_vec[2],
-        };
+        return NED::new_from_vec3(&(*rotation_matrix * ecef_vec));
     }
     pub fn distance_between(&self, other: &NED) -> f64 {
         return ((self.n - other.n).powf(2_f64)
@@ -67,8 +56,8 @@ impl NED {
         return (other.n - self.n).atan2(other.e - self.e);
     }
 
-    pub fn to_vec(&self) -> Vec<f64> {
-        return vec![self.n, self.e, self.d];
+    pub fn to_vec3(&self) -> Vec3 {
+        return Vec3::new(self.n, self.e, self.d);
     }
 
     pub fn distance_between_points(&self, other_point: &NED) -> f64 {
@@ -78,7 +67,15 @@ impl NED {
         .sqrt();
     }
 
-    // pub fn new_from_ecef_ref(ecef: &ECEF, ref_point: &WGS84Coord) -> NED {} // TODO
+    pub fn new_from_ecef(ecef: &ECEF, reference_point: &WGS84Coord) -> NED {
+        // Given an ECEF coord and the WGS84 origin of the local frame returns the NED offset from the origin
+        let rotation = generate_ecef_to_ned_matrix(reference_point);
+        return NED::new_from_ecef_rot(ecef, &rotation, &ECEF::new_from_wgs84(reference_point));
+    }
+
+    pub fn new_from_wgs84(wgs84: &WGS84Coord, reference_point: &WGS84Coord) -> NED {
+        return NED::new_from_ecef(&ECEF::new_from_wgs84(wgs84), reference_point);
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -98,15 +95,15 @@ impl NEDVel {
         };
     }
 
-    pub fn new_from_ecef_rot(ecef: &ECEFVel, rotation_matrix: &Vec<f64>) -> NEDVel {
-        let ecef_vec = vec![ecef.x_vel, ecef.y_vel, ecef.z_vel];
+    pub fn new_from_ecef_rot(ecef: &ECEFVel, rotation_matrix: &Mat3) -> NEDVel {
+        let ecef_vec = Vec3::new(ecef.x_vel, ecef.y_vel, ecef.z_vel);
 
-        let ned_vec = matrix_times_vec(rotation_matrix, &ecef_vec);
+        let ned_vec = *rotation_matrix * ecef_vec;
 
         return NEDVel {
-            n_vel: ned_vec[0],
-            e_vel: ned_vec[1],
-            d_vel: ned_vec[2],
+            n_vel: ned_vec.x,
+            e_vel: ned_vec.y,
+            d_vel: ned_vec.z,
         };
     }
 }
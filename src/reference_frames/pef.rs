@@ -1,12 +1,18 @@
-use chrono::NaiveDateTime;
-
-use crate::utils::{
-    get_pef_tod_matrix, get_polar_motion_matrix, invert_matrix, julian_to_gmst, matrix_times_vec,
-    old_maybe_broken_jday, transpose, transpose_times_vec,
+use crate::{
+    constants::EARTH_ROTATION_RATE,
+    matrix::Vec3,
+    time::{
+        eop::get_eop,
+        epoch::{Epoch, TimeScale},
+        julian_date::JulianDate,
+        sidereal::gmst_iau82,
+    },
+    utils::{get_pef_tod_matrix, get_polar_motion_matrix},
 };
 
 use super::{
     ecef::{ECEFVel, ECEF},
+    fk5::{FK5, TOD},
     teme::{TEMEVel, TEME},
 };
 
@@ -19,36 +25,40 @@ pub struct PEF {
 
 impl PEF {
     // fn new_from_ecef(ecef: &ECEF) -> PEF {}
-    pub fn new_from_teme(teme: &TEME, utc_time: &NaiveDateTime) -> PEF {
-        let teme_matrix = vec![teme.x, teme.y, teme.z];
-        let (jday, jfrac) = old_maybe_broken_jday(utc_time);
-        let julian = jday + jfrac;
-        let gmst = julian_to_gmst(julian);
+    pub fn new_from_teme(teme: &TEME, time: impl Into<Epoch>) -> PEF {
+        // The time can be an Epoch or a NaiveDateTime, which is taken as UTC
+        let epoch: Epoch = time.into();
+        let teme_vec = Vec3::new(teme.x, teme.y, teme.z);
+        // The Earth's rotation follows UT1. TEME is turned from PEF by GMST alone (Vallado, Revisiting
+        // Spacetrack Report #3)
+        let gmst = gmst_iau82(&JulianDate::new_from_epoch(&epoch, TimeScale::UT1));
         let pef_tod_matrix = get_pef_tod_matrix(gmst);
 
-        let pef = transpose_times_vec(&pef_tod_matrix, &teme_matrix);
+        let pef = pef_tod_matrix.transpose_times(&teme_vec);
         return PEF {
-            x: (pef[0]),
-            y: (pef[1]),
-            z: (pef[2]),
+            x: pef.x,
+            y: pef.y,
+            z: pef.z,
         };
     }
 
-    pub fn new_from_ecef(ecef: &ECEF, utc_time: &NaiveDateTime) -> PEF {
-        let ecef_matrix = vec![ecef.x, ecef.y, ecef.z];
-        let (jday, jfrac) = old_maybe_broken_jday(utc_time);
-        let julian = jday + jfrac;
-        let polar_motion_matrix = get_polar_motion_matrix(julian);
+    pub fn new_from_tod(tod: &TOD, time: impl Into<Epoch>) -> PEF {
+        return FK5::new(time).pef_from_tod(tod);
+    }
 
-        let trans = transpose(&polar_motion_matrix);
-        let inv = invert_matrix(&trans);
+    pub fn new_from_ecef(ecef: &ECEF, time: impl Into<Epoch>) -> PEF {
+        let epoch: Epoch = time.into();
+        let ecef_vec = Vec3::new(ecef.x, ecef.y, ecef.z);
+        let eop = get_eop(&epoch);
+        let polar_motion_matrix = get_polar_motion_matrix(eop.xp, eop.yp);
 
-        let pef = matrix_times_vec(&inv, &ecef_matrix);
+        // The inverse of the transpose of a rotation is the rotation itself
+        let pef = polar_motion_matrix * ecef_vec;
 
         return PEF {
-            x: (pef[0]),
-            y: (pef[1]),
-            z: (pef[2]),
+            x: pef.x,
+            y: pef.y,
+            z: pef.z,
         };
     }
 }
@@ -62,41 +72,39 @@ pub struct PEFVel {
 }
 
 impl PEFVel {
-    pub fn new_from_teme_vel(teme: &TEME, teme_vel: &TEMEVel, utc_time: &NaiveDateTime) -> PEFVel {
-        // Given a reference to a TEME coordinate, a reference to a TEME velocity and a reference to a NaiveDateTime produces a PEF velocity
-        // Be careful of the NaiveDateTime time zones - I am assuming this is using UNIX seconds
-        // In general, you are best of making all NaiveDateTimes from UNIX timestamps
-        let pef = PEF::new_from_teme(teme, utc_time);
-        let teme_vel_matrix = vec![teme_vel.x_vel, teme_vel.y_vel, teme_vel.z_vel];
-
-        let (jday, frac) = old_maybe_broken_jday(utc_time);
-        let gmst = julian_to_gmst(jday + frac);
+    pub fn new_from_teme_vel(teme: &TEME, teme_vel: &TEMEVel, time: impl Into<Epoch>) -> PEFVel {
+        // Given a reference to a TEME coordinate, a reference to a TEME velocity and a time produces a PEF velocity
+        // A NaiveDateTime is taken as UTC, use an Epoch for other time scales or to give UT1 - UTC
+        let epoch: Epoch = time.into();
+        let pef = PEF::new_from_teme(teme, epoch);
+        let teme_vel_vec = Vec3::new(teme_vel.x_vel, teme_vel.y_vel, teme_vel.z_vel);
+
+        let gmst = gmst_iau82(&JulianDate::new_from_epoch(&epoch, TimeScale::UT1));
         let pef_tod_matrix = get_pef_tod_matrix(gmst);
-        let omega_earth = 7.29211514670698e-05_f64 * (1.0_f64 - 0.002_f64 / 86400.0_f64);
+        let omega_earth = EARTH_ROTATION_RATE * (1. - get_eop(&epoch).lod / 86400.);
 
-        let velocity_pef_temp = transpose_times_vec(&pef_tod_matrix, &teme_vel_matrix);
+        let velocity_pef_temp = pef_tod_matrix.transpose_times(&teme_vel_vec);
 
+        // v_pef = R3(gmst) v_teme - w x r_pef
         return PEFVel {
-            x_vel: velocity_pef_temp[0] - omega_earth * pef.y,
-            y_vel: velocity_pef_temp[1] - omega_earth * pef.x,
-            z_vel: velocity_pef_temp[2],
+            x_vel: velocity_pef_temp.x + omega_earth * pef.y,
+            y_vel: velocity_pef_temp.y - omega_earth * pef.x,
+            z_vel: velocity_pef_temp.z,
         };
     }
 
-    pub fn new_from_ecef_vel(ecef_vel: &ECEFVel, utc_time: &NaiveDateTime) -> PEFVel {
-        let (jday, frac) = old_maybe_broken_jday(utc_time);
-        let polar_motion_matrix = get_polar_motion_matrix(jday + frac);
-        let velocity_ecef_matrix = vec![ecef_vel.x_vel, ecef_vel.y_vel, ecef_vel.z_vel];
-
-        let trans = transpose(&polar_motion_matrix);
-        let inv = invert_matrix(&trans);
+    pub fn new_from_ecef_vel(ecef_vel: &ECEFVel, time: impl Into<Epoch>) -> PEFVel {
+        let epoch: Epoch = time.into();
+        let eop = get_eop(&epoch);
+        let polar_motion_matrix = get_polar_motion_matrix(eop.xp, eop.yp);
+        let velocity_ecef_vec = Vec3::new(ecef_vel.x_vel, ecef_vel.y_vel, ecef_vel.z_vel);
 
-        let velocity_pef_matrix = matrix_times_vec(&inv, &velocity_ecef_matrix);
+        let velocity_pef = polar_motion_matrix * velocity_ecef_vec;
 
         return PEFVel {
-            x_vel: velocity_pef_matrix[0],
-            y_vel: velocity_pef_matrix[1],
-            z_vel: velocity_pef_matrix[2],
+            x_vel: velocity_pef.x,
+            y_vel: velocity_pef.y,
+            z_vel: velocity_pef.z,
         };
     }
 }
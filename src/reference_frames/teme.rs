@@ -1,8 +1,11 @@
-use chrono::NaiveDateTime;
-
-use crate::utils::{
-    get_pef_tod_matrix, invert_matrix, julian_to_gmst, matrix_times_vec, old_maybe_broken_jday,
-    transpose,
+use crate::{
+    constants::EARTH_ROTATION_RATE,
+    matrix::Vec3,
+    time::eop::get_eop,
+    time::epoch::{Epoch, TimeScale},
+    time::julian_date::JulianDate,
+    time::sidereal::gmst_iau82,
+    utils::get_pef_tod_matrix,
 };
 
 use super::pef::{PEFVel, PEF};
@@ -17,24 +20,21 @@ pub struct TEME {
 }
 
 impl TEME {
-    pub fn new_from_pef(pef: &PEF, utc_time: &NaiveDateTime) -> TEME {
-        // Given a reference to a PEF and a reference to a NaiveDateTime produces a TEME location
-        // Be careful of the NaiveDateTime time zones - I am assuming this is using UNIX seconds
-        // In general, you are best of making all NaiveDateTimes from UNIX timestamps
-        let pef_matrix = vec![pef.x, pef.y, pef.z];
-        let (jday, jfrac) = old_maybe_broken_jday(utc_time);
-        let julian = jday + jfrac;
-        let gmst = julian_to_gmst(julian);
+    pub fn new_from_pef(pef: &PEF, time: impl Into<Epoch>) -> TEME {
+        // Given a reference to a PEF and a time produces a TEME location
+        // A NaiveDateTime is taken as UTC, use an Epoch for other time scales or to give UT1 - UTC
+        let epoch: Epoch = time.into();
+        let pef_vec = Vec3::new(pef.x, pef.y, pef.z);
+        let gmst = gmst_iau82(&JulianDate::new_from_epoch(&epoch, TimeScale::UT1));
         let pef_tod_matrix = get_pef_tod_matrix(gmst);
 
-        let trans = transpose(&pef_tod_matrix);
-        let inv = invert_matrix(&trans);
-        let teme = matrix_times_vec(&inv, &pef_matrix);
+        // The inverse of the transpose of a rotation is the rotation itself
+        let teme = pef_tod_matrix * pef_vec;
 
         return TEME {
-            x: (teme[0]),
-            y: (teme[1]),
-            z: (teme[2]),
+            x: teme.x,
+            y: teme.y,
+            z: teme.z,
         };
     }
 }
@@ -47,21 +47,25 @@ pub struct TEMEVel {
     pub z_vel: f64,
 }
 impl TEMEVel {
-    pub fn new_from_pef_vel(pef: &PEF, pef_vel: &PEFVel, utc_time: &NaiveDateTime) -> TEMEVel {
-        let pef_vel_matrix = vec![pef_vel.x_vel, pef_vel.y_vel, pef_vel.z_vel];
-        let (jday, frac) = old_maybe_broken_jday(utc_time);
-        let gmst = julian_to_gmst(jday + frac);
+    pub fn new_from_pef_vel(pef: &PEF, pef_vel: &PEFVel, time: impl Into<Epoch>) -> TEMEVel {
+        let epoch: Epoch = time.into();
+        let pef_vel_vec = Vec3::new(pef_vel.x_vel, pef_vel.y_vel, pef_vel.z_vel);
+        let gmst = gmst_iau82(&JulianDate::new_from_epoch(&epoch, TimeScale::UT1));
         let pef_tod_matrix = get_pef_tod_matrix(gmst);
-        let omega_earth = 7.29211514670698e-05_f64 * (1.0_f64 - 0.002_f64 / 86400.0_f64);
+        let omega_earth = EARTH_ROTATION_RATE * (1. - get_eop(&epoch).lod / 86400.);
 
-        let trans = transpose(&pef_tod_matrix);
-        let inv = invert_matrix(&trans);
-        let velocity_teme_temp = matrix_times_vec(&inv, &pef_vel_matrix);
+        // v_teme = R3(-gmst) (v_pef + w x r_pef)
+        let inertial_pef_vel = Vec3::new(
+            pef_vel_vec.x - omega_earth * pef.y,
+            pef_vel_vec.y + omega_earth * pef.x,
+            pef_vel_vec.z,
+        );
+        let velocity_teme = pef_tod_matrix * inertial_pef_vel;
 
         return TEMEVel {
-            x_vel: velocity_teme_temp[0] + omega_earth * pef.y,
-            y_vel: velocity_teme_temp[1] + omega_earth * pef.x,
-            z_vel: velocity_teme_temp[2],
+            x_vel: velocity_teme.x,
+            y_vel: velocity_teme.y,
+            z_vel: velocity_teme.z,
         };
     }
 }
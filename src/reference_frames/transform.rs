@@ -0,0 +1,432 @@
+use std::{collections::VecDeque, fmt};
+
+use crate::{
+    matrix::{Mat3, Vec3},
+    time::{
+        eop::get_eop,
+        epoch::{Epoch, TimeScale},
+        julian_date::JulianDate,
+        sidereal::{earth_rotation_angle, gast_iau82, gmst_iau82},
+    },
+    utils::{get_pef_tod_matrix, get_polar_motion_matrix},
+};
+
+use super::{
+    cio::{
+        cio_locator, get_celestial_to_intermediate_matrix, get_cip_xy,
+        get_polar_motion_matrix_iau2000, tio_locator, CIRS, TIRS,
+    },
+    ecef::{generate_ecef_to_enu_matrix, generate_ecef_to_ned_matrix, ECEF},
+    enu::ENU,
+    fk5::{get_precession_matrix, Nutation, MOD, TOD},
+    gcrf::GCRF,
+    ned::NED,
+    pef::PEF,
+    teme::TEME,
+    wgs84::WGS84Coord,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameId {
+    GCRF,
+    CIRS,
+    TIRS,
+    MOD,
+    TOD,
+    TEME,
+    PEF,
+    ECEF,
+    NED,
+    ENU,
+}
+
+const FRAMES: [FrameId; 10] = [
+    FrameId::GCRF,
+    FrameId::CIRS,
+    FrameId::TIRS,
+    FrameId::MOD,
+    FrameId::TOD,
+    FrameId::TEME,
+    FrameId::PEF,
+    FrameId::ECEF,
+    FrameId::NED,
+    FrameId::ENU,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InertialReduction {
+    // Which chain of frames FrameGraph uses between GCRF and the Earth fixed frames. The other
+    // chain's frames stay reachable from the Earth fixed side
+    //
+    // IAU 2006/2000 through CIRS and TIRS, the default and what GCRF::new_from_ecef uses
+    #[default]
+    IAU2006,
+    // IAU-76/FK5 through MOD, TOD and PEF
+    FK5,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FrameError {
+    // The conversion passes through a frame that rotates with the Earth and needs a time
+    MissingTime(FrameId, FrameId),
+    // The conversion passes through a local frame and needs its origin
+    MissingReferencePoint(FrameId, FrameId),
+}
+
+impl fmt::Display for FrameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FrameError::MissingTime(from, to) => {
+                write!(f, "{:?} to {:?} needs a time", from, to)
+            }
+            FrameError::MissingReferencePoint(from, to) => {
+                write!(f, "{:?} to {:?} needs a reference point", from, to)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FrameError {}
+
+pub trait Frame: Sized {
+    // A position type that can take part in automatic conversions, as its components in its own axes
+    const ID: FrameId;
+
+    fn to_vec3(&self) -> Vec3;
+
+    fn new_from_vec3(v: &Vec3) -> Self;
+}
+
+macro_rules! impl_frame_xyz {
+    // Frames whose positions are plain x, y and z fields, named the same as their FrameId
+    ($($frame:ident),*) => {
+        $(
+            impl Frame for $frame {
+                const ID: FrameId = FrameId::$frame;
+
+                fn to_vec3(&self) -> Vec3 {
+                    return Vec3::new(self.x, self.y, self.z);
+                }
+
+                fn new_from_vec3(v: &Vec3) -> $frame {
+                    return $frame {
+                        x: v.x,
+                        y: v.y,
+                        z: v.z,
+                    };
+                }
+            }
+        )*
+    };
+}
+
+impl_frame_xyz!(GCRF, CIRS, TIRS, MOD, TOD, TEME, PEF, ECEF);
+
+impl Frame for NED {
+    const ID: FrameId = FrameId::NED;
+
+    fn to_vec3(&self) -> Vec3 {
+        return NED::to_vec3(self);
+    }
+
+    fn new_from_vec3(v: &Vec3) -> NED {
+        return NED::new_from_vec3(v);
+    }
+}
+
+impl Frame for ENU {
+    const ID: FrameId = FrameId::ENU;
+
+    fn to_vec3(&self) -> Vec3 {
+        return ENU::to_vec3(self);
+    }
+
+    fn new_from_vec3(v: &Vec3) -> ENU {
+        return ENU::new_from_vec3(v);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    // Takes positions in one frame to another as rotation * v + translation
+    from: FrameId,
+    to: FrameId,
+    rotation: Mat3,
+    translation: Vec3,
+}
+
+impl Transform {
+    pub fn identity(frame: FrameId) -> Transform {
+        return Transform {
+            from: frame,
+            to: frame,
+            rotation: Mat3::identity(),
+            translation: Vec3::default(),
+        };
+    }
+
+    pub fn get_from(&self) -> FrameId {
+        return self.from;
+    }
+
+    pub fn get_to(&self) -> FrameId {
+        return self.to;
+    }
+
+    pub fn get_rotation(&self) -> &Mat3 {
+        return &self.rotation;
+    }
+
+    pub fn get_translation(&self) -> &Vec3 {
+        return &self.translation;
+    }
+
+    pub fn then(&self, next: &Transform) -> Transform {
+        // This transform followed by next, which must start in the frame this one ends in
+        assert_eq!(self.to, next.from, "transforms don't join up");
+        return Transform {
+            from: self.from,
+            to: next.to,
+            rotation: next.rotation * self.rotation,
+            translation: next.rotation * self.translation + next.translation,
+        };
+    }
+
+    pub fn inverse(&self) -> Transform {
+        return Transform {
+            from: self.to,
+            to: self.from,
+            rotation: self.rotation.orthonormal_inverse(),
+            translation: -self.rotation.transpose_times(&self.translation),
+        };
+    }
+
+    pub fn apply(&self, v: &Vec3) -> Vec3 {
+        return self.rotation * *v + self.translation;
+    }
+
+    pub fn apply_to_direction(&self, v: &Vec3) -> Vec3 {
+        // Velocities and other free vectors only rotate. Frames that turn relative to each other
+        // (TEME and PEF) also differ by the Earth's rotation, which this leaves out
+        return self.rotation * *v;
+    }
+
+    pub fn convert<A: Frame, B: Frame>(&self, a: &A) -> B {
+        assert!(
+            A::ID == self.from && B::ID == self.to,
+            "transform is {:?} to {:?}, not {:?} to {:?}",
+            self.from,
+            self.to,
+            A::ID,
+            B::ID
+        );
+        return B::new_from_vec3(&self.apply(&a.to_vec3()));
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FrameGraph {
+    // Everything needed to link the frames together. Only the conversions that need the time or
+    // the local reference point require them, e.g. ECEF to NED works without a time
+    time: Option<Epoch>,
+    reference_point: Option<WGS84Coord>,
+    reduction: InertialReduction,
+}
+
+impl FrameGraph {
+    pub fn new(time: Option<&Epoch>, reference_point: Option<&WGS84Coord>) -> FrameGraph {
+        return FrameGraph {
+            time: time.copied(),
+            reference_point: reference_point.cloned(),
+            reduction: InertialReduction::default(),
+        };
+    }
+
+    pub fn new_from_time(time: impl Into<Epoch>) -> FrameGraph {
+        // A NaiveDateTime is taken as UTC
+        return FrameGraph::new(Some(&time.into()), None);
+    }
+
+    pub fn new_from_reference_point(reference_point: &WGS84Coord) -> FrameGraph {
+        return FrameGraph::new(None, Some(reference_point));
+    }
+
+    pub fn get_reduction(&self) -> InertialReduction {
+        return self.reduction;
+    }
+
+    pub fn set_reduction(&mut self, reduction: InertialReduction) {
+        self.reduction = reduction;
+    }
+
+    pub fn transform(&self, from: FrameId, to: FrameId) -> Result<Transform, FrameError> {
+        // Breadth first search for the shortest chain of frames, composed into a single transform
+        let path = shortest_path(from, to, self.reduction);
+
+        let mut transform = Transform::identity(from);
+        for pair in path.windows(2) {
+            transform = transform.then(&self.edge(pair[0], pair[1], from, to)?);
+        }
+
+        return Ok(transform);
+    }
+
+    pub fn convert<A: Frame, B: Frame>(&self, a: &A) -> Result<B, FrameError> {
+        return Ok(self.transform(A::ID, B::ID)?.convert(a));
+    }
+
+    fn edge(
+        &self,
+        a: FrameId,
+        b: FrameId,
+        from: FrameId,
+        to: FrameId,
+    ) -> Result<Transform, FrameError> {
+        // A single step between neighbouring frames, one way round or the other
+        let (forward, reversed) = match (a, b) {
+            (FrameId::GCRF, FrameId::MOD) | (FrameId::MOD, FrameId::TOD) => ((a, b), false),
+            (FrameId::TOD, FrameId::PEF) => ((a, b), false),
+            (FrameId::GCRF, FrameId::CIRS) | (FrameId::CIRS, FrameId::TIRS) => ((a, b), false),
+            (FrameId::TIRS, FrameId::ECEF) => ((a, b), false),
+            (FrameId::TEME, FrameId::PEF) | (FrameId::PEF, FrameId::ECEF) => ((a, b), false),
+            (FrameId::ECEF, FrameId::NED) | (FrameId::ECEF, FrameId::ENU) => ((a, b), false),
+            _ => ((b, a), true),
+        };
+
+        let time = || {
+            return self.time.ok_or(FrameError::MissingTime(from, to));
+        };
+        let reference_point = || {
+            return self
+                .reference_point
+                .as_ref()
+                .ok_or(FrameError::MissingReferencePoint(from, to));
+        };
+
+        let (rotation, translation) = match forward {
+            (FrameId::GCRF, FrameId::MOD) => (get_precession_matrix(time()?), Vec3::default()),
+            (FrameId::MOD, FrameId::TOD) => {
+                let nutation = Nutation::new_from_epoch(time()?);
+                (nutation.get_matrix(), Vec3::default())
+            }
+            (FrameId::TOD, FrameId::PEF) => {
+                let epoch = time()?;
+                let ut1 = JulianDate::new_from_epoch(&epoch, TimeScale::UT1);
+                let gast = gast_iau82(&ut1, &Nutation::new_from_epoch(epoch));
+                (Mat3::rotation_z(gast), Vec3::default())
+            }
+            (FrameId::GCRF, FrameId::CIRS) => {
+                let epoch = time()?;
+                let eop = get_eop(&epoch);
+                let tt = JulianDate::new_from_epoch(&epoch, TimeScale::TT);
+                let (x, y) = get_cip_xy(&tt);
+                let (x, y) = (x + eop.dx, y + eop.dy);
+                let s = cio_locator(&tt, x, y);
+                (
+                    get_celestial_to_intermediate_matrix(x, y, s),
+                    Vec3::default(),
+                )
+            }
+            (FrameId::CIRS, FrameId::TIRS) => {
+                let ut1 = JulianDate::new_from_epoch(&time()?, TimeScale::UT1);
+                (
+                    Mat3::rotation_z(earth_rotation_angle(&ut1)),
+                    Vec3::default(),
+                )
+            }
+            (FrameId::TIRS, FrameId::ECEF) => {
+                let epoch = time()?;
+                let eop = get_eop(&epoch);
+                let sp = tio_locator(&JulianDate::new_from_epoch(&epoch, TimeScale::TT));
+                (
+                    get_polar_motion_matrix_iau2000(eop.xp, eop.yp, sp),
+                    Vec3::default(),
+                )
+            }
+            (FrameId::TEME, FrameId::PEF) => {
+                let ut1 = JulianDate::new_from_epoch(&time()?, TimeScale::UT1);
+                (
+                    get_pef_tod_matrix(gmst_iau82(&ut1)).transpose(),
+                    Vec3::default(),
+                )
+            }
+            (FrameId::PEF, FrameId::ECEF) => {
+                let eop = get_eop(&time()?);
+                (
+                    get_polar_motion_matrix(eop.xp, eop.yp).transpose(),
+                    Vec3::default(),
+                )
+            }
+            (FrameId::ECEF, local) => {
+                let reference_point = reference_point()?;
+                let rotation = if local == FrameId::NED {
+                    generate_ecef_to_ned_matrix(reference_point)
+                } else {
+                    generate_ecef_to_enu_matrix(reference_point)
+                };
+                let origin = ECEF::new_from_wgs84(reference_point).to_vec3();
+                (rotation, -(rotation * origin))
+            }
+            _ => unreachable!("{:?} and {:?} aren't neighbours", a, b),
+        };
+
+        let transform = Transform {
+            from: forward.0,
+            to: forward.1,
+            rotation,
+            translation,
+        };
+        if reversed {
+            return Ok(transform.inverse());
+        }
+        return Ok(transform);
+    }
+}
+
+fn neighbours(frame: FrameId, reduction: InertialReduction) -> &'static [FrameId] {
+    // Only the chosen reduction joins GCRF, which leaves a tree with one path between any two
+    // frames. Otherwise the reduction used would depend on which end the search started from
+    let iau2006 = reduction == InertialReduction::IAU2006;
+    return match frame {
+        FrameId::GCRF if iau2006 => &[FrameId::CIRS],
+        FrameId::GCRF => &[FrameId::MOD],
+        FrameId::CIRS if iau2006 => &[FrameId::GCRF, FrameId::TIRS],
+        FrameId::CIRS => &[FrameId::TIRS],
+        FrameId::TIRS => &[FrameId::CIRS, FrameId::ECEF],
+        FrameId::MOD if iau2006 => &[FrameId::TOD],
+        FrameId::MOD => &[FrameId::GCRF, FrameId::TOD],
+        FrameId::TOD => &[FrameId::MOD, FrameId::PEF],
+        FrameId::TEME => &[FrameId::PEF],
+        FrameId::PEF => &[FrameId::TOD, FrameId::TEME, FrameId::ECEF],
+        FrameId::ECEF => &[FrameId::TIRS, FrameId::PEF, FrameId::NED, FrameId::ENU],
+        FrameId::NED => &[FrameId::ECEF],
+        FrameId::ENU => &[FrameId::ECEF],
+    };
+}
+
+fn shortest_path(from: FrameId, to: FrameId, reduction: InertialReduction) -> Vec<FrameId> {
+    let index = |frame: FrameId| FRAMES.iter().position(|f| *f == frame).unwrap();
+    let mut previous: [Option<FrameId>; FRAMES.len()] = [None; FRAMES.len()];
+    let mut queue = VecDeque::from([from]);
+    previous[index(from)] = Some(from);
+
+    while let Some(frame) = queue.pop_front() {
+        if frame == to {
+            break;
+        }
+        for next in neighbours(frame, reduction) {
+            if previous[index(*next)].is_none() {
+                previous[index(*next)] = Some(frame);
+                queue.push_back(*next);
+            }
+        }
+    }
+
+    // Every frame is connected, so walking back from the destination always reaches the start
+    let mut path = vec![to];
+    while *path.last().unwrap() != from {
+        path.push(previous[index(*path.last().unwrap())].unwrap());
+    }
+    path.reverse();
+    return path;
+}
@@ -1,10 +1,12 @@
 use crate::{
-    constants::{DEG_TO_RAD, EARTH_ECCENTRICITY_SQUARED, EARTH_MAJOR},
+    constants::DEG_TO_RAD,
+    ellipsoid::Ellipsoid,
+    matrix::Mat3,
+    projections::utm::{reverse, UTMCoord},
     types::{Degrees, Metres, Radians},
-    utils::transpose_times_vec,
 };
 
-use super::{ecef::ECEF, ned::NED};
+use super::{aer::AER, ecef::ECEF, enu::ENU, ned::NED};
 
 #[derive(Debug, Clone)]
 pub struct WGS84Coord {
@@ -37,18 +39,21 @@ impl WGS84Coord {
     }
     pub fn new_from_ecef(x: f64, y: f64, z: f64) -> WGS84Coord {
         // Given an x, y, z in the ECEF frame, produces a WGS84Coord
+        return WGS84Coord::new_from_ecef_on_ellipsoid(x, y, z, &Ellipsoid::WGS84);
+    }
+
+    pub fn new_from_ecef_on_ellipsoid(x: f64, y: f64, z: f64, ellipsoid: &Ellipsoid) -> WGS84Coord {
+        // Given an x, y, z in the ECEF frame, produces a latitude, longitude and height relative to the given ellipsoid
+        // The returned coordinate is only WGS84 if the ellipsoid is
+        let eccentricity_squared = ellipsoid.get_eccentricity_squared();
         let p = (x.powf(2_f64) + y.powf(2_f64)).sqrt();
         let lambda_lon = f64::atan2(y, x);
-        let mut phi_lat = f64::atan2(z, p * (1_f64 - EARTH_ECCENTRICITY_SQUARED));
+        let mut phi_lat = f64::atan2(z, p * (1_f64 - eccentricity_squared));
         let mut precision = 1_f64;
         let mut nutation: f64 = 0_f64;
         while precision > 1e-12 {
-            nutation = EARTH_MAJOR
-                / (1_f64 - (EARTH_ECCENTRICITY_SQUARED * phi_lat.sin().powf(2_f64))).sqrt();
-            let new_phi_lat = f64::atan2(
-                z + (EARTH_ECCENTRICITY_SQUARED * nutation * phi_lat.sin()),
-                p,
-            );
+            nutation = ellipsoid.prime_vertical_radius(phi_lat);
+            let new_phi_lat = f64::atan2(z + (eccentricity_squared * nutation * phi_lat.sin()), p);
             precision = (phi_lat - new_phi_lat).abs();
             phi_lat = new_phi_lat;
         }
@@ -56,22 +61,24 @@ impl WGS84Coord {
         return WGS84Coord::new_from_radians(phi_lat, lambda_lon, h);
     }
 
-    pub fn new_from_ned(
-        ned: &NED,
-        rotation_matrix: &Vec<f64>,
-        reference_point: &ECEF,
-    ) -> WGS84Coord {
-        let ned_matrix = vec![ned.n, ned.e, ned.d];
+    pub fn new_from_ned(ned: &NED, rotation_matrix: &Mat3, reference_point: &ECEF) -> WGS84Coord {
+        let ecef = ECEF::new_from_ned_rot(ned, rotation_matrix, reference_point);
 
-        let temp_matrix = transpose_times_vec(rotation_matrix, &ned_matrix);
+        return WGS84Coord::new_from_ecef_struct(&ecef);
+    }
 
-        let ecef = ECEF {
-            x: temp_matrix[0] + reference_point.x,
-            y: temp_matrix[1] + reference_point.y,
-            z: temp_matrix[2] + reference_point.z,
-        };
+    pub fn new_from_enu(enu: &ENU, reference_point: &WGS84Coord) -> WGS84Coord {
+        // Given an ENU offset and the WGS84 origin of the local frame produces a WGS84Coord
+        return WGS84Coord::new_from_ecef_struct(&ECEF::new_from_enu(enu, reference_point));
+    }
 
-        return WGS84Coord::new_from_ecef_struct(&ecef);
+    pub fn new_from_aer(aer: &AER, observer: &WGS84Coord) -> WGS84Coord {
+        return WGS84Coord::new_from_enu(&ENU::new_from_aer(aer), observer);
+    }
+
+    pub fn new_from_utm(utm: &UTMCoord) -> WGS84Coord {
+        // UTM and UPS carry no height so the altitude is zero
+        return reverse(utm).coord;
     }
 
     pub fn get_lat_radians(&self) -> f64 {
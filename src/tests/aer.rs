@@ -0,0 +1,158 @@
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use approx::assert_relative_eq;
+
+use crate::reference_frames::aer::{wrap_azimuth, AERRate, AzimuthWrap, ElevationMask, AER};
+use crate::reference_frames::ecef::{ECEFVel, ECEF};
+use crate::reference_frames::enu::ENU;
+use crate::reference_frames::ned::NED;
+use crate::reference_frames::wgs84::WGS84Coord;
+
+#[test]
+fn test_aer_from_enu() {
+    let aer = AER::new_from_enu(&ENU::new_from_raw(-100., -100., 100. * 2_f64.sqrt()));
+
+    assert_relative_eq!(aer.azimuth, 1.25 * PI, epsilon = 1e-15);
+    assert_relative_eq!(aer.elevation, PI / 4., epsilon = 1e-15);
+    assert_relative_eq!(aer.range, 200., epsilon = 1e-12);
+    assert_relative_eq!(
+        aer.get_azimuth(AzimuthWrap::MinusPiToPi),
+        -0.75 * PI,
+        epsilon = 1e-15
+    );
+
+    let ned = NED::new_from_aer(&aer);
+    assert_relative_eq!(ned.n, -100., epsilon = 1e-12);
+    assert_relative_eq!(ned.e, -100., epsilon = 1e-12);
+    assert_relative_eq!(ned.d, -100. * 2_f64.sqrt(), epsilon = 1e-12);
+
+    let back = AER::new_from_ned(&ned);
+    assert_relative_eq!(back.azimuth, aer.azimuth, epsilon = 1e-15);
+    assert_relative_eq!(back.elevation, aer.elevation, epsilon = 1e-15);
+    assert_relative_eq!(back.range, aer.range, epsilon = 1e-12);
+}
+
+#[test]
+fn test_aer_wgs84_and_ecef() {
+    // A satellite 500 km straight above a station on the equator
+    let observer = WGS84Coord::new_from_degrees(0., 0., 0.);
+    let target = ECEF {
+        x: 6378137. + 500e3,
+        y: 0.,
+        z: 0.,
+    };
+    let aer = AER::new_from_ecef(&target, &observer);
+
+    assert_relative_eq!(aer.elevation, FRAC_PI_2, epsilon = 1e-15);
+    assert_relative_eq!(aer.range, 500e3, epsilon = 1e-8);
+
+    // Round trip through look angles from a station in the southern hemisphere
+    let observer = WGS84Coord::new_from_degrees(-35.4, 148.98, 690.);
+    let target = WGS84Coord::new_from_degrees(-20., 160., 35786e3);
+    let aer = AER::new_from_wgs84(&target, &observer);
+
+    assert!(aer.azimuth > 0. && aer.azimuth < FRAC_PI_2);
+    assert!(aer.is_above(0.));
+
+    let back = WGS84Coord::new_from_aer(&aer, &observer);
+    assert_relative_eq!(back.get_lat_degrees(), -20., epsilon = 1e-9);
+    assert_relative_eq!(back.get_lon_degrees(), 160., epsilon = 1e-9);
+    assert_relative_eq!(back.get_altitude(), 35786e3, epsilon = 1e-5);
+
+    let ecef = ECEF::new_from_aer(&aer, &observer);
+    let expected = ECEF::new_from_wgs84(&target);
+    assert_relative_eq!(ecef.x, expected.x, epsilon = 1e-5);
+    assert_relative_eq!(ecef.y, expected.y, epsilon = 1e-5);
+    assert_relative_eq!(ecef.z, expected.z, epsilon = 1e-5);
+
+    // Something on the ground a long way off is below the horizon
+    let far = WGS84Coord::new_from_degrees(-30., 150., 0.);
+    assert!(!AER::new_from_wgs84(&far, &observer).is_above(0.));
+}
+
+#[test]
+fn test_aer_rates() {
+    // Compare with differences of the look angles a short time apart
+    let observer = WGS84Coord::new_from_degrees(51.5, -0.1, 50.);
+    let position = ECEF {
+        x: 4.2e6,
+        y: 1.1e6,
+        z: 5.4e6,
+    };
+    let velocity = ECEFVel {
+        x_vel: -3200.,
+        y_vel: 6100.,
+        z_vel: 1500.,
+    };
+    let rates = AERRate::new_from_ecef_vel(&position, &velocity, &observer);
+
+    let dt = 1e-3;
+    let at = |t: f64| {
+        let ecef = ECEF {
+            x: position.x + velocity.x_vel * t,
+            y: position.y + velocity.y_vel * t,
+            z: position.z + velocity.z_vel * t,
+        };
+        return AER::new_from_ecef(&ecef, &observer);
+    };
+    let (before, after) = (at(-dt), at(dt));
+
+    assert_relative_eq!(
+        rates.range_rate,
+        (after.range - before.range) / (2. * dt),
+        max_relative = 1e-6
+    );
+    assert_relative_eq!(
+        rates.azimuth_rate,
+        (after.azimuth - before.azimuth) / (2. * dt),
+        max_relative = 1e-6
+    );
+    assert_relative_eq!(
+        rates.elevation_rate,
+        (after.elevation - before.elevation) / (2. * dt),
+        max_relative = 1e-6
+    );
+}
+
+#[test]
+fn test_azimuth_wrap_and_masks() {
+    assert_relative_eq!(wrap_azimuth(-FRAC_PI_2, AzimuthWrap::ZeroToTwoPi), 1.5 * PI);
+    assert_relative_eq!(
+        wrap_azimuth(5. * PI / 2., AzimuthWrap::ZeroToTwoPi),
+        FRAC_PI_2
+    );
+    assert_relative_eq!(wrap_azimuth(1.5 * PI, AzimuthWrap::MinusPiToPi), -FRAC_PI_2);
+    assert!(wrap_azimuth(-1e-20, AzimuthWrap::ZeroToTwoPi) < 2. * PI);
+
+    // Hills to the east, open to the west, given out of order and in both conventions
+    let mask = ElevationMask::new(&[
+        (PI, 0.),
+        (FRAC_PI_2, 20_f64.to_radians()),
+        (-FRAC_PI_2, 0.),
+        (0., 10_f64.to_radians()),
+    ]);
+
+    assert_relative_eq!(mask.get_elevation(FRAC_PI_2), 20_f64.to_radians());
+    assert_relative_eq!(mask.get_elevation(PI / 4.), 15_f64.to_radians());
+    assert_relative_eq!(mask.get_elevation(1.25 * PI), 0.);
+    // Interpolated through north
+    assert_relative_eq!(
+        mask.get_elevation(1.75 * PI),
+        5_f64.to_radians(),
+        epsilon = 1e-15
+    );
+    assert_relative_eq!(
+        mask.get_elevation(-PI / 4.),
+        5_f64.to_radians(),
+        epsilon = 1e-15
+    );
+
+    let low_east = AER::new_from_raw(FRAC_PI_2, 10_f64.to_radians(), 1e6);
+    let low_west = AER::new_from_raw(-FRAC_PI_2, 10_f64.to_radians(), 1e6);
+    assert!(!low_east.is_above_mask(&mask));
+    assert!(low_west.is_above_mask(&mask));
+
+    let constant = ElevationMask::new_constant(5_f64.to_radians());
+    assert_relative_eq!(constant.get_elevation(2.), 5_f64.to_radians());
+    assert!(low_east.is_above_mask(&constant));
+}
@@ -0,0 +1,79 @@
+use approx::assert_relative_eq;
+
+use crate::geodesics::{centroid, karney};
+use crate::reference_frames::wgs84::WGS84Coord;
+
+fn from_degrees(points: &[(f64, f64, f64)]) -> Vec<WGS84Coord> {
+    return points
+        .iter()
+        .map(|(lat, lon, alt)| WGS84Coord::new_from_degrees(*lat, *lon, *alt))
+        .collect();
+}
+
+#[test]
+fn test_centroid() {
+    // Symmetric about a point on the equator
+    let points = from_degrees(&[(0., -1., 10.), (0., 1., 10.), (1., 0., 10.), (-1., 0., 10.)]);
+    let mean = centroid::centroid(&points).unwrap();
+
+    assert_relative_eq!(mean.get_lat_radians(), 0., epsilon = 1e-15);
+    assert_relative_eq!(mean.get_lon_radians(), 0., epsilon = 1e-15);
+    assert_relative_eq!(mean.get_altitude(), 10., epsilon = 1e-9);
+
+    // Averaging longitudes would put this cluster on the prime meridian
+    let points = from_degrees(&[
+        (10., 179., 0.),
+        (10., -179., 0.),
+        (11., 179.5, 0.),
+        (9., -179.5, 0.),
+    ]);
+    let mean = centroid::centroid(&points).unwrap();
+
+    assert_relative_eq!(mean.get_lat_degrees(), 10., epsilon = 1e-2);
+    assert!(mean.get_lon_degrees().abs() > 179.99);
+
+    assert!(centroid::centroid(&[]).is_none());
+    assert!(centroid::centroid(&from_degrees(&[(20., 30., 0.), (-20., -150., 0.)])).is_none());
+}
+
+#[test]
+fn test_geodesic_median() {
+    // Four fixes around London and one stray fix in Paris
+    let points = from_degrees(&[
+        (51.5, -0.1, 10.),
+        (51.6, -0.2, 12.),
+        (51.55, 0., 11.),
+        (51.52, -0.15, 90.),
+        (48.85, 2.35, 35.),
+    ]);
+    let median = centroid::geodesic_median(&points).unwrap();
+    let mean = centroid::centroid(&points).unwrap();
+
+    // The median stays with the cluster, the centroid is pulled well towards Paris
+    assert!((median.get_lat_degrees() - 51.5).abs() < 0.1);
+    assert!(mean.get_lat_degrees() < 51.1);
+    assert_relative_eq!(median.get_altitude(), 12., epsilon = 1e-12);
+
+    // No nearby point has a smaller total distance
+    let total = |lat: f64, lon: f64| {
+        return points
+            .iter()
+            .map(|p| karney::inverse(lat, lon, p.get_lat_radians(), p.get_lon_radians()).distance)
+            .sum::<f64>();
+    };
+    let best = total(median.get_lat_radians(), median.get_lon_radians());
+    for (dlat, dlon) in [(1e-7, 0.), (-1e-7, 0.), (0., 1e-7), (0., -1e-7)] {
+        assert!(
+            total(
+                median.get_lat_radians() + dlat,
+                median.get_lon_radians() + dlon
+            ) > best
+        );
+    }
+
+    let single = centroid::geodesic_median(&from_degrees(&[(45., 7., 300.)])).unwrap();
+
+    assert_relative_eq!(single.get_lat_degrees(), 45., epsilon = 1e-12);
+    assert_relative_eq!(single.get_lon_degrees(), 7., epsilon = 1e-12);
+    assert_relative_eq!(single.get_altitude(), 300., epsilon = 1e-12);
+}
@@ -0,0 +1,221 @@
+use approx::assert_relative_eq;
+use chrono::NaiveDate;
+
+use crate::constants::ARCSEC_TO_RAD;
+use crate::reference_frames::cio::{
+    cio_locator, get_celestial_to_intermediate_matrix, get_cip_xy, nutation_iau2000b, CIO, CIRS,
+    TIRS,
+};
+use crate::reference_frames::ecef::{ECEFVel, ECEF};
+use crate::reference_frames::fk5::FK5;
+use crate::reference_frames::gcrf::GCRF;
+use crate::reference_frames::pef::PEF;
+use crate::reference_frames::transform::FrameGraph;
+use crate::time::eop::{EOPSource, EarthOrientation};
+use crate::time::epoch::{Epoch, TimeScale};
+use crate::time::julian_date::JulianDate;
+
+fn sofa_epoch() -> Epoch {
+    // The SOFA examples are at MJD 53736 in both TT and UT1, which is UT1 - UTC of 64.184 s
+    let datetime = NaiveDate::from_ymd_opt(2006, 1, 1)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+    return Epoch::new_from_ut1(&datetime, 64.184);
+}
+
+fn earth_orientation(xp: f64, yp: f64, dx: f64, dy: f64, lod: f64) -> EarthOrientation {
+    return EarthOrientation {
+        xp,
+        yp,
+        delta_ut1: 0.,
+        lod,
+        dx,
+        dy,
+        source: EOPSource::Observed,
+    };
+}
+
+#[test]
+fn test_series() {
+    let epoch = sofa_epoch();
+    let tt = JulianDate::new_from_epoch(&epoch, TimeScale::TT);
+    assert_eq!(tt.get_mjd(), 53736.);
+    assert_eq!(JulianDate::new_from_epoch(&epoch, TimeScale::UT1), tt);
+
+    // iauNut00b
+    let (dpsi, deps) = nutation_iau2000b(tt.get_julian_centuries());
+    assert_relative_eq!(dpsi, -9.632_552_291_148_363e-6, epsilon = 1e-13);
+    assert_relative_eq!(deps, 4.063_197_106_621_159e-5, epsilon = 1e-13);
+
+    // iauS06
+    let s = cio_locator(&tt, 5.791_308_486_706_011e-4, 4.020_579_816_732_961e-5);
+    assert_relative_eq!(s, -1.220_032_213_076_463e-8, epsilon = 1e-18);
+
+    // iauXy06 uses the full 2000A series, which 2000B follows to within a milliarcsecond
+    let (x, y) = get_cip_xy(&tt);
+    assert_relative_eq!(x, 5.791_308_486_706_011e-4, epsilon = 5e-9);
+    assert_relative_eq!(y, 4.020_579_816_732_958e-5, epsilon = 5e-9);
+}
+
+#[test]
+fn test_celestial_to_intermediate() {
+    // iauC2ixy
+    let (x, y) = (5.791_308_486_706_011e-4, 4.020_579_816_732_961e-5);
+    let tt = JulianDate::new_from_epoch(&sofa_epoch(), TimeScale::TT);
+    let s = cio_locator(&tt, x, y);
+    let matrix = get_celestial_to_intermediate_matrix(x, y, s);
+    let expected = [
+        [
+            0.999_999_832_303_715_7,
+            5.581_526_349_032_241e-10,
+            -5.791_308_491_611_264e-4,
+        ],
+        [
+            -2.384_257_057_469_843e-8,
+            0.999_999_999_191_746_9,
+            -4.020_579_110_172_324_5e-5,
+        ],
+        [
+            5.791_308_486_706_011e-4,
+            4.020_579_816_732_961e-5,
+            0.999_999_831_495_462_8,
+        ],
+    ];
+    for (row, expected_row) in expected.iter().enumerate() {
+        for (column, value) in expected_row.iter().enumerate() {
+            assert_relative_eq!(matrix.get(row, column), *value, epsilon = 1e-12);
+        }
+    }
+}
+
+#[test]
+fn test_celestial_to_terrestrial() {
+    // iauC2t06a with the SOFA polar motion. 2000B puts the pole within a milliarcsecond of 2000A
+    let eop = earth_orientation(2.55060238e-7, 1.860359247e-6, 0., 0., 0.);
+    let matrix = CIO::new_from_eop(sofa_epoch(), &eop).get_celestial_to_terrestrial_matrix();
+    let expected = [
+        [
+            -0.181_033_212_852_868_58,
+            0.983_476_980_689_768_5,
+            6.555_550_962_998_436e-5,
+        ],
+        [
+            -0.983_476_813_413_599_7,
+            -0.181_033_220_364_944_85,
+            5.749_800_844_905_594e-4,
+        ],
+        [
+            5.773_474_014_081_54e-4,
+            3.961_832_391_768_641e-5,
+            0.999_999_831_154_315_1,
+        ],
+    ];
+    for (row, expected_row) in expected.iter().enumerate() {
+        for (column, value) in expected_row.iter().enumerate() {
+            assert_relative_eq!(matrix.get(row, column), *value, epsilon = 1e-8);
+        }
+    }
+}
+
+#[test]
+fn test_ecef_to_gcrf() {
+    // Vallado, Fundamentals of Astrodynamics and Applications, example 3-14
+    let utc = NaiveDate::from_ymd_opt(2004, 4, 6)
+        .unwrap()
+        .and_hms_nano_opt(7, 51, 28, 386_009_000)
+        .unwrap();
+    let mut epoch = Epoch::new_from_utc(&utc);
+    epoch.set_delta_ut1(-0.4399619);
+    let eop = earth_orientation(
+        -0.140682 * ARCSEC_TO_RAD,
+        0.333309 * ARCSEC_TO_RAD,
+        -0.000205 * ARCSEC_TO_RAD,
+        -0.000136 * ARCSEC_TO_RAD,
+        0.0015563,
+    );
+    let cio = CIO::new_from_eop(epoch, &eop);
+
+    let ecef = ECEF {
+        x: -1033.479383,
+        y: 7901.2952754,
+        z: 6380.3565958,
+    };
+    let ecef_vel = ECEFVel {
+        x_vel: -3.22563652,
+        y_vel: -2.87245145,
+        z_vel: 5.531924446,
+    };
+
+    // Vallado uses the full 2000A series, a milliarcsecond or 3 cm away here
+    let gcrf = cio.gcrf_from_ecef(&ecef);
+    assert_relative_eq!(gcrf.x, 5102.508959, epsilon = 1e-4);
+    assert_relative_eq!(gcrf.y, 6123.011403, epsilon = 1e-4);
+    assert_relative_eq!(gcrf.z, 6378.136925, epsilon = 1e-4);
+    let gcrf_vel = cio.gcrf_vel_from_ecef_vel(&ecef, &ecef_vel);
+    assert_relative_eq!(gcrf_vel.x_vel, -4.743220156, epsilon = 1e-7);
+    assert_relative_eq!(gcrf_vel.y_vel, 0.790536497, epsilon = 1e-7);
+    assert_relative_eq!(gcrf_vel.z_vel, 5.533755728, epsilon = 1e-7);
+
+    let tirs = cio.tirs_from_ecef(&ecef);
+    assert_relative_eq!(tirs.x, -1033.4750312, epsilon = 1e-6);
+    assert_relative_eq!(tirs.y, 7901.3055856, epsilon = 1e-6);
+    assert_relative_eq!(tirs.z, 6380.3445327, epsilon = 1e-6);
+
+    // And back again
+    let back = cio.ecef_from_gcrf(&gcrf);
+    assert_relative_eq!(back.x, ecef.x, epsilon = 1e-8);
+    assert_relative_eq!(back.y, ecef.y, epsilon = 1e-8);
+    assert_relative_eq!(back.z, ecef.z, epsilon = 1e-8);
+    let back_vel = cio.ecef_vel_from_gcrf_vel(&gcrf, &gcrf_vel);
+    assert_relative_eq!(back_vel.x_vel, ecef_vel.x_vel, epsilon = 1e-11);
+    assert_relative_eq!(back_vel.y_vel, ecef_vel.y_vel, epsilon = 1e-11);
+    assert_relative_eq!(back_vel.z_vel, ecef_vel.z_vel, epsilon = 1e-11);
+}
+
+#[test]
+fn test_frames() {
+    let epoch = sofa_epoch();
+    let gcrf = GCRF {
+        x: 5102.508959,
+        y: 6123.011403,
+        z: 6378.136925,
+    };
+
+    let cirs = CIRS::new_from_gcrf(&gcrf, epoch);
+    let tirs = TIRS::new_from_cirs(&cirs, epoch);
+    let ecef = ECEF::new_from_tirs(&tirs, epoch);
+    let expected = CIO::new(epoch).ecef_from_gcrf(&gcrf);
+    assert_relative_eq!(ecef.x, expected.x, epsilon = 1e-9);
+    assert_relative_eq!(ecef.y, expected.y, epsilon = 1e-9);
+    assert_relative_eq!(ecef.z, expected.z, epsilon = 1e-9);
+
+    // The frame graph goes between GCRF and ECEF through the intermediate frames
+    let graph = FrameGraph::new_from_time(epoch);
+    let converted: ECEF = graph.convert(&gcrf).unwrap();
+    assert_relative_eq!(converted.x, expected.x, epsilon = 1e-9);
+    assert_relative_eq!(converted.y, expected.y, epsilon = 1e-9);
+    assert_relative_eq!(converted.z, expected.z, epsilon = 1e-9);
+    let back: GCRF = graph.convert(&TIRS::new_from_ecef(&ecef, epoch)).unwrap();
+    assert_relative_eq!(back.x, gcrf.x, epsilon = 1e-9);
+    assert_relative_eq!(back.y, gcrf.y, epsilon = 1e-9);
+    assert_relative_eq!(back.z, gcrf.z, epsilon = 1e-9);
+    let back = GCRF::new_from_cirs(&CIRS::new_from_tirs(&tirs, epoch), epoch);
+    assert_relative_eq!(back.x, gcrf.x, epsilon = 1e-9);
+    let back = GCRF::new_from_ecef(&ecef, epoch);
+    assert_relative_eq!(back.x, gcrf.x, epsilon = 1e-9);
+    assert_relative_eq!(back.y, gcrf.y, epsilon = 1e-9);
+    assert_relative_eq!(back.z, gcrf.z, epsilon = 1e-9);
+
+    // By default the graph reaches GCRF from the FK5 side through ECEF too
+    let back: GCRF = graph.convert(&PEF::new_from_ecef(&ecef, epoch)).unwrap();
+    assert_relative_eq!(back.x, gcrf.x, epsilon = 1e-9);
+    assert_relative_eq!(back.y, gcrf.y, epsilon = 1e-9);
+    assert_relative_eq!(back.z, gcrf.z, epsilon = 1e-9);
+
+    // The CIO and FK5 reductions agree to the size of the 1980 nutation's errors, tens of mas
+    let fk5 = FK5::new(epoch).gcrf_from_ecef(&ecef);
+    assert_relative_eq!(fk5.x, gcrf.x, epsilon = 1e-2);
+    assert_relative_eq!(fk5.y, gcrf.y, epsilon = 1e-2);
+    assert_relative_eq!(fk5.z, gcrf.z, epsilon = 1e-2);
+}
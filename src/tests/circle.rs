@@ -0,0 +1,166 @@
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use approx::assert_relative_eq;
+
+use crate::geodesics::{
+    circle, cross_track, karney,
+    polygon::{GeodesicPolygon, Winding},
+};
+use crate::reference_frames::wgs84::WGS84Coord;
+
+#[test]
+fn test_bounding_box() {
+    let centre = WGS84Coord::new_from_degrees(52., 4., 0.);
+    let radius = 250e3;
+    let bbox = circle::bounding_box(&centre, radius);
+
+    assert!(!bbox.crosses_antimeridian());
+    for point in circle::circle(&centre, radius, 720) {
+        assert!(bbox.contains(point.get_lat_radians(), point.get_lon_radians()));
+    }
+
+    // The limits are tight, points just outside them are further away than the radius
+    let (lat, lon) = (centre.get_lat_radians(), centre.get_lon_radians());
+    let north = karney::inverse(lat, lon, bbox.max_lat, lon).distance;
+    assert_relative_eq!(north, radius, epsilon = 1e-2);
+    let east = karney::inverse(lat, lon, lat, bbox.max_lon).distance;
+    assert!(east > radius);
+    let mut widest = 0_f64;
+    for point in circle::circle(&centre, radius, 3600) {
+        widest = widest.max(point.get_lon_radians() - lon);
+    }
+    assert_relative_eq!(bbox.max_lon - lon, widest, epsilon = 1e-7);
+    assert_relative_eq!(lon - bbox.min_lon, widest, epsilon = 1e-7);
+}
+
+#[test]
+fn test_bounding_box_antimeridian_and_poles() {
+    let centre = WGS84Coord::new_from_degrees(-40., 179.5, 0.);
+    let bbox = circle::bounding_box(&centre, 200e3);
+
+    assert!(bbox.crosses_antimeridian());
+    assert!(bbox.contains((-40_f64).to_radians(), (-179.5_f64).to_radians()));
+    assert!(bbox.contains((-40_f64).to_radians(), (179_f64).to_radians()));
+    assert!(!bbox.contains((-40_f64).to_radians(), (0_f64).to_radians()));
+    for point in circle::circle(&centre, 200e3, 360) {
+        assert!(bbox.contains(point.get_lat_radians(), point.get_lon_radians()));
+    }
+
+    // Reaching over the north pole takes in every longitude
+    let centre = WGS84Coord::new_from_degrees(89., -60., 0.);
+    let bbox = circle::bounding_box(&centre, 200e3);
+
+    assert_eq!(bbox.max_lat, FRAC_PI_2);
+    assert_eq!((bbox.min_lon, bbox.max_lon), (-PI, PI));
+    assert!(bbox.min_lat < (89_f64).to_radians());
+    assert!(bbox.contains(FRAC_PI_2 - 1e-6, 2.));
+}
+
+#[test]
+fn test_circle_and_ellipse() {
+    let centre = WGS84Coord::new_from_degrees(35., -100., 0.);
+    let radius = 10e3;
+    let points = circle::circle(&centre, radius, 3600);
+
+    assert_eq!(points.len(), 3600);
+    for point in points.iter() {
+        let distance = karney::inverse(
+            centre.get_lat_radians(),
+            centre.get_lon_radians(),
+            point.get_lat_radians(),
+            point.get_lon_radians(),
+        )
+        .distance;
+        assert_relative_eq!(distance, radius, epsilon = 1e-6);
+    }
+
+    // Counter-clockwise, so the area is positive and close to that of a flat disc
+    let area = GeodesicPolygon::new_from_coords(&points, Winding::CounterClockwise)
+        .compute(true)
+        .area;
+    assert_relative_eq!(area, PI * radius * radius, max_relative = 1e-5);
+
+    // An ellipse with its major axis east-west
+    let points = circle::ellipse(&centre, 20e3, 5e3, FRAC_PI_2, 4);
+    let distances: Vec<f64> = points
+        .iter()
+        .map(|point| {
+            karney::inverse(
+                centre.get_lat_radians(),
+                centre.get_lon_radians(),
+                point.get_lat_radians(),
+                point.get_lon_radians(),
+            )
+            .distance
+        })
+        .collect();
+    assert_relative_eq!(distances[0], 5e3, epsilon = 1e-6);
+    assert_relative_eq!(distances[1], 20e3, epsilon = 1e-6);
+    assert_relative_eq!(distances[2], 5e3, epsilon = 1e-6);
+    assert_relative_eq!(distances[3], 20e3, epsilon = 1e-6);
+    // The second vertex is the western end
+    assert!(points[1].get_lon_radians() < centre.get_lon_radians());
+}
+
+#[test]
+fn test_buffer() {
+    let polyline = [
+        WGS84Coord::new_from_degrees(50., 0., 0.),
+        WGS84Coord::new_from_degrees(50.5, 0.5, 0.),
+        WGS84Coord::new_from_degrees(50.5, 0.5, 0.),
+        WGS84Coord::new_from_degrees(50., 1.2, 0.),
+        WGS84Coord::new_from_degrees(50.3, 1.5, 0.),
+    ];
+    let distance = 5e3;
+    let ring = circle::buffer(&polyline, distance, 72);
+
+    // Every vertex of the outline is at least the buffer distance from the polyline, and no more
+    // than that except at the inside of the bends
+    for point in ring.iter() {
+        let nearest = polyline
+            .windows(2)
+            .filter(|pair| pair[0].get_lon_radians() != pair[1].get_lon_radians())
+            .map(|pair| {
+                let foot = cross_track::closest_point_on_segment(
+                    pair[0].get_lat_radians(),
+                    pair[0].get_lon_radians(),
+                    pair[1].get_lat_radians(),
+                    pair[1].get_lon_radians(),
+                    point.get_lat_radians(),
+                    point.get_lon_radians(),
+                );
+                karney::inverse(
+                    foot.get_lat_radians(),
+                    foot.get_lon_radians(),
+                    point.get_lat_radians(),
+                    point.get_lon_radians(),
+                )
+                .distance
+            })
+            .fold(f64::INFINITY, f64::min);
+        assert!(nearest > distance - 1e-3);
+        assert!(nearest < 1.5 * distance);
+    }
+
+    // The outline is counter-clockwise and covers about a 10 km wide corridor plus the caps
+    let area = GeodesicPolygon::new_from_coords(&ring, Winding::CounterClockwise)
+        .compute(true)
+        .area;
+    let mut length = 0.;
+    for pair in polyline.windows(2) {
+        length += karney::inverse(
+            pair[0].get_lat_radians(),
+            pair[0].get_lon_radians(),
+            pair[1].get_lat_radians(),
+            pair[1].get_lon_radians(),
+        )
+        .distance;
+    }
+    let corridor = 2. * distance * length + PI * distance * distance;
+    assert_relative_eq!(area, corridor, max_relative = 0.02);
+
+    // A single point buffers to a circle
+    let single = circle::buffer(&polyline[..1], distance, 36);
+    assert_eq!(single.len(), 36);
+    assert!(circle::buffer(&[], distance, 36).is_empty());
+}
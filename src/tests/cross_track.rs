@@ -0,0 +1,100 @@
+use std::f64::consts::FRAC_PI_2;
+
+use approx::assert_relative_eq;
+
+use crate::ellipsoid::Ellipsoid;
+use crate::geodesics::{cross_track, karney::Geodesic};
+
+#[test]
+fn test_spherical_cross_track() {
+    // Worked example from Chris Veness' great circle calculator
+    let (lat1, lon1) = (53.3206_f64.to_radians(), -1.7297_f64.to_radians());
+    let (lat2, lon2) = (53.1887_f64.to_radians(), 0.1334_f64.to_radians());
+    let (lat, lon) = (53.2611_f64.to_radians(), -0.7972_f64.to_radians());
+
+    let track = cross_track::spherical_cross_track(lat1, lon1, lat2, lon2, lat, lon);
+
+    assert_relative_eq!(track.cross_track, -307.5, epsilon = 0.1);
+    assert_relative_eq!(track.along_track, 62.331e3, epsilon = 1.);
+
+    // Reversing the route puts the point on the other side
+    let reversed = cross_track::spherical_cross_track(lat2, lon2, lat1, lon1, lat, lon);
+
+    assert_relative_eq!(reversed.cross_track, -track.cross_track, epsilon = 1e-6);
+
+    let closest = cross_track::spherical_closest_point_on_segment(lat1, lon1, lat2, lon2, lat, lon);
+    let from_closest = cross_track::spherical_cross_track(
+        lat1,
+        lon1,
+        lat2,
+        lon2,
+        closest.get_lat_radians(),
+        closest.get_lon_radians(),
+    );
+
+    assert_relative_eq!(from_closest.cross_track, 0., epsilon = 1e-6);
+    assert_relative_eq!(from_closest.along_track, track.along_track, epsilon = 1e-6);
+}
+
+#[test]
+fn test_equator() {
+    // The meridian through a point meets the equator at right angles, so the foot is due south
+    let (lon1, lon2) = (0., 10_f64.to_radians());
+    let (lat, lon) = (1_f64.to_radians(), 5_f64.to_radians());
+
+    let track = cross_track::cross_track(0., lon1, 0., lon2, lat, lon);
+
+    // Meridian arc to 1 degree, negative as the point is left of an eastbound route
+    assert_relative_eq!(track.cross_track, -110574.389, epsilon = 1e-3);
+    assert_relative_eq!(
+        track.along_track,
+        Ellipsoid::WGS84.get_semi_major() * lon,
+        epsilon = 1e-8
+    );
+
+    let closest = cross_track::closest_point_on_segment(0., lon1, 0., lon2, lat, lon);
+
+    assert_relative_eq!(closest.get_lat_radians(), 0., epsilon = 1e-15);
+    assert_relative_eq!(closest.get_lon_radians(), lon, epsilon = 1e-15);
+}
+
+#[test]
+fn test_constructed_perpendicular() {
+    // Step along a geodesic then off it at right angles, and recover both distances
+    let geodesic = Geodesic::new(&Ellipsoid::WGS84);
+    let (lat1, lon1, azimuth) = (
+        52_f64.to_radians(),
+        -30_f64.to_radians(),
+        70_f64.to_radians(),
+    );
+    let end = geodesic.direct(lat1, lon1, azimuth, 3e6);
+
+    for (along, offset) in [
+        (1.2e6, 250e3),
+        (2.9e6, -40e3),
+        (-500e3, 1.5e6),
+        (3.6e6, -800e3),
+    ] {
+        let foot = geodesic.direct(lat1, lon1, azimuth, along);
+        let point = geodesic.direct(foot.lat, foot.lon, foot.azimuth + FRAC_PI_2, offset);
+
+        let track = cross_track::cross_track(lat1, lon1, end.lat, end.lon, point.lat, point.lon);
+
+        assert_relative_eq!(track.along_track, along, epsilon = 1e-6);
+        assert_relative_eq!(track.cross_track, offset, epsilon = 1e-6);
+
+        let closest = cross_track::closest_point_on_segment(
+            lat1, lon1, end.lat, end.lon, point.lat, point.lon,
+        );
+        let expected = if along < 0. {
+            (lat1, lon1)
+        } else if along > 3e6 {
+            (end.lat, end.lon)
+        } else {
+            (foot.lat, foot.lon)
+        };
+
+        assert_relative_eq!(closest.get_lat_radians(), expected.0, epsilon = 1e-12);
+        assert_relative_eq!(closest.get_lon_radians(), expected.1, epsilon = 1e-12);
+    }
+}
@@ -0,0 +1,166 @@
+use approx::assert_relative_eq;
+
+use crate::datum::helmert::{HelmertParams, RotationConvention};
+use crate::datum::Datum;
+use crate::reference_frames::ecef::ECEF;
+use crate::reference_frames::wgs84::WGS84Coord;
+
+#[test]
+fn test_helmert_round_trip() {
+    let helmert = Datum::OSGB36.to_wgs84;
+    let ecef = ECEF {
+        x: 3874938.849,
+        y: 116218.624,
+        z: 5047168.208,
+    };
+
+    let wgs84 = helmert.apply(&ecef);
+    let back = helmert.apply_inverse(&wgs84);
+
+    assert_relative_eq!(back.x, ecef.x, epsilon = 1e-6);
+    assert_relative_eq!(back.y, ecef.y, epsilon = 1e-6);
+    assert_relative_eq!(back.z, ecef.z, epsilon = 1e-6);
+
+    // Negating the parameters is the first order reverse, within a few mm of the exact one
+    let approx_back = helmert.reversed().apply(&wgs84);
+
+    assert_relative_eq!(approx_back.x, ecef.x, epsilon = 1e-2);
+    assert_relative_eq!(approx_back.y, ecef.y, epsilon = 1e-2);
+    assert_relative_eq!(approx_back.z, ecef.z, epsilon = 1e-2);
+}
+
+#[test]
+fn test_helmert_conventions_agree() {
+    let position_vector = HelmertParams {
+        tx: 10.0,
+        ty: -20.0,
+        tz: 30.0,
+        rx: 1.5,
+        ry: -0.5,
+        rz: 2.0,
+        scale_ppm: 3.0,
+        convention: RotationConvention::PositionVector,
+    };
+    let coordinate_frame = position_vector.to_convention(RotationConvention::CoordinateFrame);
+
+    assert_eq!(coordinate_frame.rx, -1.5);
+
+    let ecef = ECEF {
+        x: 4045456.0,
+        y: 713323.0,
+        z: 4862789.0,
+    };
+
+    let a = position_vector.apply(&ecef);
+    let b = coordinate_frame.apply(&ecef);
+
+    assert_relative_eq!(a.x, b.x, epsilon = 1e-9);
+    assert_relative_eq!(a.y, b.y, epsilon = 1e-9);
+    assert_relative_eq!(a.z, b.z, epsilon = 1e-9);
+}
+
+#[test]
+fn test_datum_round_trip() {
+    let point = WGS84Coord::new_from_degrees(35.68, 139.77, 40.0);
+
+    for datum in Datum::ALL.iter() {
+        let local = datum.from_wgs84(&point);
+        let back = datum.to_wgs84(&local);
+
+        assert_relative_eq!(
+            back.get_lat_radians(),
+            point.get_lat_radians(),
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(
+            back.get_lon_radians(),
+            point.get_lon_radians(),
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(back.get_altitude(), point.get_altitude(), epsilon = 1e-5);
+    }
+
+    // Around Tokyo the same point reads about 12 arcseconds further south and east on Tokyo datum
+    let tokyo = Datum::TOKYO.from_wgs84(&point);
+
+    assert_relative_eq!(tokyo.get_lat_degrees() - 35.68, -0.0032, epsilon = 2e-4);
+    assert_relative_eq!(tokyo.get_lon_degrees() - 139.77, 0.0032, epsilon = 2e-4);
+}
+
+#[test]
+fn test_molodensky_matches_geocentric_translation() {
+    // With a pure translation the exact ECEF route and Molodensky should agree closely
+    let point = WGS84Coord::new_from_degrees(39.0, -98.0, 300.0);
+    let nad27 = Datum::NAD27;
+
+    let exact = nad27.to_wgs84(&point);
+    let standard = nad27.to_wgs84_molodensky(&point, false);
+    let abridged = nad27.to_wgs84_molodensky(&point, true);
+
+    // 1e-7 rad is about 0.6 m on the ground
+    assert_relative_eq!(
+        standard.get_lat_radians(),
+        exact.get_lat_radians(),
+        epsilon = 1e-7
+    );
+    assert_relative_eq!(
+        standard.get_lon_radians(),
+        exact.get_lon_radians(),
+        epsilon = 1e-7
+    );
+    assert_relative_eq!(standard.get_altitude(), exact.get_altitude(), epsilon = 0.5);
+
+    assert_relative_eq!(
+        abridged.get_lat_radians(),
+        exact.get_lat_radians(),
+        epsilon = 1e-6
+    );
+    assert_relative_eq!(
+        abridged.get_lon_radians(),
+        exact.get_lon_radians(),
+        epsilon = 1e-6
+    );
+    assert_relative_eq!(abridged.get_altitude(), exact.get_altitude(), epsilon = 5.0);
+
+    let back = nad27.from_wgs84_molodensky(&standard, false);
+
+    assert_relative_eq!(
+        back.get_lat_radians(),
+        point.get_lat_radians(),
+        epsilon = 1e-7
+    );
+    assert_relative_eq!(
+        back.get_lon_radians(),
+        point.get_lon_radians(),
+        epsilon = 1e-7
+    );
+}
+
+#[test]
+fn test_datum_lookup() {
+    assert_eq!(Datum::new_from_name("osgb36"), Some(Datum::OSGB36));
+    assert_eq!(
+        Datum::new_from_name("NAD27")
+            .unwrap()
+            .ellipsoid
+            .get_semi_major(),
+        6378206.4
+    );
+    assert_eq!(Datum::new_from_name("NAD83"), None);
+
+    // ED50 -> OSGB36 through WGS84 and back again
+    let point = WGS84Coord::new_from_degrees(53.0, -1.0, 0.0);
+    let osgb = Datum::ED50.transform(&point, &Datum::OSGB36);
+    let ed50 = Datum::OSGB36.transform(&osgb, &Datum::ED50);
+
+    assert_relative_eq!(
+        ed50.get_lat_radians(),
+        point.get_lat_radians(),
+        epsilon = 1e-12
+    );
+    assert_relative_eq!(
+        ed50.get_lon_radians(),
+        point.get_lon_radians(),
+        epsilon = 1e-12
+    );
+}
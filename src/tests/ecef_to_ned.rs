@@ -1,5 +1,6 @@
 use approx::assert_relative_eq;
 
+use crate::matrix::Mat3;
 use crate::reference_frames::ecef::{
     construct_ecef_to_ned_jacobian, generate_ecef_to_ned_matrix, ECEFVel, ECEF,
 };
@@ -8,7 +9,7 @@ use crate::reference_frames::wgs84::WGS84Coord;
 
 #[test]
 fn test_ecef_to_ned_jacobian() {
-    let basic_matrix = vec![1.0; 9];
+    let basic_matrix = Mat3::new_from_array([1.0; 9]);
 
     let jacobian = construct_ecef_to_ned_jacobian(&basic_matrix);
 
@@ -23,7 +24,7 @@ fn test_ecef_to_ned_jacobian() {
 
     // =============================================
 
-    let basic_matrix = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0];
+    let basic_matrix = Mat3::new_from_array([1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
 
     let jacobian = construct_ecef_to_ned_jacobian(&basic_matrix);
 
@@ -0,0 +1,98 @@
+use approx::assert_relative_eq;
+
+use crate::constants::EARTH_MEAN_RADIUS;
+use crate::ellipsoid::Ellipsoid;
+use crate::geodesics::{karney, vincenty};
+use crate::reference_frames::ecef::ECEF;
+use crate::reference_frames::wgs84::WGS84Coord;
+
+#[test]
+fn test_derived_parameters() {
+    let wgs84 = Ellipsoid::WGS84;
+
+    assert_relative_eq!(wgs84.get_semi_minor(), 6356752.314245, epsilon = 1e-6);
+    assert_relative_eq!(
+        wgs84.get_eccentricity_squared(),
+        6.69437999014e-3,
+        epsilon = 1e-14
+    );
+    assert_relative_eq!(
+        wgs84.get_second_eccentricity_squared(),
+        6.73949674228e-3,
+        epsilon = 1e-14
+    );
+
+    // Clarke 1866 is defined by its axes; its inverse flattening is the published 294.9786982
+    assert_relative_eq!(
+        Ellipsoid::CLARKE_1866.get_inverse_flattening(),
+        294.9786982,
+        epsilon = 1e-6
+    );
+
+    assert_eq!(Ellipsoid::SPHERE.get_eccentricity_squared(), 0.0);
+    assert_eq!(Ellipsoid::default(), Ellipsoid::WGS84);
+}
+
+#[test]
+fn test_geodetic_ecef_round_trip_on_ellipsoid() {
+    // Ordnance Survey's worked example: OSGB36 / Airy 1830 geodetic to cartesian
+    let airy = Ellipsoid::AIRY_1830;
+    let point = WGS84Coord::new_from_degrees(
+        52. + 39. / 60. + 27.2531 / 3600.,
+        1. + 43. / 60. + 4.5177 / 3600.,
+        24.7,
+    );
+
+    let ecef = ECEF::new_from_geodetic(&point, &airy);
+
+    assert_relative_eq!(ecef.x, 3874938.849, epsilon = 1e-3);
+    assert_relative_eq!(ecef.y, 116218.624, epsilon = 1e-3);
+    assert_relative_eq!(ecef.z, 5047168.208, epsilon = 1e-3);
+
+    let back = WGS84Coord::new_from_ecef_on_ellipsoid(ecef.x, ecef.y, ecef.z, &airy);
+
+    assert_relative_eq!(
+        back.get_lat_radians(),
+        point.get_lat_radians(),
+        epsilon = 1e-11
+    );
+    assert_relative_eq!(
+        back.get_lon_radians(),
+        point.get_lon_radians(),
+        epsilon = 1e-11
+    );
+    assert_relative_eq!(back.get_altitude(), point.get_altitude(), epsilon = 1e-4);
+}
+
+#[test]
+fn test_geodesics_on_sphere() {
+    // On a sphere of the mean radius the ellipsoidal solvers should reduce to the great circle
+    let lat1 = 51.5_f64.to_radians();
+    let lon1 = -0.12_f64.to_radians();
+    let lat2 = 40.7_f64.to_radians();
+    let lon2 = -74.0_f64.to_radians();
+
+    // Spherical law of cosines, well conditioned at this separation
+    let central_angle =
+        (lat1.sin() * lat2.sin() + lat1.cos() * lat2.cos() * (lon2 - lon1).cos()).acos();
+    let great_circle_distance = EARTH_MEAN_RADIUS * central_angle;
+    let great_circle_bearing = ((lon2 - lon1).sin() * lat2.cos())
+        .atan2(lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * (lon2 - lon1).cos());
+
+    let vincenty =
+        vincenty::distance_and_bearing_on_ellipsoid(lat1, lon1, lat2, lon2, &Ellipsoid::SPHERE);
+
+    assert_relative_eq!(vincenty.distance, great_circle_distance, epsilon = 1e-6);
+    assert_relative_eq!(vincenty.bearing, great_circle_bearing, epsilon = 1e-12);
+
+    let karney = karney::location_and_bearing_on_ellipsoid(
+        lat1,
+        lon1,
+        great_circle_bearing,
+        great_circle_distance,
+        &Ellipsoid::SPHERE,
+    );
+
+    assert_relative_eq!(karney.lat, lat2, epsilon = 1e-12);
+    assert_relative_eq!(karney.lon, lon2, epsilon = 1e-12);
+}
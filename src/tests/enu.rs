@@ -0,0 +1,116 @@
+use approx::assert_relative_eq;
+
+use crate::reference_frames::ecef::{
+    generate_ecef_to_enu_matrix, generate_ecef_to_ned_matrix, ECEF,
+};
+use crate::reference_frames::enu::ENU;
+use crate::reference_frames::ned::NED;
+use crate::reference_frames::wgs84::WGS84Coord;
+
+#[test]
+fn test_ecef_to_enu() {
+    // A point well above the reference, the rotation keeps the length of the offset
+    let reference_point = WGS84Coord::new_from_degrees(45.9132, 36.7484, 1877.7532);
+    let ecef = ECEF {
+        x: 5507528.9,
+        y: 4556224.1,
+        z: 6012820.8,
+    };
+
+    let enu = ENU::new_from_ecef(&ecef, &reference_point);
+
+    let origin = ECEF::new_from_wgs84(&reference_point);
+    let length = (ecef.x - origin.x)
+        .hypot(ecef.y - origin.y)
+        .hypot(ecef.z - origin.z);
+    assert_relative_eq!(enu.e.hypot(enu.n).hypot(enu.u), length, epsilon = 1e-8);
+    assert!(enu.u > 0.);
+
+    let back = ECEF::new_from_enu(&enu, &reference_point);
+
+    assert_relative_eq!(back.x, ecef.x, epsilon = 1e-8);
+    assert_relative_eq!(back.y, ecef.y, epsilon = 1e-8);
+    assert_relative_eq!(back.z, ecef.z, epsilon = 1e-8);
+}
+
+#[test]
+fn test_enu_axes() {
+    // Due north, due east and straight up from a point in the southern and western hemispheres
+    let reference_point = WGS84Coord::new_from_degrees(-33.8, -70.6, 500.);
+
+    let above = WGS84Coord::new_from_degrees(-33.8, -70.6, 1500.);
+    let enu = ENU::new_from_wgs84(&above, &reference_point);
+    assert_relative_eq!(enu.e, 0., epsilon = 1e-8);
+    assert_relative_eq!(enu.n, 0., epsilon = 1e-8);
+    assert_relative_eq!(enu.u, 1000., epsilon = 1e-8);
+
+    let north = WGS84Coord::new_from_degrees(-33.7999, -70.6, 500.);
+    let enu = ENU::new_from_wgs84(&north, &reference_point);
+    assert!(enu.n > 11.);
+    assert_relative_eq!(enu.e, 0., epsilon = 1e-8);
+    assert!(enu.u.abs() < 1e-4);
+
+    let east = WGS84Coord::new_from_degrees(-33.8, -70.5999, 500.);
+    let enu = ENU::new_from_wgs84(&east, &reference_point);
+    assert!(enu.e > 9.);
+    assert!(enu.n.abs() < 1e-4);
+    assert!(enu.u.abs() < 1e-4);
+}
+
+#[test]
+fn test_enu_ned_consistency() {
+    let reference_point = WGS84Coord::new_from_degrees(50., 10., 120.);
+    let point = WGS84Coord::new_from_degrees(50.3, 9.4, 2400.);
+
+    let enu = ENU::new_from_wgs84(&point, &reference_point);
+    let ned = NED::new_from_wgs84(&point, &reference_point);
+
+    assert_relative_eq!(enu.e, ned.e, epsilon = 1e-9);
+    assert_relative_eq!(enu.n, ned.n, epsilon = 1e-9);
+    assert_relative_eq!(enu.u, -ned.d, epsilon = 1e-9);
+
+    let swapped = NED::new_from_enu(&enu);
+    assert_relative_eq!(swapped.n, ned.n, epsilon = 1e-9);
+    assert_relative_eq!(swapped.e, ned.e, epsilon = 1e-9);
+    assert_relative_eq!(swapped.d, ned.d, epsilon = 1e-9);
+
+    // The two rotations share their rows
+    let to_enu = generate_ecef_to_enu_matrix(&reference_point);
+    let to_ned = generate_ecef_to_ned_matrix(&reference_point);
+    for i in 0..3 {
+        assert_eq!(to_enu[(0, i)], to_ned[(1, i)]);
+        assert_eq!(to_enu[(1, i)], to_ned[(0, i)]);
+        assert_eq!(to_enu[(2, i)], -to_ned[(2, i)]);
+    }
+
+    let from_ned = ECEF::new_from_ned(&ned, &reference_point);
+    let from_enu = ECEF::new_from_enu(&enu, &reference_point);
+    assert_relative_eq!(from_ned.x, from_enu.x, epsilon = 1e-8);
+    assert_relative_eq!(from_ned.y, from_enu.y, epsilon = 1e-8);
+    assert_relative_eq!(from_ned.z, from_enu.z, epsilon = 1e-8);
+}
+
+#[test]
+fn test_enu_wgs84_round_trip() {
+    let references = [
+        WGS84Coord::new_from_degrees(0., 0., 0.),
+        WGS84Coord::new_from_degrees(89.9, 45., 10.),
+        WGS84Coord::new_from_degrees(-62., 179.9, -30.),
+    ];
+    let offsets = [
+        ENU::new_from_raw(1000., -2000., 50.),
+        ENU::new_from_raw(-150e3, 80e3, -3e3),
+        ENU::new_from_raw(0., 0., 400e3),
+    ];
+
+    for reference_point in references.iter() {
+        for enu in offsets.iter() {
+            let wgs84 = WGS84Coord::new_from_enu(enu, reference_point);
+            let back = ENU::new_from_wgs84(&wgs84, reference_point);
+
+            assert_relative_eq!(back.e, enu.e, epsilon = 1e-6);
+            assert_relative_eq!(back.n, enu.n, epsilon = 1e-6);
+            assert_relative_eq!(back.u, enu.u, epsilon = 1e-6);
+        }
+    }
+}
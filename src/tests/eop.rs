@@ -0,0 +1,175 @@
+use approx::assert_relative_eq;
+use chrono::NaiveDate;
+
+use crate::constants::ARCSEC_TO_RAD;
+use crate::time::eop::{
+    get_eop_at_mjd, load_eop_file, parse_finals2000a, reset_eop_table, EOPError, EOPFormat,
+    EOPSource, EOPTable, EarthOrientation,
+};
+use crate::time::epoch::{Epoch, TimeScale};
+
+// Across the leap second at the end of 2016, where UT1 - UTC jumps by a second
+const FINALS: &str = "\
+161231 57753.00 I  0.013490 0.000090  0.279542 0.000090  I-0.4073970 0.0000150  1.2260 0.0101  I     0.123    0.060    -0.221    0.060
+17 1 1 57754.00 I  0.012234 0.000090  0.280431 0.000090  I 0.5924240 0.0000150  1.1830 0.0101  I     0.140    0.060    -0.195    0.060
+17 1 2 57755.00 P  0.011105 0.000090  0.281319 0.000090  P 0.5912410 0.0000150  1.0900 0.0101  P     0.150    0.060    -0.180    0.060
+17 1 3 57756.00
+";
+
+#[test]
+fn test_parse_finals2000a() {
+    let records = parse_finals2000a(FINALS).unwrap();
+    assert_eq!(records.len(), 3);
+
+    let record = &records[1];
+    assert_eq!(record.mjd, 57754.);
+    assert_relative_eq!(record.xp, 0.012234 * ARCSEC_TO_RAD);
+    assert_relative_eq!(record.yp, 0.280431 * ARCSEC_TO_RAD);
+    assert_eq!(record.delta_ut1, 0.592424);
+    assert_relative_eq!(record.lod, 1.183e-3);
+    assert_relative_eq!(record.dx, 0.14e-3 * ARCSEC_TO_RAD);
+    assert_relative_eq!(record.dy, -0.195e-3 * ARCSEC_TO_RAD);
+    assert!(!record.predicted);
+    assert!(records[2].predicted);
+
+    let broken = FINALS.replace("0.280431", "0.28x431");
+    assert_eq!(
+        parse_finals2000a(&broken).unwrap_err(),
+        EOPError::InvalidLine(2, broken.lines().nth(1).unwrap().to_string())
+    );
+}
+
+#[test]
+fn test_interpolation() {
+    let table = EOPTable::new_from_str(FINALS, EOPFormat::Finals2000A).unwrap();
+    assert_eq!(table.get_coverage(), (57753., 57755.));
+
+    // UT1 - TAI is interpolated so that the leap second doesn't smear across the day
+    let eop = table.get(57753.5);
+    assert_relative_eq!(eop.delta_ut1, -0.4074865, epsilon = 1e-9);
+    assert_relative_eq!(eop.xp, 0.012862 * ARCSEC_TO_RAD, epsilon = 1e-15);
+    assert_relative_eq!(eop.lod, 1.2045e-3, epsilon = 1e-12);
+    assert_eq!(eop.source, EOPSource::Observed);
+
+    let eop = table.get(57754.25);
+    assert_relative_eq!(eop.delta_ut1, 0.59212825, epsilon = 1e-9);
+    assert_eq!(eop.source, EOPSource::Predicted);
+
+    let eop = table.get(57755.);
+    assert_relative_eq!(eop.delta_ut1, 0.591241, epsilon = 1e-12);
+
+    // Outside the table
+    assert_eq!(table.get(57756.), EarthOrientation::new_from_model(57756.));
+    assert_eq!(table.get(57756.).source, EOPSource::Model);
+}
+
+#[test]
+fn test_model() {
+    let model = EarthOrientation::new_from_model(57226.);
+    assert_relative_eq!(model.xp, 0.1824 * ARCSEC_TO_RAD, epsilon = 1e-15);
+    assert_relative_eq!(model.yp, 0.4246 * ARCSEC_TO_RAD, epsilon = 1e-15);
+    assert_eq!(model.delta_ut1, 0.);
+}
+
+#[test]
+fn test_parse_bulletin_a() {
+    let bulletin = "\
+        IERS Rapid Service
+        MJD      x    error     y    error   UT1-UTC   error
+                  \"      \"       \"      \"     s        s
+   24  1 12  60321 0.05988 .00009 0.30541 .00009 -0.006893 0.000019
+   24  1 13  60322 0.06148 .00009 0.30446 .00009 -0.007208 0.000017
+
+         PREDICTIONS:
+                    x =  0.1359 + 0.0765 cos A - 0.0021 sin A
+               MJD      x(arcsec)   y(arcsec)   UT1-UTC(sec)
+         2024  1 14  60323       0.0630      0.3035     -0.00751
+";
+    let table = EOPTable::new_from_str(bulletin, EOPFormat::BulletinA).unwrap();
+    let records = table.get_records();
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[0].mjd, 60321.);
+    assert_relative_eq!(records[0].yp, 0.30541 * ARCSEC_TO_RAD);
+    assert_eq!(records[1].delta_ut1, -0.007208);
+    assert!(!records[1].predicted);
+    assert_eq!(records[2].delta_ut1, -0.00751);
+    assert!(records[2].predicted);
+}
+
+#[test]
+fn test_parse_c04() {
+    let c04_14 = "\
+  EARTH ORIENTATION PARAMETERS (IERS) C04
+  Date      MJD      x          y        UT1-UTC       LOD         dX        dY
+1962   1   1  37665  -0.012700   0.213000   0.0326338   0.0017230   0.000000   0.000000   0.030000   0.030000  0.0020000  0.0014000    0.004774    0.002000
+1962   1   2  37666  -0.015900   0.214100   0.0320547   0.0016690   0.000000   0.000000   0.030000   0.030000  0.0020000  0.0014000    0.004774    0.002000
+";
+    let records = EOPTable::new_from_str(c04_14, EOPFormat::C04).unwrap();
+    let records = records.get_records();
+    assert_eq!(records.len(), 2);
+    assert_relative_eq!(records[1].xp, -0.0159 * ARCSEC_TO_RAD);
+    assert_eq!(records[1].lod, 0.001669);
+
+    let c04_20 = "\
+# YR  MM  DD  HH       MJD        x(\")        y(\")  UT1-UTC(s)       dX(\")      dY(\")       xrt(\")      yrt(\")      LOD(s)        x Er        y Er  UT1-UTC Er      dX Er       dY Er       xrt Er      yrt Er      LOD Er
+2017  01  01  00  57754.00    0.012231    0.280437   0.5924198    0.000138   -0.000196   -0.001108    0.000816   0.0011821    0.000027    0.000029   0.0000091    0.000059    0.000060    0.000053    0.000050   0.0000103
+";
+    let records = EOPTable::new_from_str(c04_20, EOPFormat::C04).unwrap();
+    let record = records.get_records()[0];
+    assert_eq!(record.mjd, 57754.);
+    assert_eq!(record.delta_ut1, 0.5924198);
+    assert_eq!(record.lod, 0.0011821);
+    assert_relative_eq!(record.dy, -0.000196 * ARCSEC_TO_RAD);
+
+    assert_eq!(
+        EOPTable::new_from_str("1962 1 1 37665 0.1 0.2\n", EOPFormat::C04).unwrap_err(),
+        EOPError::InvalidLine(1, "1962 1 1 37665 0.1 0.2".to_string())
+    );
+    assert_eq!(
+        EOPTable::new_from_str("no data\n", EOPFormat::C04).unwrap_err(),
+        EOPError::Empty
+    );
+}
+
+#[test]
+fn test_load_eop_file() {
+    // A table only covering 1962, which no other test uses, as the table is shared
+    let c04 = "\
+1962   1   1  37665  -0.012700   0.213000   0.0326338   0.0017230   0.000000   0.000000   0.030000   0.030000  0.0020000  0.0014000    0.004774    0.002000
+1962   1   2  37666  -0.015900   0.214100   0.0320547   0.0016690   0.000000   0.000000   0.030000   0.030000  0.0020000  0.0014000    0.004774    0.002000
+";
+    let path = std::env::temp_dir().join("crate_test_eopc04.dat");
+    std::fs::write(&path, c04).unwrap();
+
+    assert_eq!(load_eop_file(&path, EOPFormat::C04).unwrap(), 2);
+    assert_relative_eq!(
+        get_eop_at_mjd(37665.5).delta_ut1,
+        0.03234425,
+        epsilon = 1e-9
+    );
+
+    // Epochs pick up UT1 - UTC from the table unless given it
+    let utc = NaiveDate::from_ymd_opt(1962, 1, 1)
+        .unwrap()
+        .and_hms_opt(12, 0, 0)
+        .unwrap();
+    let epoch = Epoch::new_from_utc(&utc);
+    assert_relative_eq!(epoch.get_delta_ut1(), 0.03234425, epsilon = 1e-9);
+    let ut1 = epoch.get_seconds_since_j2000(TimeScale::UT1);
+    let utc_seconds = epoch.get_seconds_since_j2000(TimeScale::UTC);
+    assert_relative_eq!(ut1 - utc_seconds, 0.03234425, epsilon = 1e-6);
+
+    let mut given = epoch;
+    given.set_delta_ut1(0.1);
+    assert_eq!(given.get_delta_ut1(), 0.1);
+
+    reset_eop_table();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(get_eop_at_mjd(37665.5).source, EOPSource::Model);
+    assert_eq!(epoch.get_delta_ut1(), 0.);
+
+    assert!(matches!(
+        load_eop_file("/this/file/does/not/exist", EOPFormat::Finals2000A),
+        Err(EOPError::Io(_))
+    ));
+}
@@ -0,0 +1,180 @@
+use approx::assert_relative_eq;
+use chrono::{NaiveDate, NaiveDateTime};
+
+use crate::time::epoch::{Epoch, TimeScale, TAI_MINUS_GPS, TT_MINUS_TAI};
+use crate::time::leap_seconds::{
+    get_leap_seconds, load_leap_second_file, parse_leap_second_file, reset_leap_seconds,
+    tai_minus_utc, LeapSecondError,
+};
+
+fn datetime(y: i32, m: u32, d: u32, h: u32, min: u32, s: u32, nano: u32) -> NaiveDateTime {
+    return NaiveDate::from_ymd_opt(y, m, d)
+        .unwrap()
+        .and_hms_nano_opt(h, min, s, nano)
+        .unwrap();
+}
+
+#[test]
+fn test_scale_offsets() {
+    let epoch = Epoch::new_from_utc(&datetime(2017, 6, 1, 0, 0, 0, 0));
+    let tai = epoch.get_seconds_since_j2000(TimeScale::TAI);
+    let utc = epoch.get_seconds_since_j2000(TimeScale::UTC);
+    let tt = epoch.get_seconds_since_j2000(TimeScale::TT);
+    let gps = epoch.get_seconds_since_j2000(TimeScale::GPS);
+    let tdb = epoch.get_seconds_since_j2000(TimeScale::TDB);
+
+    assert_relative_eq!(tai - utc, 37., epsilon = 1e-6);
+    assert_relative_eq!(tt - tai, TT_MINUS_TAI, epsilon = 1e-6);
+    assert_relative_eq!(tai - gps, TAI_MINUS_GPS, epsilon = 1e-6);
+    assert!((tdb - tt).abs() < 1.7e-3);
+
+    // At the J2000 epoch TT is 2000-01-01 12:00:00 exactly
+    let j2000 = Epoch::new_from_datetime(&datetime(2000, 1, 1, 12, 0, 0, 0), TimeScale::TT);
+    assert_relative_eq!(j2000.get_seconds_since_j2000(TimeScale::TT), 0.);
+    assert_eq!(
+        j2000.get_datetime(TimeScale::UTC),
+        datetime(2000, 1, 1, 11, 58, 55, 816_000_000)
+    );
+
+    // Round trips through every scale
+    for scale in [
+        TimeScale::UTC,
+        TimeScale::TAI,
+        TimeScale::TT,
+        TimeScale::GPS,
+        TimeScale::TDB,
+    ] {
+        let dt = datetime(2021, 3, 4, 5, 6, 7, 890_000_000);
+        let epoch = Epoch::new_from_datetime(&dt, scale);
+        assert_eq!(epoch.get_datetime(scale), dt);
+        let seconds = epoch.get_seconds_since_j2000(scale);
+        assert_relative_eq!(
+            Epoch::new_from_seconds(seconds, scale).seconds_since(&epoch),
+            0.,
+            epsilon = 1e-6
+        );
+    }
+}
+
+#[test]
+fn test_ut1() {
+    let utc = datetime(2020, 5, 6, 7, 8, 9, 0);
+    let mut epoch = Epoch::new_from_utc(&utc);
+    epoch.set_delta_ut1(-0.2);
+    assert_eq!(
+        epoch.get_datetime(TimeScale::UT1),
+        datetime(2020, 5, 6, 7, 8, 8, 800_000_000)
+    );
+
+    let from_ut1 = Epoch::new_from_ut1(&datetime(2020, 5, 6, 7, 8, 8, 800_000_000), -0.2);
+    assert_eq!(from_ut1.get_datetime(TimeScale::UTC), utc);
+    assert_eq!(from_ut1.get_delta_ut1(), -0.2);
+}
+
+#[test]
+fn test_leap_second() {
+    // 2016-12-31 ended with 23:59:60, TAI - UTC went from 36 to 37 s
+    let before = Epoch::new_from_utc(&datetime(2016, 12, 31, 23, 59, 59, 0));
+    let leap = Epoch::new_from_utc(&datetime(2016, 12, 31, 23, 59, 59, 1_500_000_000));
+    let after = Epoch::new_from_utc(&datetime(2017, 1, 1, 0, 0, 0, 0));
+
+    assert_relative_eq!(after.seconds_since(&before), 2., epsilon = 1e-9);
+    assert_relative_eq!(leap.seconds_since(&before), 1.5, epsilon = 1e-9);
+    assert_eq!(
+        leap.get_datetime(TimeScale::UTC),
+        datetime(2016, 12, 31, 23, 59, 59, 1_500_000_000)
+    );
+    assert_eq!(
+        after.get_datetime(TimeScale::UTC),
+        datetime(2017, 1, 1, 0, 0, 0, 0)
+    );
+    assert_eq!(
+        before.add_seconds(1.).get_datetime(TimeScale::UTC),
+        datetime(2016, 12, 31, 23, 59, 59, 1_000_000_000)
+    );
+
+    let utc = |e: &Epoch| e.get_seconds_since_j2000(TimeScale::UTC);
+    let tai = |e: &Epoch| e.get_seconds_since_j2000(TimeScale::TAI);
+    assert_relative_eq!(tai(&before) - utc(&before), 36., epsilon = 1e-6);
+    assert_relative_eq!(tai(&after) - utc(&after), 37., epsilon = 1e-6);
+
+    assert_eq!(tai_minus_utc(57753.), 36.);
+    assert_eq!(tai_minus_utc(57754.), 37.);
+}
+
+#[test]
+fn test_gps_week() {
+    // GPS was 18 s ahead of UTC from 2017, and 2017-01-01 was the Sunday starting week 1930
+    let epoch = Epoch::new_from_utc(&datetime(2017, 1, 1, 0, 0, 0, 0));
+    let (week, seconds) = epoch.get_gps_week();
+    assert_eq!(week, 1930);
+    assert_relative_eq!(seconds, 18., epsilon = 1e-6);
+
+    let from_week = Epoch::new_from_gps_week(1930, 18.);
+    assert_relative_eq!(from_week.seconds_since(&epoch), 0., epsilon = 1e-6);
+
+    let start = Epoch::new_from_gps_week(0, 0.);
+    assert_eq!(
+        start.get_datetime(TimeScale::GPS),
+        datetime(1980, 1, 6, 0, 0, 0, 0)
+    );
+    assert_eq!(
+        start.get_datetime(TimeScale::UTC),
+        datetime(1980, 1, 6, 0, 0, 0, 0)
+    );
+}
+
+#[test]
+fn test_from_naive_datetime() {
+    let dt = datetime(2023, 11, 14, 22, 13, 20, 0);
+    let epoch: Epoch = (&dt).into();
+    assert_eq!(epoch, Epoch::new_from_utc(&dt));
+    assert_eq!(Epoch::from(dt), epoch);
+}
+
+#[test]
+fn test_parse_leap_second_file() {
+    let contents = "#  File expires on 28 June 2025\n\
+                    #\n\
+                    41317.0    1  1 1972       10\n\
+                    41499.0    1  7 1972       11\n\
+                    \n\
+                    57754.0    1  1 2017       37\n";
+    let table = parse_leap_second_file(contents).unwrap();
+    assert_eq!(table, vec![(41317., 10.), (41499., 11.), (57754., 37.)]);
+
+    assert_eq!(
+        parse_leap_second_file("41317.0 1 1 1972\n").unwrap_err(),
+        LeapSecondError::InvalidLine(1, "41317.0 1 1 1972".to_string())
+    );
+    assert_eq!(
+        parse_leap_second_file("41499.0 1 7 1972 11\n41317.0 1 1 1972 10\n").unwrap_err(),
+        LeapSecondError::NotIncreasing(2)
+    );
+    assert_eq!(
+        parse_leap_second_file("# nothing\n").unwrap_err(),
+        LeapSecondError::Empty
+    );
+}
+
+#[test]
+fn test_load_leap_second_file() {
+    // Loads the same table as the embedded one, other tests may be running at the same time
+    let embedded = get_leap_seconds();
+    let mut contents = String::from("# MJD Date TAI-UTC\n");
+    for (mjd, offset) in &embedded {
+        contents += &format!("{:.1} 1 1 2000 {}\n", mjd, offset);
+    }
+    let path = std::env::temp_dir().join("crate_test_leap_seconds.dat");
+    std::fs::write(&path, contents).unwrap();
+
+    assert_eq!(load_leap_second_file(&path).unwrap(), embedded.len());
+    assert_eq!(get_leap_seconds(), embedded);
+    reset_leap_seconds();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(matches!(
+        load_leap_second_file("/this/file/does/not/exist"),
+        Err(LeapSecondError::Io(_))
+    ));
+}
@@ -0,0 +1,166 @@
+use approx::assert_relative_eq;
+use chrono::NaiveDate;
+
+use crate::constants::ARCSEC_TO_RAD;
+use crate::reference_frames::ecef::{ECEFVel, ECEF};
+use crate::reference_frames::fk5::{Nutation, FK5, TOD};
+use crate::reference_frames::gcrf::GCRF;
+use crate::reference_frames::pef::{PEFVel, PEF};
+use crate::reference_frames::transform::{FrameGraph, FrameId, InertialReduction};
+use crate::time::epoch::{Epoch, TimeScale};
+use crate::time::julian_date::JulianDate;
+use crate::time::sidereal::{gast_iau82, gmst_iau82};
+
+fn vallado_epoch() -> Epoch {
+    // Vallado, Fundamentals of Astrodynamics and Applications, example 3-15
+    let utc = NaiveDate::from_ymd_opt(2004, 4, 6)
+        .unwrap()
+        .and_hms_nano_opt(7, 51, 28, 386_009_000)
+        .unwrap();
+    let mut epoch = Epoch::new_from_utc(&utc);
+    epoch.set_delta_ut1(-0.4399619);
+    return epoch;
+}
+
+fn vallado_pef() -> (PEF, PEFVel) {
+    let pef = PEF {
+        x: -1033.4750313,
+        y: 7901.3055856,
+        z: 6380.3445328,
+    };
+    let pef_vel = PEFVel {
+        x_vel: -3.225632747,
+        y_vel: -2.872442511,
+        z_vel: 5.531931288,
+    };
+    return (pef, pef_vel);
+}
+
+#[test]
+fn test_nutation_and_sidereal_time() {
+    let epoch = vallado_epoch();
+    let nutation = Nutation::new_from_epoch(epoch);
+
+    assert_relative_eq!(nutation.dpsi.to_degrees(), -0.0034108, epsilon = 1e-7);
+    assert_relative_eq!(nutation.deps.to_degrees(), 0.0020316, epsilon = 1e-7);
+    assert_relative_eq!(
+        nutation.mean_obliquity.to_degrees(),
+        23.4387368,
+        epsilon = 1e-7
+    );
+    assert_relative_eq!(
+        nutation.get_true_obliquity().to_degrees(),
+        23.4407685,
+        epsilon = 1e-7
+    );
+    let ut1 = JulianDate::new_from_epoch(&epoch, TimeScale::UT1);
+    assert_relative_eq!(gmst_iau82(&ut1).to_degrees(), 312.8098943, epsilon = 1e-6);
+    assert_relative_eq!(
+        gast_iau82(&ut1, &nutation).to_degrees(),
+        312.8067654,
+        epsilon = 1e-6
+    );
+}
+
+#[test]
+fn test_pef_to_gcrf() {
+    let epoch = vallado_epoch();
+    let (pef, pef_vel) = vallado_pef();
+
+    // With the IERS corrections to the 1980 nutation the result is in the GCRF
+    let mut nutation = Nutation::new_from_epoch(epoch);
+    nutation.add_corrections(-0.052195 * ARCSEC_TO_RAD, -0.003875 * ARCSEC_TO_RAD);
+    let fk5 = FK5::new_from_nutation(epoch, &nutation);
+
+    let gcrf = fk5.gcrf_from_pef(&pef);
+    assert_relative_eq!(gcrf.x, 5102.508958, epsilon = 1e-5);
+    assert_relative_eq!(gcrf.y, 6123.011401, epsilon = 1e-5);
+    assert_relative_eq!(gcrf.z, 6378.136928, epsilon = 1e-5);
+
+    let gcrf_vel = fk5.gcrf_vel_from_pef_vel(&pef, &pef_vel);
+    assert_relative_eq!(gcrf_vel.x_vel, -4.743220157, epsilon = 1e-7);
+    assert_relative_eq!(gcrf_vel.y_vel, 0.790536497, epsilon = 1e-7);
+    assert_relative_eq!(gcrf_vel.z_vel, 5.533755727, epsilon = 1e-7);
+
+    // And back again
+    let back = fk5.pef_from_gcrf(&gcrf);
+    assert_relative_eq!(back.x, pef.x, epsilon = 1e-8);
+    assert_relative_eq!(back.y, pef.y, epsilon = 1e-8);
+    assert_relative_eq!(back.z, pef.z, epsilon = 1e-8);
+    let back_vel = fk5.pef_vel_from_gcrf_vel(&gcrf, &gcrf_vel);
+    assert_relative_eq!(back_vel.x_vel, pef_vel.x_vel, epsilon = 1e-11);
+    assert_relative_eq!(back_vel.y_vel, pef_vel.y_vel, epsilon = 1e-11);
+    assert_relative_eq!(back_vel.z_vel, pef_vel.z_vel, epsilon = 1e-11);
+
+    // Without them it's FK5 J2000
+    let j2000 = FK5::new(epoch).gcrf_from_pef(&pef);
+    assert_relative_eq!(j2000.x, 5102.5096, epsilon = 1e-4);
+    assert_relative_eq!(j2000.y, 6123.01152, epsilon = 1e-4);
+    assert_relative_eq!(j2000.z, 6378.1363, epsilon = 1e-4);
+}
+
+#[test]
+fn test_ecef_round_trip() {
+    let epoch = vallado_epoch();
+    let ecef = ECEF {
+        x: -1033479.383,
+        y: 7901295.2754,
+        z: 6380356.5958,
+    };
+    let ecef_vel = ECEFVel {
+        x_vel: -3225.636520,
+        y_vel: -2872.451450,
+        z_vel: 5531.924446,
+    };
+
+    let fk5 = FK5::new(epoch);
+    let gcrf = fk5.gcrf_from_ecef(&ecef);
+    let gcrf_vel = fk5.gcrf_vel_from_ecef_vel(&ecef, &ecef_vel);
+    let back = fk5.ecef_from_gcrf(&gcrf);
+    let back_vel = fk5.ecef_vel_from_gcrf_vel(&gcrf, &gcrf_vel);
+
+    assert_relative_eq!(back.x, ecef.x, epsilon = 1e-6);
+    assert_relative_eq!(back.y, ecef.y, epsilon = 1e-6);
+    assert_relative_eq!(back.z, ecef.z, epsilon = 1e-6);
+    assert_relative_eq!(back_vel.x_vel, ecef_vel.x_vel, epsilon = 1e-9);
+    assert_relative_eq!(back_vel.y_vel, ecef_vel.y_vel, epsilon = 1e-9);
+    assert_relative_eq!(back_vel.z_vel, ecef_vel.z_vel, epsilon = 1e-9);
+
+    // The inertial speed is the Earth fixed speed plus about 0.5 km/s of rotation at this point
+    let speed = |x: f64, y: f64, z: f64| (x * x + y * y + z * z).sqrt();
+    assert!(
+        speed(gcrf_vel.x_vel, gcrf_vel.y_vel, gcrf_vel.z_vel)
+            > speed(ecef_vel.x_vel, ecef_vel.y_vel, ecef_vel.z_vel)
+    );
+}
+
+#[test]
+fn test_frame_graph() {
+    let epoch = vallado_epoch();
+    let (pef, _) = vallado_pef();
+
+    let mut graph = FrameGraph::new_from_time(epoch);
+    graph.set_reduction(InertialReduction::FK5);
+    let gcrf: GCRF = graph.convert(&pef).unwrap();
+    let expected = FK5::new(epoch).gcrf_from_pef(&pef);
+    assert_relative_eq!(gcrf.x, expected.x, epsilon = 1e-8);
+    assert_relative_eq!(gcrf.y, expected.y, epsilon = 1e-8);
+    assert_relative_eq!(gcrf.z, expected.z, epsilon = 1e-8);
+
+    let tod: TOD = graph.convert(&gcrf).unwrap();
+    let expected = TOD::new_from_pef(&pef, epoch);
+    assert_relative_eq!(tod.x, expected.x, epsilon = 1e-8);
+    assert_relative_eq!(tod.y, expected.y, epsilon = 1e-8);
+    assert_relative_eq!(tod.z, expected.z, epsilon = 1e-8);
+
+    let transform = graph.transform(FrameId::GCRF, FrameId::ECEF).unwrap();
+    assert_eq!(transform.get_from(), FrameId::GCRF);
+    assert_eq!(transform.get_to(), FrameId::ECEF);
+
+    // Starting from ECEF rather than PEF takes the same reduction
+    let ecef = ECEF::new_from_pef(&pef, epoch);
+    let from_ecef: GCRF = graph.convert(&ecef).unwrap();
+    assert_relative_eq!(from_ecef.x, gcrf.x, epsilon = 1e-8);
+    assert_relative_eq!(from_ecef.y, gcrf.y, epsilon = 1e-8);
+    assert_relative_eq!(from_ecef.z, gcrf.z, epsilon = 1e-8);
+}
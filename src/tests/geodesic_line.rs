@@ -0,0 +1,128 @@
+// Reference values are quoted verbatim from GeographicLib
+#![allow(clippy::excessive_precision)]
+
+use approx::assert_relative_eq;
+
+use crate::ellipsoid::Ellipsoid;
+use crate::geodesics::geodesic_line::GeodesicLine;
+use crate::geodesics::karney::{self, Geodesic};
+
+#[test]
+fn test_direct_geod_test() {
+    // Lines from GeographicLib's GeodTest.dat
+    let geodesic = Geodesic::new(&Ellipsoid::WGS84);
+
+    let line = GeodesicLine::new(
+        &geodesic,
+        35.60777_f64.to_radians(),
+        -139.44815_f64.to_radians(),
+        111.098748429560326_f64.to_radians(),
+    );
+    let position = line.position(8935244.5604818305);
+
+    assert_relative_eq!(position.lat.to_degrees(), -11.17491, epsilon = 1e-12);
+    assert_relative_eq!(position.lon.to_degrees(), -69.95921, epsilon = 1e-12);
+    assert_relative_eq!(
+        position.azimuth.to_degrees(),
+        129.289270889708762,
+        epsilon = 1e-12
+    );
+    assert_relative_eq!(
+        position.arc_length.to_degrees(),
+        80.50729714281974,
+        epsilon = 1e-12
+    );
+    assert_relative_eq!(position.reduced_length, 6273170.2055303837, epsilon = 1e-8);
+    assert_relative_eq!(
+        position.geodesic_scale12,
+        0.16606318447386067,
+        epsilon = 1e-14
+    );
+    assert_relative_eq!(
+        position.geodesic_scale21,
+        0.16479116945612937,
+        epsilon = 1e-14
+    );
+    assert_relative_eq!(position.area, 12841384694976.432, epsilon = 0.1);
+
+    // Longitudes come back in [-180, 180] (GeodTest quotes 197.18234)
+    let position = geodesic.direct(
+        55.52454_f64.to_radians(),
+        106.05087_f64.to_radians(),
+        22.020059880982801_f64.to_radians(),
+        4105086.1713924406,
+    );
+
+    assert_relative_eq!(position.lat.to_degrees(), 77.03196, epsilon = 1e-12);
+    assert_relative_eq!(position.lon.to_degrees(), 197.18234 - 360., epsilon = 1e-12);
+    assert_relative_eq!(
+        position.azimuth.to_degrees(),
+        109.112041110671519,
+        epsilon = 1e-12
+    );
+}
+
+#[test]
+fn test_arc_position_matches_distance() {
+    let geodesic = Geodesic::new(&Ellipsoid::WGS84);
+    let line = GeodesicLine::new(&geodesic, 0.7, -1.2, 2.1);
+
+    let by_distance = line.position(6_000_000.);
+    let by_arc = line.arc_position(by_distance.arc_length);
+
+    assert_relative_eq!(by_arc.distance, 6_000_000., epsilon = 1e-8);
+    assert_relative_eq!(by_arc.lat, by_distance.lat, epsilon = 1e-14);
+    assert_relative_eq!(by_arc.lon, by_distance.lon, epsilon = 1e-14);
+    assert_relative_eq!(by_arc.azimuth, by_distance.azimuth, epsilon = 1e-14);
+}
+
+#[test]
+fn test_line_from_endpoints() {
+    // JFK to Singapore Changi, which passes close to the north pole
+    let geodesic = Geodesic::new(&Ellipsoid::WGS84);
+    let (lat1, lon1) = (40.64_f64.to_radians(), -73.78_f64.to_radians());
+    let (lat2, lon2) = (1.36_f64.to_radians(), 103.99_f64.to_radians());
+
+    let line = GeodesicLine::new_from_endpoints(&geodesic, lat1, lon1, lat2, lon2);
+    let inverse = karney::inverse(lat1, lon1, lat2, lon2);
+
+    assert_relative_eq!(line.get_length().unwrap(), inverse.distance, epsilon = 1e-8);
+    assert_relative_eq!(line.get_azimuth1(), inverse.azimuth1, epsilon = 1e-14);
+
+    let end = line.position(inverse.distance);
+    assert_relative_eq!(end.lat, lat2, epsilon = 1e-13);
+    assert_relative_eq!(end.lon, lon2, epsilon = 1e-13);
+    assert_relative_eq!(end.azimuth, inverse.azimuth2, epsilon = 1e-13);
+
+    // Lines built from a start and azimuth have no natural length
+    assert!(GeodesicLine::new(&geodesic, lat1, lon1, 0.3)
+        .get_length()
+        .is_none());
+}
+
+#[test]
+fn test_equally_spaced_points() {
+    let geodesic = Geodesic::new(&Ellipsoid::WGS84);
+    let (lat1, lon1) = (51.47_f64.to_radians(), -0.45_f64.to_radians());
+    let (lat2, lon2) = (33.94_f64.to_radians(), -118.41_f64.to_radians());
+
+    let line = GeodesicLine::new_from_endpoints(&geodesic, lat1, lon1, lat2, lon2);
+    let length = line.get_length().unwrap();
+    let points = line.equally_spaced(length, 11);
+
+    assert_eq!(points.len(), 11);
+    assert_eq!(points[0].distance, 0.);
+    assert_relative_eq!(points[0].lat, lat1, epsilon = 1e-14);
+    assert_relative_eq!(points[10].lat, lat2, epsilon = 1e-13);
+    assert_relative_eq!(points[10].lon, lon2, epsilon = 1e-13);
+
+    // Consecutive points are a tenth of the route apart
+    for pair in points.windows(2) {
+        let leg = karney::inverse(pair[0].lat, pair[0].lon, pair[1].lat, pair[1].lon);
+        assert_relative_eq!(leg.distance, length / 10., epsilon = 1e-6);
+        assert_relative_eq!(leg.azimuth1, pair[0].azimuth, epsilon = 1e-12);
+    }
+
+    assert!(line.equally_spaced(length, 0).is_empty());
+    assert_eq!(line.equally_spaced(length, 1).len(), 1);
+}
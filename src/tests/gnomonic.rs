@@ -0,0 +1,56 @@
+use approx::assert_relative_eq;
+
+use crate::constants::EARTH_MEAN_RADIUS;
+use crate::ellipsoid::Ellipsoid;
+use crate::geodesics::karney;
+use crate::projections::gnomonic::Gnomonic;
+
+#[test]
+fn test_round_trip() {
+    let gnomonic = Gnomonic::new(&Ellipsoid::WGS84);
+    let (lat0, lon0) = (48_f64.to_radians(), 2_f64.to_radians());
+
+    for (lat, lon) in [
+        (52_f64, 13_f64),
+        (40., -3.),
+        (60., 25.),
+        (-10., 30.),
+        (48., 2.),
+    ] {
+        let (lat, lon) = (lat.to_radians(), lon.to_radians());
+        let point = gnomonic.forward(lat0, lon0, lat, lon);
+        let back = gnomonic.reverse(lat0, lon0, point.x, point.y);
+
+        assert_relative_eq!(back.lat, lat, epsilon = 1e-14);
+        assert_relative_eq!(back.lon, lon, epsilon = 1e-14);
+        assert_relative_eq!(back.azimuth, point.azimuth, epsilon = 1e-12);
+        assert_relative_eq!(
+            back.reciprocal_scale,
+            point.reciprocal_scale,
+            epsilon = 1e-12
+        );
+    }
+
+    // Over the horizon
+    let far = gnomonic.forward(lat0, lon0, -45_f64.to_radians(), -170_f64.to_radians());
+
+    assert!(far.x.is_nan() && far.y.is_nan());
+}
+
+#[test]
+fn test_sphere() {
+    // On a sphere the gnomonic radius is R tan(sigma) and the azimuthal scale is sec^2(sigma)
+    let gnomonic = Gnomonic::new(&Ellipsoid::SPHERE);
+    let (lat, lon) = (0.4, 0.9);
+    let point = gnomonic.forward(0.1, 0.2, lat, lon);
+    let inverse = karney::inverse_on_ellipsoid(0.1, 0.2, lat, lon, &Ellipsoid::SPHERE);
+    let sigma = inverse.distance / EARTH_MEAN_RADIUS;
+
+    assert_relative_eq!(
+        point.x.hypot(point.y),
+        EARTH_MEAN_RADIUS * sigma.tan(),
+        epsilon = 1e-6
+    );
+    assert_relative_eq!(point.x.atan2(point.y), inverse.azimuth1, epsilon = 1e-14);
+    assert_relative_eq!(point.reciprocal_scale, sigma.cos(), epsilon = 1e-14);
+}
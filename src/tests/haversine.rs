@@ -0,0 +1,42 @@
+use std::f64::consts::PI;
+
+use approx::assert_relative_eq;
+
+use crate::constants::EARTH_MEAN_RADIUS;
+use crate::geodesics::haversine;
+
+#[test]
+fn test_lands_end_to_john_o_groats() {
+    // Worked example from Chris Veness' great circle calculator
+    let result = haversine::distance_and_bearing(
+        (50. + 3. / 60. + 59. / 3600_f64).to_radians(),
+        -(5. + 42. / 60. + 53. / 3600_f64).to_radians(),
+        (58. + 38. / 60. + 38. / 3600_f64).to_radians(),
+        -(3. + 4. / 60. + 12. / 3600_f64).to_radians(),
+    );
+
+    assert_relative_eq!(result.distance, 968.9e3, epsilon = 50.);
+    assert_relative_eq!(
+        result.bearing,
+        (9. + 7. / 60. + 11. / 3600_f64).to_radians(),
+        epsilon = 1e-5
+    );
+}
+
+#[test]
+fn test_long_great_circles() {
+    // Along the equator the central angle is just the longitude difference, right up to antipodal points
+    for lon in [90_f64, 150., 179.9] {
+        let result = haversine::distance_and_bearing(0., 0., 0., lon.to_radians());
+
+        assert_relative_eq!(
+            result.distance,
+            EARTH_MEAN_RADIUS * lon.to_radians(),
+            epsilon = 1e-6
+        );
+    }
+
+    let result = haversine::distance_and_bearing(0., 0., 0., PI);
+
+    assert_relative_eq!(result.distance, EARTH_MEAN_RADIUS * PI, epsilon = 1e-6);
+}
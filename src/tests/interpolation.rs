@@ -0,0 +1,80 @@
+use approx::assert_relative_eq;
+
+use crate::geodesics::{interpolation, karney};
+use crate::reference_frames::wgs84::WGS84Coord;
+
+fn distance(a: &WGS84Coord, b: &WGS84Coord) -> f64 {
+    return karney::inverse(
+        a.get_lat_radians(),
+        a.get_lon_radians(),
+        b.get_lat_radians(),
+        b.get_lon_radians(),
+    )
+    .distance;
+}
+
+#[test]
+fn test_midpoint() {
+    // JFK to Singapore passes close to the north pole, far from the mean of the coordinates
+    let start = WGS84Coord::new_from_degrees(40.64, -73.78, 0.);
+    let end = WGS84Coord::new_from_degrees(1.36, 103.99, 100.);
+
+    let mid = interpolation::midpoint(&start, &end);
+    let total = distance(&start, &end);
+
+    assert_relative_eq!(distance(&start, &mid), total / 2., epsilon = 1e-8);
+    assert_relative_eq!(distance(&mid, &end), total / 2., epsilon = 1e-8);
+    assert!(mid.get_lat_degrees() > 70.);
+    assert_relative_eq!(mid.get_altitude(), 50., epsilon = 1e-12);
+}
+
+#[test]
+fn test_interpolate() {
+    let start = WGS84Coord::new_from_degrees(-33.95, 151.18, 10.);
+    let end = WGS84Coord::new_from_degrees(-37.01, 174.79, 20.);
+    let total = distance(&start, &end);
+
+    let first = interpolation::interpolate(&start, &end, 0.);
+
+    assert_relative_eq!(
+        first.get_lat_radians(),
+        start.get_lat_radians(),
+        epsilon = 1e-15
+    );
+    assert_relative_eq!(
+        first.get_lon_radians(),
+        start.get_lon_radians(),
+        epsilon = 1e-15
+    );
+
+    let last = interpolation::interpolate(&start, &end, 1.);
+
+    assert_relative_eq!(
+        last.get_lat_radians(),
+        end.get_lat_radians(),
+        epsilon = 1e-14
+    );
+    assert_relative_eq!(
+        last.get_lon_radians(),
+        end.get_lon_radians(),
+        epsilon = 1e-14
+    );
+
+    for fraction in [0.1, 0.25, 0.9] {
+        let point = interpolation::interpolate(&start, &end, fraction);
+
+        assert_relative_eq!(distance(&start, &point), fraction * total, epsilon = 1e-8);
+        assert_relative_eq!(
+            distance(&point, &end),
+            (1. - fraction) * total,
+            epsilon = 1e-8
+        );
+        assert_relative_eq!(point.get_altitude(), 10. + 10. * fraction, epsilon = 1e-12);
+    }
+
+    // Past the end the geodesic carries on
+    let beyond = interpolation::interpolate(&start, &end, 1.5);
+
+    assert_relative_eq!(distance(&start, &beyond), 1.5 * total, epsilon = 1e-8);
+    assert_relative_eq!(distance(&end, &beyond), 0.5 * total, epsilon = 1e-8);
+}
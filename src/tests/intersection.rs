@@ -0,0 +1,120 @@
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use approx::assert_relative_eq;
+
+use crate::ellipsoid::Ellipsoid;
+use crate::geodesics::{intersection, karney, karney::Geodesic};
+use crate::reference_frames::wgs84::WGS84Coord;
+
+#[test]
+fn test_spherical_intersection() {
+    // Worked example from Chris Veness' great circle calculator
+    let first = WGS84Coord::new_from_degrees(51.8853, 0.2545, 0.);
+    let second = WGS84Coord::new_from_degrees(49.0034, 2.5735, 0.);
+
+    let [closest, antipode] = intersection::spherical_intersections(
+        &first,
+        108.547_f64.to_radians(),
+        &second,
+        32.435_f64.to_radians(),
+    )
+    .unwrap();
+
+    assert_relative_eq!(closest.coord.get_lat_degrees(), 50.9078, epsilon = 1e-4);
+    assert_relative_eq!(closest.coord.get_lon_degrees(), 4.5084, epsilon = 1e-4);
+    assert!(closest.is_within_both());
+
+    assert_relative_eq!(
+        antipode.coord.get_lat_degrees(),
+        -closest.coord.get_lat_degrees(),
+        epsilon = 1e-12
+    );
+    assert_relative_eq!(
+        antipode.coord.get_lon_degrees(),
+        closest.coord.get_lon_degrees() - 180.,
+        epsilon = 1e-12
+    );
+    assert!(!antipode.within1 && !antipode.within2);
+
+    // Two meridians of the same great circle have no single intersection
+    let north = WGS84Coord::new_from_degrees(10., 20., 0.);
+    let south = WGS84Coord::new_from_degrees(-30., 20., 0.);
+
+    assert!(intersection::spherical_intersection(&north, PI, &south, 0.).is_none());
+}
+
+#[test]
+fn test_equator_and_meridian() {
+    // The equator and a meridian meet at a known point on any ellipsoid
+    let start1 = WGS84Coord::new_from_degrees(0., 0., 0.);
+    let start2 = WGS84Coord::new_from_degrees(20., 10., 0.);
+
+    let result = intersection::intersection(&start1, FRAC_PI_2, &start2, PI).unwrap();
+
+    assert_relative_eq!(result.coord.get_lat_radians(), 0., epsilon = 1e-14);
+    assert_relative_eq!(
+        result.coord.get_lon_radians(),
+        10_f64.to_radians(),
+        epsilon = 1e-14
+    );
+    assert_relative_eq!(
+        result.distance1,
+        Ellipsoid::WGS84.get_semi_major() * 10_f64.to_radians(),
+        epsilon = 1e-6
+    );
+    assert_relative_eq!(
+        result.distance2,
+        karney::inverse(0., 0., 20_f64.to_radians(), 0.).distance,
+        epsilon = 1e-6
+    );
+    assert!(result.is_within_both());
+}
+
+#[test]
+fn test_constructed_intersection() {
+    // Two geodesics built through a common point, given by a point and an azimuth and as segments
+    let geodesic = Geodesic::new(&Ellipsoid::WGS84);
+    let (lat, lon) = (-35_f64.to_radians(), 150_f64.to_radians());
+
+    for (azimuth1, azimuth2, distance1, distance2) in [
+        (30_f64, 100_f64, 1.5e6, 800e3),
+        (-80., 170., 3e6, -400e3),
+        (45., 60., -200e3, 2.5e6),
+    ] {
+        let (azimuth1, azimuth2) = (azimuth1.to_radians(), azimuth2.to_radians());
+        let start1 = geodesic.direct(lat, lon, azimuth1 + PI, distance1);
+        let start2 = geodesic.direct(lat, lon, azimuth2 + PI, distance2);
+        let coord1 = WGS84Coord::new_from_radians(start1.lat, start1.lon, 0.);
+        let coord2 = WGS84Coord::new_from_radians(start2.lat, start2.lon, 0.);
+
+        let result =
+            intersection::intersection(&coord1, start1.azimuth + PI, &coord2, start2.azimuth + PI)
+                .unwrap();
+
+        assert_relative_eq!(result.coord.get_lat_radians(), lat, epsilon = 1e-13);
+        assert_relative_eq!(result.coord.get_lon_radians(), lon, epsilon = 1e-13);
+        assert_relative_eq!(result.distance1, distance1, epsilon = 1e-6);
+        assert_relative_eq!(result.distance2, distance2, epsilon = 1e-6);
+        assert_eq!(result.within1, distance1 >= 0.);
+        assert_eq!(result.within2, distance2 >= 0.);
+
+        // Segments running on 1000 km past the intersection
+        let end1 = geodesic.direct(lat, lon, azimuth1, 1e6);
+        let end2 = geodesic.direct(lat, lon, azimuth2, 1e6);
+
+        let segments = intersection::segment_intersection(
+            &coord1,
+            &WGS84Coord::new_from_radians(end1.lat, end1.lon, 0.),
+            &coord2,
+            &WGS84Coord::new_from_radians(end2.lat, end2.lon, 0.),
+        )
+        .unwrap();
+
+        assert_relative_eq!(segments.coord.get_lat_radians(), lat, epsilon = 1e-12);
+        assert_relative_eq!(segments.coord.get_lon_radians(), lon, epsilon = 1e-12);
+        assert_eq!(
+            segments.is_within_both(),
+            distance1 >= 0. && distance2 >= 0.
+        );
+    }
+}
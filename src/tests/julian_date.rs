@@ -0,0 +1,115 @@
+use approx::assert_relative_eq;
+use chrono::NaiveDate;
+
+use crate::time::epoch::{Epoch, TimeScale};
+use crate::time::julian_date::JulianDate;
+
+#[test]
+fn test_calendar_to_julian() {
+    // Meeus, Astronomical Algorithms, chapter 7
+    let cases = [
+        (2000, 1, 1, 12, 2451545.0),
+        (1999, 1, 1, 0, 2451179.5),
+        (1987, 1, 27, 0, 2446822.5),
+        (1987, 6, 19, 12, 2446966.0),
+        (1988, 1, 27, 0, 2447187.5),
+        (1988, 6, 19, 12, 2447332.0),
+        (1900, 1, 1, 0, 2415020.5),
+        (1600, 1, 1, 0, 2305447.5),
+        (1600, 12, 31, 0, 2305812.5),
+    ];
+    for (year, month, day, hour, expected) in cases {
+        let jd = JulianDate::new_from_calendar(year, month, day, hour, 0, 0.);
+        assert_eq!(jd.get_julian(), expected);
+    }
+
+    // Sputnik 1, 1957 October 4.81
+    let sputnik = JulianDate::new_from_calendar(1957, 10, 4, 19, 26, 24.);
+    assert_relative_eq!(sputnik.get_julian(), 2436116.31, epsilon = 1e-9);
+
+    // Vallado example 3-4, 1996 October 26 14:20 UT
+    let jd = JulianDate::new_from_calendar(1996, 10, 26, 14, 20, 0.);
+    assert_relative_eq!(jd.get_julian(), 2450383.09722222, epsilon = 1e-8);
+    assert_eq!(jd.get_day(), 2450382.5);
+    assert_relative_eq!(jd.get_fraction(), 0.59722222, epsilon = 1e-8);
+}
+
+#[test]
+fn test_julian_to_calendar() {
+    // Split so that 0.81 of a day isn't rounded against the large day number
+    let sputnik = JulianDate::new(2436116.5, -0.19);
+    assert_eq!(
+        sputnik.get_datetime(),
+        NaiveDate::from_ymd_opt(1957, 10, 4)
+            .unwrap()
+            .and_hms_opt(19, 26, 24)
+            .unwrap()
+    );
+
+    let datetime = NaiveDate::from_ymd_opt(2024, 2, 29)
+        .unwrap()
+        .and_hms_nano_opt(23, 59, 59, 999_999_999)
+        .unwrap();
+    let jd = JulianDate::new_from_datetime(&datetime);
+    assert_eq!(jd.get_datetime(), datetime);
+    assert_eq!(JulianDate::from(&datetime), jd);
+}
+
+#[test]
+fn test_mjd_and_j2000() {
+    let origin = JulianDate::new_from_calendar(1858, 11, 17, 0, 0, 0.);
+    assert_eq!(origin.get_mjd(), 0.);
+    assert_eq!(JulianDate::new_from_mjd(51544.5).get_julian(), 2451545.0);
+
+    let jd = JulianDate::new_from_calendar(2000, 1, 2, 12, 0, 0.5);
+    assert_relative_eq!(jd.get_j2000_seconds(), 86400.5, epsilon = 1e-9);
+    assert_eq!(JulianDate::new_from_j2000_seconds(86400.5), jd);
+    assert_eq!(
+        JulianDate::new_from_j2000_seconds(-0.5).get_datetime(),
+        NaiveDate::from_ymd_opt(2000, 1, 1)
+            .unwrap()
+            .and_hms_milli_opt(11, 59, 59, 500)
+            .unwrap()
+    );
+
+    // Vallado example 3-5, 1992 August 20 12:14 UT1
+    let jd = JulianDate::new_from_calendar(1992, 8, 20, 12, 14, 0.);
+    assert_relative_eq!(jd.get_julian(), 2448855.009722, epsilon = 1e-6);
+    assert_relative_eq!(jd.get_julian_centuries(), -0.07364792, epsilon = 1e-8);
+}
+
+#[test]
+fn test_two_part_precision() {
+    // A microsecond is well below what a single f64 Julian date can hold
+    let jd = JulianDate::new_from_calendar(2030, 1, 1, 0, 0, 1e-6);
+    assert_relative_eq!(jd.get_fraction() * 86400., 1e-6, epsilon = 1e-12);
+    assert_relative_eq!(
+        jd.add_days(1.5).get_j2000_seconds() - jd.get_j2000_seconds(),
+        129600.,
+        epsilon = 1e-6
+    );
+
+    // The same instant split differently
+    assert_eq!(
+        JulianDate::new(2451545.25, 0.),
+        JulianDate::new(2451545., 0.25)
+    );
+    assert_eq!(JulianDate::new(2451545.25, 0.).get_day(), 2451544.5);
+}
+
+#[test]
+fn test_from_epoch() {
+    let utc = NaiveDate::from_ymd_opt(2017, 6, 1)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+    let epoch = Epoch::new_from_utc(&utc);
+
+    assert_eq!(
+        JulianDate::new_from_epoch(&epoch, TimeScale::UTC),
+        JulianDate::new_from_datetime(&utc)
+    );
+    let tt = JulianDate::new_from_epoch(&epoch, TimeScale::TT);
+    let seconds = tt.get_j2000_seconds() - JulianDate::new_from_datetime(&utc).get_j2000_seconds();
+    assert_relative_eq!(seconds, 69.184, epsilon = 1e-6);
+}
@@ -0,0 +1,271 @@
+// Reference values are quoted verbatim from GeographicLib
+#![allow(clippy::excessive_precision)]
+
+use approx::assert_relative_eq;
+
+use crate::ellipsoid::Ellipsoid;
+use crate::geodesics::karney::{self, Geodesic};
+
+// Lines from GeographicLib's GeodTest.dat:
+// lat1 lon1 azi1 lat2 lon2 azi2 s12 a12 m12 M12 M21 S12 (degrees, metres)
+#[allow(clippy::type_complexity)]
+const GEOD_TEST: [(f64, f64, f64, f64, f64, f64, f64, f64, f64, f64, f64, f64); 5] = [
+    (
+        35.60777,
+        -139.44815,
+        111.098748429560326,
+        -11.17491,
+        -69.95921,
+        129.289270889708762,
+        8935244.5604818305,
+        80.50729714281974,
+        6273170.2055303837,
+        0.16606318447386067,
+        0.16479116945612937,
+        12841384694976.432,
+    ),
+    (
+        55.52454,
+        106.05087,
+        22.020059880982801,
+        77.03196,
+        197.18234,
+        109.112041110671519,
+        4105086.1713924406,
+        36.892740690445894,
+        3828869.3344387607,
+        0.80076349608092607,
+        0.80101006984201008,
+        61674961290615.615,
+    ),
+    (
+        -21.97856,
+        142.59065,
+        -32.44456876433189,
+        41.84138,
+        98.56635,
+        -41.84359951440466,
+        8394328.894657671,
+        75.62930491011522,
+        6161154.5773110616,
+        0.24816339233950381,
+        0.24930251203627892,
+        -6637997720646.717,
+    ),
+    (
+        -66.99028,
+        112.2363,
+        173.73491240878403,
+        -12.70631,
+        285.90344,
+        2.512956620913668,
+        11150344.2312080241,
+        100.278634181155759,
+        6289939.5670446687,
+        -0.17199490274700385,
+        -0.17722569526345708,
+        -121287239862139.744,
+    ),
+    (
+        -87.85331,
+        85.66836,
+        -65.120313040242748,
+        66.48646,
+        16.09921,
+        -4.888658719272296,
+        17286615.3147144645,
+        155.58592449699137,
+        2635887.4729110181,
+        -0.90697975771398578,
+        -0.91095608883042767,
+        42667211366919.534,
+    ),
+];
+
+fn inverse_degrees(
+    geodesic: &Geodesic,
+    lat1: f64,
+    lon1: f64,
+    lat2: f64,
+    lon2: f64,
+) -> (f64, f64, f64) {
+    // Returns (s12, azi1, azi2) with the azimuths in degrees, to compare against published values
+    let inverse = geodesic.inverse(
+        lat1.to_radians(),
+        lon1.to_radians(),
+        lat2.to_radians(),
+        lon2.to_radians(),
+    );
+    return (
+        inverse.distance,
+        inverse.azimuth1.to_degrees(),
+        inverse.azimuth2.to_degrees(),
+    );
+}
+
+#[test]
+fn test_inverse_geod_test() {
+    let geodesic = Geodesic::new(&Ellipsoid::WGS84);
+
+    for (lat1, lon1, azi1, lat2, lon2, azi2, s12, a12, m12, scale12, scale21, area) in GEOD_TEST {
+        let inverse = geodesic.inverse(
+            lat1.to_radians(),
+            lon1.to_radians(),
+            lat2.to_radians(),
+            lon2.to_radians(),
+        );
+
+        assert_relative_eq!(inverse.distance, s12, epsilon = 1e-8);
+        assert_relative_eq!(inverse.azimuth1.to_degrees(), azi1, epsilon = 1e-11);
+        assert_relative_eq!(inverse.azimuth2.to_degrees(), azi2, epsilon = 1e-11);
+        assert_relative_eq!(inverse.arc_length.to_degrees(), a12, epsilon = 1e-11);
+        assert_relative_eq!(inverse.reduced_length, m12, epsilon = 1e-8);
+        assert_relative_eq!(inverse.geodesic_scale12, scale12, epsilon = 1e-14);
+        assert_relative_eq!(inverse.geodesic_scale21, scale21, epsilon = 1e-14);
+        assert_relative_eq!(inverse.area, area, epsilon = 0.1);
+    }
+}
+
+#[test]
+fn test_inverse_nearly_antipodal() {
+    // GeodSolve6, 9, 10 and 11: nearly antipodal points where earlier versions failed to converge
+    let geodesic = Geodesic::new(&Ellipsoid::WGS84);
+
+    let cases = [
+        (
+            88.202499451857,
+            -88.202499451857,
+            179.981022032992859592,
+            20003898.214,
+        ),
+        (
+            89.333123580033,
+            -89.333123580032997687,
+            179.99295812360148422,
+            20003926.881,
+        ),
+        (
+            56.320923501171,
+            -56.320923501171,
+            179.664747671772880215,
+            19993558.287,
+        ),
+        (
+            52.784459512564,
+            -52.784459512563990912,
+            179.634407464943777557,
+            19991596.095,
+        ),
+        (
+            48.522876735459,
+            -48.52287673545898293,
+            179.599720456223079643,
+            19989144.774,
+        ),
+    ];
+
+    for (lat1, lat2, lon2, s12) in cases {
+        let (distance, _, _) = inverse_degrees(&geodesic, lat1, 0., lat2, lon2);
+        assert_relative_eq!(distance, s12, epsilon = 0.5e-3);
+    }
+
+    // GeodSolve59: points close to 180 degrees apart in longitude
+    let (distance, azi1, azi2) = inverse_degrees(&geodesic, 5., 0.00000000000001, 10., 180.);
+    assert_relative_eq!(azi1, 0.000000000000035, epsilon = 1.5e-14);
+    assert_relative_eq!(azi2, 179.99999999999996, epsilon = 1.5e-14);
+    assert_relative_eq!(distance, 18345191.174332713, epsilon = 5e-9);
+}
+
+#[test]
+fn test_inverse_equatorial_and_meridional() {
+    // GeodSolve33: the geodesic between equatorial points switches from the equator to a
+    // meridian as the points approach being antipodal
+    let geodesic = Geodesic::new(&Ellipsoid::WGS84);
+
+    let (distance, azi1, azi2) = inverse_degrees(&geodesic, 0., 0., 0., 179.);
+    assert_relative_eq!(azi1, 90., epsilon = 0.5e-5);
+    assert_relative_eq!(azi2, 90., epsilon = 0.5e-5);
+    assert_relative_eq!(distance, 19926189., epsilon = 0.5);
+
+    let (distance, azi1, azi2) = inverse_degrees(&geodesic, 0., 0., 0., 179.5);
+    assert_relative_eq!(azi1, 55.96650, epsilon = 0.5e-5);
+    assert_relative_eq!(azi2, 124.03350, epsilon = 0.5e-5);
+    assert_relative_eq!(distance, 19980862., epsilon = 0.5);
+
+    let (distance, azi1, azi2) = inverse_degrees(&geodesic, 0., 0., 0., 180.);
+    assert_relative_eq!(azi1, 0., epsilon = 0.5e-5);
+    assert_relative_eq!(azi2.abs(), 180., epsilon = 0.5e-5);
+    assert_relative_eq!(distance, 20003931., epsilon = 0.5);
+
+    let (distance, azi1, azi2) = inverse_degrees(&geodesic, 0., 0., 1., 180.);
+    assert_relative_eq!(azi1, 0., epsilon = 0.5e-5);
+    assert_relative_eq!(azi2.abs(), 180., epsilon = 0.5e-5);
+    assert_relative_eq!(distance, 19893357., epsilon = 0.5);
+
+    // On a prolate ellipsoid the equator is the shortest path all the way to the antipode
+    let prolate = Geodesic::new(&Ellipsoid::new(6.4e6, -1. / 300.));
+
+    let (distance, azi1, azi2) = inverse_degrees(&prolate, 0., 0., 0., 180.);
+    assert_relative_eq!(azi1, 90., epsilon = 0.5e-5);
+    assert_relative_eq!(azi2, 90., epsilon = 0.5e-5);
+    assert_relative_eq!(distance, 20106193., epsilon = 0.5);
+
+    let (distance, azi1, azi2) = inverse_degrees(&prolate, 0., 0., 0.5, 180.);
+    assert_relative_eq!(azi1, 33.02493, epsilon = 0.5e-5);
+    assert_relative_eq!(azi2, 146.97364, epsilon = 0.5e-5);
+    assert_relative_eq!(distance, 20082617., epsilon = 0.5);
+}
+
+#[test]
+fn test_inverse_degenerate_inputs() {
+    let geodesic = Geodesic::new(&Ellipsoid::WGS84);
+
+    // GeodSolve4: very short lines
+    let (distance, _, _) =
+        inverse_degrees(&geodesic, 36.493349428792, 0., 36.49334942879201, 0.0000008);
+    assert_relative_eq!(distance, 0.072, epsilon = 0.5e-3);
+
+    // Coincident points
+    let inverse = karney::inverse(0.5, 0.5, 0.5, 0.5);
+    assert_eq!(inverse.distance, 0.);
+    assert_eq!(inverse.reduced_length, 0.);
+    assert_eq!(inverse.geodesic_scale12, 1.);
+
+    // GeodSolve14 and 55: NaNs propagate rather than being silently swallowed
+    let inverse = karney::inverse(0., 0., 1_f64.to_radians(), f64::NAN);
+    assert!(inverse.distance.is_nan());
+    assert!(inverse.azimuth1.is_nan());
+    assert!(inverse.azimuth2.is_nan());
+
+    let inverse = karney::inverse(f64::NAN, 0., 0., 90_f64.to_radians());
+    assert!(inverse.distance.is_nan());
+    assert!(inverse.azimuth1.is_nan());
+}
+
+#[test]
+fn test_inverse_area_on_sphere() {
+    // GeodSolve26: 0/0 problem with the area on a sphere
+    let sphere = Geodesic::new(&Ellipsoid::new(6.4e6, 0.));
+    let inverse = sphere.inverse(
+        1_f64.to_radians(),
+        2_f64.to_radians(),
+        3_f64.to_radians(),
+        4_f64.to_radians(),
+    );
+
+    assert_relative_eq!(inverse.area, 49911046115., epsilon = 0.5);
+}
+
+#[test]
+fn test_distance_and_bearing_uses_inverse() {
+    // JFK to Paris CDG (GeodSolve0)
+    let result = karney::distance_and_bearing(
+        40.6_f64.to_radians(),
+        -73.8_f64.to_radians(),
+        49.01666667_f64.to_radians(),
+        2.55_f64.to_radians(),
+    );
+
+    assert_relative_eq!(result.distance, 5853226., epsilon = 0.5);
+    assert_relative_eq!(result.bearing.to_degrees(), 53.47022, epsilon = 0.5e-5);
+}
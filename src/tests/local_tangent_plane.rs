@@ -0,0 +1,109 @@
+use approx::assert_relative_eq;
+
+use crate::reference_frames::aer::AER;
+use crate::reference_frames::ecef::{generate_ecef_to_ned_matrix, ECEF};
+use crate::reference_frames::enu::ENU;
+use crate::reference_frames::local_tangent_plane::{LocalTangentPlane, TangentPlaneAxes};
+use crate::reference_frames::ned::{NEDAccel, NEDVel, NED};
+use crate::reference_frames::wgs84::WGS84Coord;
+
+#[test]
+fn test_positions() {
+    let reference_point = WGS84Coord::new_from_degrees(50., 10., 0.);
+    let plane = LocalTangentPlane::new_ned(&reference_point);
+
+    // The same answers as building the rotation by hand
+    let rotation = generate_ecef_to_ned_matrix(&reference_point);
+    assert_eq!(plane.get_rotation(), &rotation);
+    assert_eq!(plane.get_axes(), TangentPlaneAxes::NED);
+
+    let point = WGS84Coord::new_from_degrees(50.01, 10.02, 300.);
+    let ned = plane.ned_from_wgs84(&point);
+    let by_hand = NED::new_from_ecef_rot(
+        &ECEF::new_from_wgs84(&point),
+        &rotation,
+        plane.get_reference_ecef(),
+    );
+    assert_relative_eq!(ned.n, by_hand.n, epsilon = 1e-9);
+    assert_relative_eq!(ned.e, by_hand.e, epsilon = 1e-9);
+    assert_relative_eq!(ned.d, by_hand.d, epsilon = 1e-9);
+
+    let enu = plane.enu_from_wgs84(&point);
+    assert_relative_eq!(enu.e, ned.e, epsilon = 1e-9);
+    assert_relative_eq!(enu.n, ned.n, epsilon = 1e-9);
+    assert_relative_eq!(enu.u, -ned.d, epsilon = 1e-9);
+
+    let back = plane.wgs84_from_ned(&ned);
+    assert_relative_eq!(back.get_lat_degrees(), 50.01, epsilon = 1e-10);
+    assert_relative_eq!(back.get_lon_degrees(), 10.02, epsilon = 1e-10);
+    assert_relative_eq!(back.get_altitude(), 300., epsilon = 1e-6);
+
+    let back = plane.wgs84_from_enu(&enu);
+    assert_relative_eq!(back.get_lat_degrees(), 50.01, epsilon = 1e-10);
+    assert_relative_eq!(back.get_lon_degrees(), 10.02, epsilon = 1e-10);
+
+    let aer = plane.aer_from_wgs84(&point);
+    let expected = AER::new_from_wgs84(&point, &reference_point);
+    assert_relative_eq!(aer.azimuth, expected.azimuth, epsilon = 1e-12);
+    assert_relative_eq!(aer.elevation, expected.elevation, epsilon = 1e-12);
+    let ecef = plane.ecef_from_aer(&aer);
+    let expected = ECEF::new_from_wgs84(&point);
+    assert_relative_eq!(ecef.x, expected.x, epsilon = 1e-6);
+    assert_relative_eq!(ecef.y, expected.y, epsilon = 1e-6);
+    assert_relative_eq!(ecef.z, expected.z, epsilon = 1e-6);
+
+    let origin = plane.ecef_from_enu(&ENU::default());
+    assert_relative_eq!(origin.x, plane.get_reference_ecef().x, epsilon = 1e-9);
+}
+
+#[test]
+fn test_velocities_and_accelerations() {
+    let reference_point = WGS84Coord::new_from_degrees(-12., 130.8, 30.);
+    let plane = LocalTangentPlane::new(&reference_point, TangentPlaneAxes::ENU);
+
+    // Climbing towards the north east
+    let ned_vel = NEDVel {
+        n_vel: 100.,
+        e_vel: 100.,
+        d_vel: -10.,
+    };
+    let ecef_vel = plane.ecef_vel_from_ned_vel(&ned_vel);
+    let enu_vel = plane.enu_vel_from_ecef_vel(&ecef_vel);
+
+    assert_relative_eq!(enu_vel.e_vel, 100., epsilon = 1e-12);
+    assert_relative_eq!(enu_vel.n_vel, 100., epsilon = 1e-12);
+    assert_relative_eq!(enu_vel.u_vel, 10., epsilon = 1e-12);
+    assert_relative_eq!(ecef_vel.get_speed(), 20100_f64.sqrt(), epsilon = 1e-12);
+
+    let swapped = plane.enu_vel_from_ned_vel(&ned_vel);
+    assert_eq!(swapped.u_vel, 10.);
+    let back = plane.ned_vel_from_enu_vel(&swapped);
+    assert_eq!(back.d_vel, -10.);
+
+    let round = plane.ecef_vel_from_enu_vel(&enu_vel);
+    let again = plane.ned_vel_from_ecef_vel(&round);
+    assert_relative_eq!(again.n_vel, 100., epsilon = 1e-12);
+    assert_relative_eq!(again.d_vel, -10., epsilon = 1e-12);
+
+    // Gravity points down
+    let gravity = NEDAccel {
+        n_accel: 0.,
+        e_accel: 0.,
+        d_accel: 9.8,
+    };
+    let ecef_accel = plane.ecef_accel_from_ned_accel(&gravity);
+    let reference = plane.get_reference_ecef();
+    let towards_centre = -(ecef_accel.x_accel * reference.x
+        + ecef_accel.y_accel * reference.y
+        + ecef_accel.z_accel * reference.z)
+        / (reference.x.hypot(reference.y).hypot(reference.z));
+    assert!(towards_centre > 9.79);
+    assert_relative_eq!(ecef_accel.magnitude(), 9.8, epsilon = 1e-12);
+
+    let enu_accel = plane.enu_accel_from_ecef_accel(&ecef_accel);
+    assert_relative_eq!(enu_accel.u_accel, -9.8, epsilon = 1e-12);
+    let back = plane.ecef_accel_from_enu_accel(&enu_accel);
+    let ned_accel = plane.ned_accel_from_ecef_accel(&back);
+    assert_relative_eq!(ned_accel.d_accel, 9.8, epsilon = 1e-12);
+    assert_relative_eq!(ned_accel.n_accel, 0., epsilon = 1e-12);
+}
@@ -0,0 +1,77 @@
+use std::f64::consts::FRAC_PI_2;
+
+use approx::assert_relative_eq;
+
+use crate::matrix::{Mat3, Vec3};
+
+#[test]
+fn test_rotations_and_composition() {
+    // Turning the axes a quarter turn about z moves the x axis onto -y
+    let x_axis = Vec3::new(1., 0., 0.);
+    let turned = Mat3::rotation_z(FRAC_PI_2) * x_axis;
+
+    assert_relative_eq!(turned.x, 0., epsilon = 1e-15);
+    assert_relative_eq!(turned.y, -1., epsilon = 1e-15);
+    assert_relative_eq!(turned.z, 0., epsilon = 1e-15);
+
+    let a = Mat3::rotation_x(0.3);
+    let b = Mat3::rotation_y(-1.1);
+    let c = Mat3::rotation_z(2.5);
+    let v = Vec3::new(1., -2., 3.5);
+
+    let composed = (a * b * c) * v;
+    let stepwise = a * (b * (c * v));
+    assert_relative_eq!(composed.x, stepwise.x, epsilon = 1e-14);
+    assert_relative_eq!(composed.y, stepwise.y, epsilon = 1e-14);
+    assert_relative_eq!(composed.z, stepwise.z, epsilon = 1e-14);
+
+    // Rotations keep lengths and undo with the transpose
+    let r = a * b * c;
+    assert_relative_eq!((r * v).norm(), v.norm(), epsilon = 1e-14);
+    assert_relative_eq!(r.determinant(), 1., epsilon = 1e-15);
+
+    let back = r.transpose_times(&(r * v));
+    assert_relative_eq!(back.x, v.x, epsilon = 1e-14);
+    assert_relative_eq!(back.y, v.y, epsilon = 1e-14);
+    assert_relative_eq!(back.z, v.z, epsilon = 1e-14);
+
+    let identity = r * r.orthonormal_inverse();
+    for i in 0..3 {
+        for j in 0..3 {
+            assert_relative_eq!(identity[(i, j)], Mat3::identity()[(i, j)], epsilon = 1e-15);
+        }
+    }
+}
+
+#[test]
+fn test_inverse() {
+    let m = Mat3::new_from_array([2., 0., 1., 1., 3., 2., 1., 1., 2.]);
+    let inverse = m.inverse().unwrap();
+
+    assert_relative_eq!(m.determinant(), 6., epsilon = 1e-15);
+    assert_relative_eq!(inverse[(0, 0)], 4. / 6., epsilon = 1e-15);
+    let product = m * inverse;
+    for i in 0..3 {
+        for j in 0..3 {
+            let expected = if i == j { 1. } else { 0. };
+            assert_relative_eq!(product[(i, j)], expected, epsilon = 1e-15);
+        }
+    }
+    assert!(Mat3::new_from_array([1., 2., 3., 2., 4., 6., 0., 1., 0.])
+        .inverse()
+        .is_none());
+}
+
+#[test]
+fn test_vec3() {
+    let a = Vec3::new(1., 2., 3.);
+    let b = Vec3::new(-2., 0.5, 4.);
+
+    assert_eq!(a.dot(&b), 11.);
+    assert_eq!(a.cross(&b), Vec3::new(6.5, -10., 4.5));
+    assert_eq!(a.cross(&b).dot(&a), 0.);
+    assert_eq!(a + b - b, a);
+    assert_eq!(-a * 2., Vec3::new(-2., -4., -6.));
+    assert_eq!(a[2], 3.);
+    assert_eq!(Vec3::new_from_array(a.to_array()), a);
+}
@@ -0,0 +1,124 @@
+use approx::assert_relative_eq;
+
+use crate::projections::mgrs::{MGRSCoord, MGRSError};
+use crate::projections::utm::{Hemisphere, UTMCoord};
+use crate::reference_frames::wgs84::WGS84Coord;
+
+#[test]
+fn test_mgrs_encode() {
+    // GeoConvert documentation example: 33.3N 44.4E -> 38n 444140.54 3684706.36
+    let point = WGS84Coord::new_from_degrees(33.3, 44.4, 0.0);
+
+    let expected = [
+        "38SMB",
+        "38SMB48",
+        "38SMB4484",
+        "38SMB441847",
+        "38SMB44148470",
+        "38SMB4414084706",
+    ];
+    for (precision, mgrs) in expected.iter().enumerate() {
+        let encoded = MGRSCoord::new_from_wgs84(&point, precision as u8).unwrap();
+        assert_eq!(encoded.to_string(), *mgrs);
+    }
+
+    assert_eq!(
+        MGRSCoord::new_from_wgs84(&point, 6),
+        Err(MGRSError::InvalidPrecision(6))
+    );
+
+    // Single digit zones are zero padded
+    let honolulu = MGRSCoord::new_from_wgs84(&WGS84Coord::new_from_degrees(21.3, -157.85, 0.0), 0);
+
+    assert_eq!(honolulu.unwrap().to_string(), "04QFJ");
+}
+
+#[test]
+fn test_mgrs_polar() {
+    let north_pole = MGRSCoord::new_from_wgs84(&WGS84Coord::new_from_degrees(90.0, 0.0, 0.0), 5);
+    let south_pole = MGRSCoord::new_from_wgs84(&WGS84Coord::new_from_degrees(-90.0, 0.0, 0.0), 5);
+
+    assert_eq!(north_pole.unwrap().to_string(), "ZAH0000000000");
+    assert_eq!(south_pole.unwrap().to_string(), "BAN0000000000");
+
+    // Round trip through all four UPS bands
+    for &(lat, lon) in [(85.0, -100.0), (85.0, 100.0), (-81.0, -20.0), (-81.0, 20.0)].iter() {
+        let point = WGS84Coord::new_from_degrees(lat, lon, 0.0);
+        let mgrs = MGRSCoord::new_from_wgs84(&point, 5).unwrap();
+        let decoded = MGRSCoord::new_from_str(&mgrs.to_string()).unwrap();
+
+        assert_eq!(decoded, mgrs);
+        assert!(decoded.get_corner().is_ups());
+
+        let centre = decoded.to_wgs84_centre();
+
+        // Within a metre
+        assert_relative_eq!(centre.get_lat_degrees(), lat, epsilon = 1e-5);
+        assert_relative_eq!(centre.get_lon_degrees(), lon, epsilon = 1e-3);
+    }
+}
+
+#[test]
+fn test_mgrs_decode() {
+    let mgrs = MGRSCoord::new_from_str("38SMB4484").unwrap();
+
+    assert_eq!(mgrs.get_precision(), 2);
+    assert_eq!(mgrs.get_cell_size(), 1000.0);
+    assert_eq!(
+        mgrs.get_corner(),
+        UTMCoord::new(38, Hemisphere::North, 444000.0, 3684000.0).unwrap()
+    );
+    assert_eq!(
+        mgrs.get_centre(),
+        UTMCoord::new(38, Hemisphere::North, 444500.0, 3684500.0).unwrap()
+    );
+
+    // USNG style: spaces, lower case, no zero padding
+    let usng: MGRSCoord = "4q fj 12345 67890".parse().unwrap();
+
+    assert_eq!(usng.to_string(), "04QFJ1234567890");
+    assert_eq!(usng.get_corner().get_easting(), 612345.0);
+    assert_eq!(usng.get_corner().get_northing(), 2367890.0);
+
+    // Southern hemisphere
+    let sydney = WGS84Coord::new_from_degrees(-33.8568, 151.2153, 0.0);
+    let encoded = MGRSCoord::new_from_wgs84(&sydney, 5).unwrap();
+    let decoded = MGRSCoord::new_from_str(&encoded.to_string()).unwrap();
+
+    assert_eq!(decoded.get_corner().get_hemisphere(), Hemisphere::South);
+    assert_relative_eq!(
+        decoded.to_wgs84_corner().get_lat_degrees(),
+        sydney.get_lat_degrees(),
+        epsilon = 1e-4
+    );
+    assert_relative_eq!(
+        decoded.to_wgs84_corner().get_lon_degrees(),
+        sydney.get_lon_degrees(),
+        epsilon = 1e-4
+    );
+}
+
+#[test]
+fn test_mgrs_validation() {
+    let err = |s: &str| MGRSCoord::new_from_str(s).unwrap_err();
+
+    assert_eq!(err("61SMB"), MGRSError::InvalidGridZone("61S".to_string()));
+    assert_eq!(err("38IMB"), MGRSError::InvalidGridZone("38I".to_string()));
+    assert_eq!(err("38S"), MGRSError::InvalidGridZone("38S".to_string()));
+    assert_eq!(err("CAN"), MGRSError::InvalidGridZone("C".to_string()));
+    // Zone 38 uses columns J-R
+    assert_eq!(err("38SAB"), MGRSError::InvalidColumn('A'));
+    assert_eq!(err("38SMW"), MGRSError::InvalidRow('W'));
+    // Row letter exists but that square is nowhere near band S
+    assert_eq!(
+        err("38SML"),
+        MGRSError::SquareNotInZone("38SML".to_string())
+    );
+    // UPS north only has 14 row letters
+    assert_eq!(err("ZAQ"), MGRSError::InvalidRow('Q'));
+    assert_eq!(err("38SMB123"), MGRSError::InvalidDigits("123".to_string()));
+    assert_eq!(
+        err("38SMB12A4"),
+        MGRSError::InvalidDigits("12A4".to_string())
+    );
+}
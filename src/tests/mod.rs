@@ -1 +1,30 @@
+mod aer;
+mod centroid;
+mod cio;
+mod circle;
+mod cross_track;
+mod datum;
 mod ecef_to_ned;
+mod ellipsoid;
+mod enu;
+mod eop;
+mod epoch;
+mod fk5;
+mod geodesic_line;
+mod gnomonic;
+mod haversine;
+mod interpolation;
+mod intersection;
+mod julian_date;
+mod karney;
+mod local_tangent_plane;
+mod matrix;
+mod mgrs;
+mod polygon;
+mod rhumb;
+mod sidereal;
+mod teme;
+mod tle;
+mod transform;
+mod utm;
+mod vincenty;
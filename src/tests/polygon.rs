@@ -0,0 +1,158 @@
+use approx::assert_relative_eq;
+
+use crate::ellipsoid::Ellipsoid;
+use crate::geodesics::karney;
+use crate::geodesics::polygon::{area_and_perimeter, GeodesicPolygon, Winding};
+use crate::reference_frames::wgs84::WGS84Coord;
+
+fn polygon_from_degrees(vertices: &[(f64, f64)], winding: Winding) -> GeodesicPolygon {
+    let mut polygon = GeodesicPolygon::new(&Ellipsoid::WGS84, winding);
+    for (lat, lon) in vertices.iter() {
+        polygon.add_point(lat.to_radians(), lon.to_radians());
+    }
+    return polygon;
+}
+
+#[test]
+fn test_one_degree_square() {
+    let coords = [
+        WGS84Coord::new_from_degrees(0., 0., 0.),
+        WGS84Coord::new_from_degrees(0., 1., 0.),
+        WGS84Coord::new_from_degrees(1., 1., 0.),
+        WGS84Coord::new_from_degrees(1., 0., 0.),
+    ];
+
+    let result = area_and_perimeter(&coords);
+
+    assert_eq!(result.num_vertices, 4);
+    assert_relative_eq!(result.perimeter, 443770.917, epsilon = 1e-3);
+    assert_relative_eq!(result.area, 12308778361.469, epsilon = 1e-3);
+
+    // Walking the other way round flips the sign, or gives the rest of the earth if unsigned
+    let reversed: Vec<WGS84Coord> = coords.iter().rev().cloned().collect();
+    let result = area_and_perimeter(&reversed);
+    assert_relative_eq!(result.area, -12308778361.469, epsilon = 1e-3);
+
+    let unsigned =
+        GeodesicPolygon::new_from_coords(&reversed, Winding::CounterClockwise).compute(false);
+    assert_relative_eq!(
+        unsigned.area,
+        510065621724088.5 - 12308778361.469,
+        epsilon = 1.
+    );
+
+    // Or choose clockwise as the positive direction
+    let clockwise = GeodesicPolygon::new_from_coords(&reversed, Winding::Clockwise).compute(true);
+    assert_relative_eq!(clockwise.area, 12308778361.469, epsilon = 1e-3);
+}
+
+#[test]
+fn test_polygons_around_poles() {
+    // GeographicLib's Planimeter0 and Planimeter5 checks
+    let north = polygon_from_degrees(
+        &[(89., 0.), (89., 90.), (89., 180.), (89., 270.)],
+        Winding::CounterClockwise,
+    )
+    .compute(true);
+    assert_relative_eq!(north.perimeter, 631819.8745, epsilon = 1e-4);
+    assert_relative_eq!(north.area, 24952305678., epsilon = 1.);
+
+    let south = polygon_from_degrees(
+        &[(-89., 0.), (-89., 90.), (-89., 180.), (-89., 270.)],
+        Winding::CounterClockwise,
+    )
+    .compute(true);
+    assert_relative_eq!(south.perimeter, 631819.8745, epsilon = 1e-4);
+    assert_relative_eq!(south.area, -24952305678., epsilon = 1.);
+
+    let octant = polygon_from_degrees(&[(90., 0.), (0., 0.), (0., 90.)], Winding::CounterClockwise)
+        .compute(true);
+    assert_relative_eq!(octant.perimeter, 30022685., epsilon = 1.);
+    assert_relative_eq!(octant.area, 63758202715511., epsilon = 1.);
+
+    let cap = polygon_from_degrees(
+        &[(89., 0.1), (89., 90.1), (89., -179.9)],
+        Winding::CounterClockwise,
+    )
+    .compute(true);
+    assert_relative_eq!(cap.perimeter, 539297., epsilon = 1.);
+    assert_relative_eq!(cap.area, 12476152838.5, epsilon = 1.);
+}
+
+#[test]
+fn test_degenerate_polygons() {
+    // Planimeter6: polygons folded back on themselves have no area
+    let folded = polygon_from_degrees(
+        &[(9., -0.00000000000001), (9., 180.), (9., 0.)],
+        Winding::CounterClockwise,
+    )
+    .compute(true);
+    assert_relative_eq!(folded.perimeter, 36026861., epsilon = 1.);
+    assert_relative_eq!(folded.area, 0., epsilon = 1.);
+
+    let empty = GeodesicPolygon::new(&Ellipsoid::WGS84, Winding::CounterClockwise).compute(true);
+    assert_eq!(empty.num_vertices, 0);
+    assert_eq!(empty.area, 0.);
+}
+
+#[test]
+fn test_incremental_queries() {
+    let mut polygon =
+        polygon_from_degrees(&[(0., 0.), (0., 1.), (1., 1.)], Winding::CounterClockwise);
+
+    // Testing the last vertex gives the full square without changing the polygon
+    let tested = polygon.test_point(1_f64.to_radians(), 0., true);
+    assert_relative_eq!(tested.area, 12308778361.469, epsilon = 1e-3);
+    assert_eq!(tested.num_vertices, 4);
+    assert_eq!(polygon.get_num_vertices(), 3);
+
+    // The same vertex reached as an edge from the last one
+    let leg = karney::inverse(
+        1_f64.to_radians(),
+        1_f64.to_radians(),
+        1_f64.to_radians(),
+        0.,
+    );
+    let along_edge = polygon.test_edge(leg.azimuth1, leg.distance, true);
+    assert_relative_eq!(along_edge.area, tested.area, epsilon = 1e-3);
+    assert_relative_eq!(along_edge.perimeter, tested.perimeter, epsilon = 1e-6);
+
+    polygon.add_point(1_f64.to_radians(), 0.);
+    let result = polygon.compute(true);
+    assert_relative_eq!(result.area, tested.area, epsilon = 1e-6);
+    assert_relative_eq!(result.perimeter, tested.perimeter, epsilon = 1e-9);
+
+    polygon.clear();
+    assert_eq!(polygon.get_num_vertices(), 0);
+}
+
+#[test]
+fn test_add_edge_around_pole() {
+    // A polygon built entirely from edges which wraps round the north pole, so the meridian
+    // crossings have to be counted from the unrolled longitudes
+    let mut polygon = GeodesicPolygon::new(&Ellipsoid::WGS84, Winding::CounterClockwise);
+    polygon.add_point(89_f64.to_radians(), 0.);
+
+    let by_points = polygon_from_degrees(
+        &[(89., 0.), (89., 90.), (89., 180.), (89., 270.)],
+        Winding::CounterClockwise,
+    );
+    let reference = by_points.compute(true);
+
+    let mut lon = 0_f64;
+    for _ in 0..3 {
+        let next = lon + 90.;
+        let leg = karney::inverse(
+            89_f64.to_radians(),
+            lon.to_radians(),
+            89_f64.to_radians(),
+            next.to_radians(),
+        );
+        polygon.add_edge(leg.azimuth1, leg.distance);
+        lon = next;
+    }
+
+    let result = polygon.compute(true);
+    assert_relative_eq!(result.perimeter, reference.perimeter, epsilon = 1e-6);
+    assert_relative_eq!(result.area, reference.area, max_relative = 1e-11);
+}
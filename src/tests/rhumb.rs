@@ -0,0 +1,151 @@
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use approx::assert_relative_eq;
+
+use crate::ellipsoid::Ellipsoid;
+use crate::geodesics::rhumb::{self, Rhumb};
+
+fn dms(degrees: f64, minutes: f64, seconds: f64) -> f64 {
+    return (degrees + minutes / 60. + seconds / 3600.).to_radians();
+}
+
+#[test]
+fn test_inverse_jfk_to_singapore() {
+    // Example from the GeographicLib RhumbSolve documentation
+    let result = rhumb::distance_and_bearing(
+        dms(40., 38., 23.),
+        -dms(73., 46., 44.),
+        dms(1., 21., 33.),
+        dms(103., 59., 22.),
+    );
+
+    assert_relative_eq!(result.distance, 18523563.04, epsilon = 1e-2);
+    assert_relative_eq!(
+        result.bearing,
+        103.5828330034_f64.to_radians(),
+        epsilon = 1e-11
+    );
+
+    let end = rhumb::location_and_bearing(
+        dms(40., 38., 23.),
+        -dms(73., 46., 44.),
+        result.bearing,
+        result.distance,
+    );
+
+    assert_relative_eq!(end.lat, dms(1., 21., 33.), epsilon = 1e-12);
+    assert_relative_eq!(end.lon, dms(103., 59., 22.), epsilon = 1e-12);
+    assert_eq!(end.bearing, result.bearing);
+}
+
+#[test]
+fn test_spherical_dover_to_calais() {
+    // Worked example from Chris Veness' rhumb line calculator, on a sphere of radius 6371 km
+    let result = rhumb::spherical_distance_and_bearing(
+        dms(51., 7., 32.),
+        dms(1., 20., 17.),
+        dms(50., 57., 48.),
+        dms(1., 51., 9.),
+    );
+
+    assert_relative_eq!(result.distance, 40.23e3, epsilon = 5.);
+    assert_relative_eq!(result.bearing, dms(116., 38., 10.), epsilon = 1e-5);
+
+    let end = rhumb::spherical_location_and_bearing(
+        dms(51., 7., 32.),
+        dms(1., 20., 17.),
+        result.bearing,
+        result.distance,
+    );
+
+    assert_relative_eq!(end.lat, dms(50., 57., 48.), epsilon = 1e-12);
+    assert_relative_eq!(end.lon, dms(1., 51., 9.), epsilon = 1e-12);
+}
+
+#[test]
+fn test_east_west_and_antimeridian() {
+    // Along a parallel the rhumb line is the parallel itself
+    let wgs84 = Ellipsoid::WGS84;
+    let lat = 30_f64.to_radians();
+    let parallel_radius = wgs84.prime_vertical_radius(lat) * lat.cos();
+
+    let east = rhumb::distance_and_bearing(lat, 179_f64.to_radians(), lat, -179_f64.to_radians());
+
+    assert_relative_eq!(
+        east.distance,
+        parallel_radius * 2_f64.to_radians(),
+        epsilon = 1e-6
+    );
+    assert_relative_eq!(east.bearing, FRAC_PI_2, epsilon = 1e-15);
+
+    let west = rhumb::distance_and_bearing(lat, -179_f64.to_radians(), lat, 179_f64.to_radians());
+
+    assert_relative_eq!(west.distance, east.distance, epsilon = 1e-6);
+    assert_relative_eq!(west.bearing, 3. * FRAC_PI_2, epsilon = 1e-15);
+
+    let end = rhumb::location_and_bearing(lat, 179_f64.to_radians(), FRAC_PI_2, east.distance);
+
+    assert_relative_eq!(end.lat, lat, epsilon = 1e-15);
+    assert_relative_eq!(end.lon, -179_f64.to_radians(), epsilon = 1e-14);
+
+    // A line only just off east-west is continuous with the parallel
+    let nearly = rhumb::distance_and_bearing(lat, 0., lat + 1e-12, 0.5);
+
+    assert_relative_eq!(nearly.distance, parallel_radius * 0.5, epsilon = 1e-4);
+}
+
+#[test]
+fn test_meridians_and_poles() {
+    let rhumb = Rhumb::new(&Ellipsoid::WGS84);
+
+    // Quarter meridian of WGS84
+    assert_relative_eq!(rhumb.meridian_arc(FRAC_PI_2), 10001965.729, epsilon = 1e-3);
+
+    let north = rhumb::distance_and_bearing(0.2, 0.3, 0.5, 0.3);
+
+    assert_relative_eq!(
+        north.distance,
+        rhumb.meridian_arc(0.5) - rhumb.meridian_arc(0.2),
+        epsilon = 1e-8
+    );
+    assert_eq!(north.bearing, 0.);
+
+    let south = rhumb::distance_and_bearing(0.5, 0.3, 0.2, 0.3);
+
+    assert_relative_eq!(south.distance, north.distance, epsilon = 1e-8);
+    assert_relative_eq!(south.bearing, PI, epsilon = 1e-15);
+
+    // Any line to the pole must head straight along the meridian
+    let pole = rhumb::distance_and_bearing(0.2, 0.3, FRAC_PI_2, 1.);
+
+    assert_relative_eq!(
+        pole.distance,
+        rhumb.meridian_arc(FRAC_PI_2) - rhumb.meridian_arc(0.2),
+        epsilon = 1e-8
+    );
+    assert_eq!(pole.bearing, 0.);
+
+    let end = rhumb::location_and_bearing(0.2, 0.3, 0., pole.distance);
+
+    assert_eq!(end.lat, FRAC_PI_2);
+
+    // Rhumb lines spiral into the pole rather than over it
+    let past = rhumb::location_and_bearing(0.2, 0.3, 0.1, 2e7);
+
+    assert!(past.lat.is_nan() && past.lon.is_nan());
+}
+
+#[test]
+fn test_sphere_isometric_latitude() {
+    // On a sphere psi reduces to the Mercator ln(tan(pi / 4 + lat / 2))
+    let rhumb = Rhumb::new(&Ellipsoid::SPHERE);
+
+    for lat in [-1.4, -0.6, 0., 0.3, 1.2] {
+        assert_relative_eq!(
+            rhumb.isometric_latitude(lat),
+            (PI / 4. + lat / 2.).tan().ln(),
+            epsilon = 1e-14
+        );
+    }
+    assert!(rhumb.isometric_latitude(FRAC_PI_2).is_infinite());
+}
@@ -0,0 +1,57 @@
+use std::f64::consts::TAU;
+
+use approx::assert_relative_eq;
+
+use crate::time::julian_date::JulianDate;
+use crate::time::sidereal::{earth_rotation_angle, gast_iau2006, gmst_iau2006, gmst_iau82};
+
+#[test]
+fn test_sofa_examples() {
+    // The SOFA test cases, with TT and UT1 both at MJD 53736
+    let date = JulianDate::new_from_mjd(53736.);
+
+    // iauGmst82
+    assert_relative_eq!(gmst_iau82(&date), 1.754174981860675, epsilon = 1e-12);
+    // iauGmst06
+    assert_relative_eq!(
+        gmst_iau2006(&date, &date),
+        1.754174971870091,
+        epsilon = 1e-12
+    );
+    // iauGst06a, which has the full 2000A nutation rather than 2000B
+    assert_relative_eq!(
+        gast_iau2006(&date, &date),
+        1.754166137675019,
+        epsilon = 1e-8
+    );
+    // iauEra00
+    let era = earth_rotation_angle(&JulianDate::new_from_mjd(54388.));
+    assert_relative_eq!(era, 0.4022837240028158, epsilon = 1e-12);
+}
+
+#[test]
+fn test_gmst() {
+    // Vallado, Fundamentals of Astrodynamics and Applications, example 3-5
+    let ut1 = JulianDate::new_from_calendar(1992, 8, 20, 12, 14, 0.);
+    assert_relative_eq!(gmst_iau82(&ut1).to_degrees(), 152.578787886, epsilon = 1e-7);
+
+    // The IAU 1982 and 2006 forms drift apart by only a few hundredths of an arcsecond a decade
+    for year in [1960, 1985, 2010, 2035] {
+        let ut1 = JulianDate::new_from_calendar(year, 3, 1, 6, 30, 0.);
+        let difference = (gmst_iau82(&ut1) - gmst_iau2006(&ut1, &ut1) + TAU / 2.).rem_euclid(TAU);
+        assert_relative_eq!(difference, TAU / 2., epsilon = 1e-6);
+    }
+
+    // Always within a single turn, before J2000 as well as after
+    for mjd in [36115.3, 51544.49, 51544.5, 60000.99] {
+        let ut1 = JulianDate::new_from_mjd(mjd);
+        for angle in [
+            gmst_iau82(&ut1),
+            gmst_iau2006(&ut1, &ut1),
+            gast_iau2006(&ut1, &ut1),
+            earth_rotation_angle(&ut1),
+        ] {
+            assert!((0. ..TAU).contains(&angle), "{} at {}", angle, mjd);
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use approx::assert_relative_eq;
+use chrono::NaiveDate;
+
+use crate::reference_frames::pef::{PEFVel, PEF};
+use crate::reference_frames::teme::{TEMEVel, TEME};
+use crate::time::epoch::Epoch;
+
+#[test]
+fn test_teme_to_pef() {
+    // Vallado, Crawford, Hujsak and Kelso, Revisiting Spacetrack Report #3, from the epoch of
+    // Vallado's example 3-15
+    let utc = NaiveDate::from_ymd_opt(2004, 4, 6)
+        .unwrap()
+        .and_hms_nano_opt(7, 51, 28, 386_009_000)
+        .unwrap();
+    let mut epoch = Epoch::new_from_utc(&utc);
+    epoch.set_delta_ut1(-0.4399619);
+
+    let teme = TEME {
+        x: 5094.1801621,
+        y: 6127.6446595,
+        z: 6380.3445327,
+    };
+    let teme_vel = TEMEVel {
+        x_vel: -4.746131487,
+        y_vel: 0.785818041,
+        z_vel: 5.531931288,
+    };
+
+    // The published vectors agree with SOFA's GMST to a fifth of a milliarcsecond, under a cm here
+    let pef = PEF::new_from_teme(&teme, epoch);
+    assert_relative_eq!(pef.x, -1033.4750313, epsilon = 1e-5);
+    assert_relative_eq!(pef.y, 7901.3055856, epsilon = 1e-5);
+    assert_relative_eq!(pef.z, 6380.3445328, epsilon = 1e-5);
+    let pef_vel = PEFVel::new_from_teme_vel(&teme, &teme_vel, epoch);
+    assert_relative_eq!(pef_vel.x_vel, -3.225632747, epsilon = 1e-7);
+    assert_relative_eq!(pef_vel.y_vel, -2.872442511, epsilon = 1e-7);
+    assert_relative_eq!(pef_vel.z_vel, 5.531931288, epsilon = 1e-7);
+
+    let back = TEME::new_from_pef(&pef, epoch);
+    assert_relative_eq!(back.x, teme.x, epsilon = 1e-8);
+    assert_relative_eq!(back.y, teme.y, epsilon = 1e-8);
+    assert_relative_eq!(back.z, teme.z, epsilon = 1e-8);
+    let back_vel = TEMEVel::new_from_pef_vel(&pef, &pef_vel, epoch);
+    assert_relative_eq!(back_vel.x_vel, teme_vel.x_vel, epsilon = 1e-11);
+    assert_relative_eq!(back_vel.y_vel, teme_vel.y_vel, epsilon = 1e-11);
+    assert_relative_eq!(back_vel.z_vel, teme_vel.z_vel, epsilon = 1e-11);
+}
@@ -0,0 +1,182 @@
+use approx::assert_relative_eq;
+use chrono::NaiveDate;
+
+use crate::time::epoch::{Epoch, TimeScale};
+use crate::tle::{
+    checksum, format_alpha5, load_tle_catalogue, parse_alpha5, parse_tle_catalogue, TLEError, TLE,
+};
+
+const ISS: &str = "\
+ISS (ZARYA)
+1 25544U 98067A   08264.51782528 -.00002182  00000-0 -11606-4 0  2927
+2 25544  51.6416 247.4627 0006703 130.5360 325.0288 15.72125391563537
+";
+
+// Vanguard 1 from the SGP4 verification set, and again under an Alpha-5 number
+const VANGUARD: [&str; 2] = [
+    "1 00005U 58002B   00179.78495062  .00000023  00000-0  28098-4 0  4753",
+    "2 00005  34.2682 348.7242 1859667 331.7664  19.3264 10.82419157413667",
+];
+
+#[test]
+fn test_parse_three_line() {
+    let tle = TLE::new_from_str(ISS).unwrap();
+    assert_eq!(tle.name.as_deref(), Some("ISS (ZARYA)"));
+    assert_eq!(tle.catalogue_number, 25544);
+    assert_eq!(tle.classification, 'U');
+    assert_eq!(tle.international_designator, "98067A");
+    assert_relative_eq!(tle.mean_motion_dot, -0.00002182);
+    assert_eq!(tle.mean_motion_ddot, 0.);
+    assert_relative_eq!(tle.bstar, -0.11606e-4, epsilon = 1e-18);
+    assert_eq!(tle.ephemeris_type, 0);
+    assert_eq!(tle.element_set_number, 292);
+    assert_relative_eq!(tle.inclination.to_degrees(), 51.6416, epsilon = 1e-12);
+    assert_relative_eq!(tle.raan.to_degrees(), 247.4627, epsilon = 1e-12);
+    assert_eq!(tle.eccentricity, 0.0006703);
+    assert_relative_eq!(
+        tle.argument_of_perigee.to_degrees(),
+        130.536,
+        epsilon = 1e-12
+    );
+    assert_relative_eq!(tle.mean_anomaly.to_degrees(), 325.0288, epsilon = 1e-12);
+    assert_eq!(tle.mean_motion, 15.72125391);
+    assert_eq!(tle.revolution_number, 56353);
+
+    // Day 264.51782528 of 2008
+    let expected = NaiveDate::from_ymd_opt(2008, 9, 20)
+        .unwrap()
+        .and_hms_micro_opt(12, 25, 40, 104_192)
+        .unwrap();
+    assert_eq!(tle.epoch, Epoch::new_from_utc(&expected));
+    assert_eq!(tle.epoch.get_datetime(TimeScale::UTC), expected);
+
+    // The same without the name, and with a Space-Track style "0 " name line
+    let lines: Vec<&str> = ISS.lines().collect();
+    let two_line = TLE::new_from_lines(lines[1], lines[2]).unwrap();
+    assert_eq!(two_line.name, None);
+    assert_eq!(two_line.epoch, tle.epoch);
+    let space_track = TLE::new_from_str(&format!("0 ISS (ZARYA)\n{}\n{}", lines[1], lines[2]));
+    assert_eq!(space_track.unwrap(), tle);
+}
+
+#[test]
+fn test_alpha5_and_exponents() {
+    let tle = TLE::new_from_lines(VANGUARD[0], VANGUARD[1]).unwrap();
+    assert_eq!(tle.catalogue_number, 5);
+    assert_eq!(tle.get_alpha5().as_deref(), Some("00005"));
+    assert_relative_eq!(tle.bstar, 0.28098e-4, epsilon = 1e-18);
+    assert_eq!(tle.eccentricity, 0.1859667);
+    assert_eq!(
+        tle.epoch.get_datetime(TimeScale::UTC).date(),
+        NaiveDate::from_ymd_opt(2000, 6, 27).unwrap()
+    );
+
+    // A letter in the first column doesn't change the checksum
+    let alpha5 = TLE::new_from_lines(
+        &VANGUARD[0].replacen("00005", "T0005", 1),
+        &VANGUARD[1].replacen("00005", "T0005", 1),
+    )
+    .unwrap();
+    assert_eq!(alpha5.catalogue_number, 270005);
+    assert_eq!(alpha5.get_alpha5().as_deref(), Some("T0005"));
+
+    assert_eq!(parse_alpha5("A0000"), Some(100000));
+    assert_eq!(parse_alpha5("J2345"), Some(182345));
+    assert_eq!(parse_alpha5("Z9999"), Some(339999));
+    assert_eq!(parse_alpha5("I0001"), None);
+    assert_eq!(parse_alpha5("O0001"), None);
+    assert_eq!(parse_alpha5("A001"), None);
+    assert_eq!(parse_alpha5("  123"), Some(123));
+    for number in [0, 99999, 100000, 179999, 180000, 229999, 230000, 339999] {
+        assert_eq!(parse_alpha5(&format_alpha5(number).unwrap()), Some(number));
+    }
+    assert_eq!(format_alpha5(339999).as_deref(), Some("Z9999"));
+    assert_eq!(format_alpha5(340000), None);
+
+    // Positive exponents and signs on the mantissa
+    let line1 = VANGUARD[0].replacen(" 28098-4", "+12345+1", 1);
+    let line1 = format!("{}{}", &line1[..68], checksum(&line1));
+    let tle = TLE::new_from_lines(&line1, VANGUARD[1]).unwrap();
+    assert_relative_eq!(tle.bstar, 1.2345, epsilon = 1e-15);
+}
+
+#[test]
+fn test_errors() {
+    let [line1, line2] = VANGUARD;
+
+    assert_eq!(
+        TLE::new_from_lines(&line1.replacen("4753", "4754", 1), line2).unwrap_err(),
+        TLEError::InvalidChecksum(1, 3, 4)
+    );
+    assert_eq!(
+        TLE::new_from_lines(&line1[..60], line2).unwrap_err(),
+        TLEError::InvalidLength(1, 60)
+    );
+    assert_eq!(
+        TLE::new_from_lines(line2, line1).unwrap_err(),
+        TLEError::UnexpectedLine(1, line2.to_string())
+    );
+
+    // A bad field with its checksum fixed up
+    let broken = line2.replacen("34.2682", "34.2x82", 1);
+    let broken = format!("{}{}", &broken[..68], checksum(&broken));
+    assert_eq!(
+        TLE::new_from_lines(line1, &broken).unwrap_err(),
+        TLEError::InvalidField(2, "inclination", "34.2x82".to_string())
+    );
+    let other = line2.replacen("00005", "00006", 1);
+    let other = format!("{}{}", &other[..68], checksum(&other));
+    assert_eq!(
+        TLE::new_from_lines(line1, &other).unwrap_err(),
+        TLEError::CatalogueNumberMismatch(2, 5, 6)
+    );
+    let day = line1.replacen("00179.78495062", "01366.50000000", 1);
+    let day = format!("{}{}", &day[..68], checksum(&day));
+    assert_eq!(
+        TLE::new_from_lines(&day, line2).unwrap_err(),
+        TLEError::InvalidField(1, "epoch", "01366.50000000".to_string())
+    );
+}
+
+#[test]
+fn test_catalogue() {
+    let [line1, line2] = VANGUARD;
+    let bad = line1.replacen("4753", "4750", 1);
+    let contents = format!(
+        "{}\nVANGUARD 1\n{}\n{}\n\n{}\n{}\n{}\nORPHAN\n",
+        ISS.trim_end(),
+        line1,
+        line2,
+        bad,
+        line2,
+        line2
+    );
+
+    let sets = parse_tle_catalogue(&contents);
+    assert_eq!(sets.len(), 5);
+    assert_eq!(sets[0].as_ref().unwrap().catalogue_number, 25544);
+    assert_eq!(
+        sets[1].as_ref().unwrap().name.as_deref(),
+        Some("VANGUARD 1")
+    );
+    // Line numbers count the blank line
+    assert_eq!(sets[2], Err(TLEError::InvalidChecksum(8, 3, 0)));
+    assert_eq!(
+        sets[3],
+        Err(TLEError::UnexpectedLine(10, line2.to_string()))
+    );
+    assert_eq!(sets[4], Err(TLEError::MissingLine(11)));
+
+    let path = std::env::temp_dir().join("crate_test_catalogue.tle");
+    std::fs::write(&path, &contents).unwrap();
+    assert_eq!(load_tle_catalogue(&path).unwrap(), sets);
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(
+        load_tle_catalogue("/this/file/does/not/exist"),
+        Err(TLEError::Io(_))
+    ));
+    assert_eq!(
+        TLEError::InvalidChecksum(8, 3, 0).to_string(),
+        "checksum on line 8 is 0, should be 3"
+    );
+}
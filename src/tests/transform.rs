@@ -0,0 +1,95 @@
+use approx::assert_relative_eq;
+use chrono::DateTime;
+
+use crate::matrix::Vec3;
+use crate::reference_frames::ecef::ECEF;
+use crate::reference_frames::enu::ENU;
+use crate::reference_frames::ned::NED;
+use crate::reference_frames::pef::PEF;
+use crate::reference_frames::teme::TEME;
+use crate::reference_frames::transform::{Frame, FrameError, FrameGraph, FrameId};
+use crate::reference_frames::wgs84::WGS84Coord;
+use crate::time::epoch::Epoch;
+
+#[test]
+fn test_teme_to_ned_matches_chain() {
+    let utc_time = DateTime::from_timestamp(1_700_000_000, 0)
+        .unwrap()
+        .naive_utc();
+    let reference_point = WGS84Coord::new_from_degrees(-31.9, 115.9, 20.);
+    let teme = TEME {
+        x: 5094180.16,
+        y: 6127644.65,
+        z: 6380344.53,
+    };
+
+    // The long way round
+    let pef = PEF::new_from_teme(&teme, utc_time);
+    let ecef = ECEF::new_from_pef(&pef, utc_time);
+    let expected = NED::new_from_ecef(&ecef, &reference_point);
+
+    let epoch = Epoch::new_from_utc(&utc_time);
+    let graph = FrameGraph::new(Some(&epoch), Some(&reference_point));
+    let transform = graph.transform(FrameId::TEME, FrameId::NED).unwrap();
+    let ned: NED = transform.convert(&teme);
+
+    assert_eq!(transform.get_from(), FrameId::TEME);
+    assert_eq!(transform.get_to(), FrameId::NED);
+    assert_relative_eq!(ned.n, expected.n, epsilon = 1e-6);
+    assert_relative_eq!(ned.e, expected.e, epsilon = 1e-6);
+    assert_relative_eq!(ned.d, expected.d, epsilon = 1e-6);
+
+    // And back again
+    let back: TEME = graph.convert(&ned).unwrap();
+    assert_relative_eq!(back.x, teme.x, epsilon = 1e-6);
+    assert_relative_eq!(back.y, teme.y, epsilon = 1e-6);
+    assert_relative_eq!(back.z, teme.z, epsilon = 1e-6);
+
+    // Between the two local frames the rotation is a fixed swap of axes
+    let enu: ENU = graph.convert(&ned).unwrap();
+    assert_relative_eq!(enu.e, ned.e, epsilon = 1e-6);
+    assert_relative_eq!(enu.n, ned.n, epsilon = 1e-6);
+    assert_relative_eq!(enu.u, -ned.d, epsilon = 1e-6);
+
+    let velocity = graph
+        .transform(FrameId::NED, FrameId::ENU)
+        .unwrap()
+        .apply_to_direction(&Vec3::new(1., 2., 3.));
+    assert_relative_eq!(velocity.x, 2., epsilon = 1e-12);
+    assert_relative_eq!(velocity.y, 1., epsilon = 1e-12);
+    assert_relative_eq!(velocity.z, -3., epsilon = 1e-12);
+}
+
+#[test]
+fn test_missing_context() {
+    let reference_point = WGS84Coord::new_from_degrees(51.5, 0., 0.);
+    let graph = FrameGraph::new_from_reference_point(&reference_point);
+
+    // Earth fixed to local needs no time
+    let ecef = ECEF::new_from_wgs84(&WGS84Coord::new_from_degrees(51.5, 0., 1000.));
+    let ned: NED = graph.convert(&ecef).unwrap();
+    assert_relative_eq!(ned.d, -1000., epsilon = 1e-8);
+
+    let result: Result<TEME, FrameError> = graph.convert(&ned);
+    assert_eq!(
+        result.unwrap_err(),
+        FrameError::MissingTime(FrameId::NED, FrameId::TEME)
+    );
+
+    let utc_time = DateTime::from_timestamp(0, 0).unwrap().naive_utc();
+    let graph = FrameGraph::new_from_time(utc_time);
+    assert!(graph.transform(FrameId::TEME, FrameId::ECEF).is_ok());
+    assert_eq!(
+        graph.transform(FrameId::TEME, FrameId::ENU).unwrap_err(),
+        FrameError::MissingReferencePoint(FrameId::TEME, FrameId::ENU)
+    );
+
+    let identity = graph.transform(FrameId::PEF, FrameId::PEF).unwrap();
+    let pef = PEF {
+        x: 1.,
+        y: 2.,
+        z: 3.,
+    };
+    let same: PEF = identity.convert(&pef);
+    assert_eq!(same.to_vec3(), pef.to_vec3());
+}
@@ -0,0 +1,183 @@
+use approx::assert_relative_eq;
+
+use crate::projections::utm::{forward, reverse, standard_zone, Hemisphere, UTMCoord, UTMError};
+use crate::reference_frames::wgs84::WGS84Coord;
+
+#[test]
+fn test_utm_known_points() {
+    // GeoConvert documentation example: 33.3N 44.4E -> 38n 444140.54 3684706.36
+    let utm = UTMCoord::new_from_wgs84(&WGS84Coord::new_from_degrees(33.3, 44.4, 0.0)).unwrap();
+
+    assert_eq!(utm.get_zone(), 38);
+    assert_eq!(utm.get_hemisphere(), Hemisphere::North);
+    assert_relative_eq!(utm.get_easting(), 444140.54, epsilon = 5e-3);
+    assert_relative_eq!(utm.get_northing(), 3684706.36, epsilon = 5e-3);
+
+    // On the equator at a central meridian
+    let utm = UTMCoord::new_from_wgs84(&WGS84Coord::new_from_degrees(0.0, 3.0, 0.0)).unwrap();
+
+    assert_eq!(utm.get_zone(), 31);
+    assert_relative_eq!(utm.get_easting(), 500000.0, epsilon = 1e-9);
+    assert_relative_eq!(utm.get_northing(), 0.0, epsilon = 1e-9);
+
+    // Southern hemisphere gets the 10,000 km false northing
+    let utm = UTMCoord::new_from_wgs84(&WGS84Coord::new_from_degrees(-33.9, 18.4, 0.0)).unwrap();
+
+    assert_eq!(utm.get_zone(), 34);
+    assert_eq!(utm.get_hemisphere(), Hemisphere::South);
+    assert!(utm.get_northing() > 6000e3 && utm.get_northing() < 6500e3);
+}
+
+#[test]
+fn test_utm_round_trip() {
+    for &(lat, lon) in [
+        (51.5, -0.12),
+        (-45.0, 170.5),
+        (60.0, 5.5),
+        (78.0, 15.0),
+        (0.5, -179.9),
+    ]
+    .iter()
+    {
+        let point = WGS84Coord::new_from_degrees(lat, lon, 0.0);
+        let utm = UTMCoord::new_from_wgs84(&point).unwrap();
+        let back = WGS84Coord::new_from_utm(&utm);
+
+        // 1e-14 rad is well under a nanometre
+        assert_relative_eq!(
+            back.get_lat_radians(),
+            point.get_lat_radians(),
+            epsilon = 1e-14
+        );
+        assert_relative_eq!(
+            back.get_lon_radians(),
+            point.get_lon_radians(),
+            epsilon = 1e-14
+        );
+    }
+
+    // Forcing the neighbouring zone still round trips
+    let point = WGS84Coord::new_from_degrees(45.0, 13.5, 0.0);
+    let utm = UTMCoord::new_from_wgs84_zone(&point, 32).unwrap();
+    let back = WGS84Coord::new_from_utm(&utm);
+
+    assert_eq!(utm.get_zone(), 32);
+
+    assert_relative_eq!(
+        back.get_lat_radians(),
+        point.get_lat_radians(),
+        epsilon = 1e-12
+    );
+    assert_relative_eq!(
+        back.get_lon_radians(),
+        point.get_lon_radians(),
+        epsilon = 1e-12
+    );
+}
+
+#[test]
+fn test_zone_exceptions() {
+    let zone = |lat: f64, lon: f64| standard_zone(&WGS84Coord::new_from_degrees(lat, lon, 0.0));
+
+    assert_eq!(zone(50.0, 4.0), Ok(31));
+    // Norway
+    assert_eq!(zone(60.0, 4.0), Ok(32));
+    assert_eq!(zone(60.0, 2.0), Ok(31));
+    // Svalbard
+    assert_eq!(zone(78.0, 8.0), Ok(31));
+    assert_eq!(zone(78.0, 10.0), Ok(33));
+    assert_eq!(zone(78.0, 25.0), Ok(35));
+    assert_eq!(zone(78.0, 40.0), Ok(37));
+    // Polar regions
+    assert_eq!(zone(84.0, 0.0), Ok(0));
+    assert_eq!(zone(-80.5, 0.0), Ok(0));
+    assert_eq!(zone(-79.5, 0.0), Ok(31));
+    // Antimeridian
+    assert_eq!(zone(0.0, 180.0), Ok(1));
+    assert_eq!(zone(0.0, -180.0), Ok(1));
+    assert_eq!(zone(0.0, 179.9), Ok(60));
+
+    assert_eq!(zone(91.0, 0.0), Err(UTMError::InvalidLatitude(91.0)));
+}
+
+#[test]
+fn test_ups() {
+    // The poles sit at the UPS false origin
+    let pole = UTMCoord::new_from_wgs84(&WGS84Coord::new_from_degrees(90.0, 0.0, 0.0)).unwrap();
+
+    assert!(pole.is_ups());
+    assert_eq!(pole.get_hemisphere(), Hemisphere::North);
+    assert_relative_eq!(pole.get_easting(), 2000e3, epsilon = 1e-6);
+    assert_relative_eq!(pole.get_northing(), 2000e3, epsilon = 1e-6);
+
+    let point = WGS84Coord::new_from_degrees(-85.0, 45.0, 0.0);
+    let ups = forward(&point, None).unwrap();
+
+    assert!(ups.utm.is_ups());
+    assert_eq!(ups.utm.get_hemisphere(), Hemisphere::South);
+    // South of the pole the grid convergence is minus the longitude
+    assert_relative_eq!(ups.convergence, -45_f64.to_radians(), epsilon = 1e-12);
+
+    let back = reverse(&ups.utm);
+
+    assert_relative_eq!(
+        back.coord.get_lat_radians(),
+        point.get_lat_radians(),
+        epsilon = 1e-13
+    );
+    assert_relative_eq!(
+        back.coord.get_lon_radians(),
+        point.get_lon_radians(),
+        epsilon = 1e-13
+    );
+    assert_relative_eq!(back.scale, ups.scale, epsilon = 1e-12);
+
+    // 81.1 degrees is where UPS scale returns to one
+    let ups = forward(&WGS84Coord::new_from_degrees(81.114528, 0.0, 0.0), Some(0)).unwrap();
+
+    assert_relative_eq!(ups.scale, 1.0, epsilon = 1e-6);
+}
+
+#[test]
+fn test_convergence_and_scale() {
+    // Central meridian: no convergence and the central scale factor
+    let on_meridian = forward(&WGS84Coord::new_from_degrees(40.0, 9.0, 0.0), None).unwrap();
+
+    assert_relative_eq!(on_meridian.convergence, 0.0, epsilon = 1e-15);
+    assert_relative_eq!(on_meridian.scale, 0.9996, epsilon = 1e-15);
+
+    // East of the central meridian in the north grid north is east of true north
+    // convergence ~ dlon * sin(lat) to first order
+    let point = WGS84Coord::new_from_degrees(40.0, 11.0, 0.0);
+    let east = forward(&point, None).unwrap();
+
+    assert!(east.convergence > 0.0);
+    assert_relative_eq!(
+        east.convergence,
+        2_f64.to_radians() * 40_f64.to_radians().sin(),
+        epsilon = 1e-4
+    );
+    assert!(east.scale > 0.9996);
+
+    let back = reverse(&east.utm);
+
+    assert_relative_eq!(back.convergence, east.convergence, epsilon = 1e-14);
+    assert_relative_eq!(back.scale, east.scale, epsilon = 1e-14);
+}
+
+#[test]
+fn test_utm_validation() {
+    assert_eq!(
+        UTMCoord::new(61, Hemisphere::North, 500e3, 0.0),
+        Err(UTMError::InvalidZone(61))
+    );
+    assert_eq!(
+        UTMCoord::new(31, Hemisphere::North, 1500e3, 0.0),
+        Err(UTMError::EastingOutOfRange(1500e3))
+    );
+    assert_eq!(
+        UTMCoord::new(31, Hemisphere::South, 500e3, 500e3),
+        Err(UTMError::NorthingOutOfRange(500e3))
+    );
+    assert!(UTMCoord::new(0, Hemisphere::North, 2000e3, 2000e3).is_ok());
+}
@@ -0,0 +1,113 @@
+use approx::assert_relative_eq;
+
+use crate::ellipsoid::Ellipsoid;
+use crate::geodesics::{karney, vincenty};
+
+fn dms_to_radians(degrees: f64, minutes: f64, seconds: f64) -> f64 {
+    return (degrees.abs() + minutes / 60. + seconds / 3600.)
+        .copysign(degrees)
+        .to_radians();
+}
+
+#[test]
+fn test_inverse_flinders_peak_to_buninyong() {
+    // Geoscience Australia's worked example on GRS80
+    let lat1 = dms_to_radians(-37., 57., 3.72030);
+    let lon1 = dms_to_radians(144., 25., 29.52440);
+    let lat2 = dms_to_radians(-37., 39., 10.15610);
+    let lon2 = dms_to_radians(143., 55., 35.38390);
+
+    let inverse =
+        vincenty::try_inverse_on_ellipsoid(lat1, lon1, lat2, lon2, &Ellipsoid::GRS80).unwrap();
+
+    assert_relative_eq!(inverse.distance, 54972.271, epsilon = 1e-3);
+    assert_relative_eq!(
+        inverse.initial_bearing + 2. * std::f64::consts::PI,
+        dms_to_radians(306., 52., 5.37),
+        epsilon = 1e-7
+    );
+    // The published reverse azimuth is the final bearing turned around
+    assert_relative_eq!(
+        inverse.final_bearing + std::f64::consts::PI,
+        dms_to_radians(127., 10., 25.07),
+        epsilon = 1e-7
+    );
+    assert!(!inverse.karney_fallback);
+}
+
+#[test]
+fn test_inverse_coincident_points() {
+    let inverse = vincenty::try_inverse(0.3, 0.2, 0.3, 0.2).unwrap();
+
+    assert_eq!(inverse.distance, 0.);
+    assert_eq!(inverse.initial_bearing, 0.);
+}
+
+#[test]
+fn test_inverse_equatorial() {
+    // A quarter of the equator, where cos^2(alpha) is zero
+    let inverse = vincenty::try_inverse(0., 0., 0., std::f64::consts::FRAC_PI_2).unwrap();
+
+    assert_relative_eq!(inverse.distance, 10018754.171, epsilon = 1e-3);
+    assert_relative_eq!(
+        inverse.final_bearing,
+        std::f64::consts::FRAC_PI_2,
+        epsilon = 1e-12
+    );
+}
+
+#[test]
+fn test_inverse_nearly_antipodal_falls_back_to_karney() {
+    let lat2 = 0.5_f64.to_radians();
+    let lon2 = 179.7_f64.to_radians();
+
+    assert!(matches!(
+        vincenty::try_inverse(0., 0., lat2, lon2),
+        Err(vincenty::VincentyError::DidNotConverge(_))
+    ));
+
+    let inverse = vincenty::inverse(0., 0., lat2, lon2);
+    let karney = karney::inverse(0., 0., lat2, lon2);
+
+    assert!(inverse.karney_fallback);
+    assert_relative_eq!(inverse.distance, karney.distance, epsilon = 1e-9);
+    assert_relative_eq!(inverse.initial_bearing, karney.azimuth1, epsilon = 1e-12);
+    assert_relative_eq!(inverse.final_bearing, karney.azimuth2, epsilon = 1e-12);
+
+    // Exactly antipodal points never converge either, but the result is still usable
+    let result = vincenty::distance_and_bearing(0., 0., 0., std::f64::consts::PI);
+    assert_relative_eq!(result.distance, 20003931.459, epsilon = 1e-3);
+}
+
+#[test]
+fn test_direct_flinders_peak_to_buninyong() {
+    let lat1 = dms_to_radians(-37., 57., 3.72030);
+    let lon1 = dms_to_radians(144., 25., 29.52440);
+    let bearing = dms_to_radians(306., 52., 5.37);
+
+    let direct = vincenty::location_and_bearing_on_ellipsoid(
+        lat1,
+        lon1,
+        bearing,
+        54972.271,
+        &Ellipsoid::GRS80,
+    );
+
+    assert_relative_eq!(
+        direct.lat,
+        dms_to_radians(-37., 39., 10.15610),
+        epsilon = 1e-9
+    );
+    assert_relative_eq!(
+        direct.lon,
+        dms_to_radians(143., 55., 35.38390),
+        epsilon = 1e-9
+    );
+
+    // Along the equator and across the antimeridian, where the C term matters most
+    let direct = vincenty::location_and_bearing(0., 3.1, 0.5 * std::f64::consts::PI, 5e6);
+    let expected = karney::location_and_bearing(0., 3.1, 0.5 * std::f64::consts::PI, 5e6);
+
+    assert_relative_eq!(direct.lat, expected.lat, epsilon = 1e-12);
+    assert_relative_eq!(direct.lon, expected.lon, epsilon = 1e-10);
+}
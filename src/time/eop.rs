@@ -0,0 +1,365 @@
+use std::{f64::consts::PI, fmt, fs, path::Path, sync::RwLock};
+
+use crate::{
+    constants::ARCSEC_TO_RAD,
+    types::{Radians, Seconds},
+};
+
+use super::{epoch::Epoch, leap_seconds::tai_minus_utc};
+
+// Shared by every conversion, like the leap second table. None until a file is loaded
+static EOP_TABLE: RwLock<Option<EOPTable>> = RwLock::new(None);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EOPFormat {
+    // finals2000A.all, finals2000A.data or finals2000A.daily, the fixed column IERS format
+    Finals2000A,
+    // The weekly Bulletin A text (ser7.dat), reading its rapid service and prediction tables
+    BulletinA,
+    // EOP 14 C04 or EOP 20 C04, whitespace separated
+    C04,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EOPSource {
+    Observed,
+    Predicted,
+    // Outside the table, or with no table loaded, from the harmonic polar motion model
+    Model,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EOPError {
+    Io(String),
+    // Line number (from 1) and contents of a line that couldn't be read
+    InvalidLine(usize, String),
+    NotIncreasing(usize),
+    Empty,
+}
+
+impl fmt::Display for EOPError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EOPError::Io(err) => write!(f, "couldn't read EOP file: {}", err),
+            EOPError::InvalidLine(n, line) => {
+                write!(f, "invalid EOP entry on line {}: {}", n, line)
+            }
+            EOPError::NotIncreasing(n) => write!(f, "EOP dates out of order on line {}", n),
+            EOPError::Empty => write!(f, "no EOP entries found"),
+        }
+    }
+}
+
+impl std::error::Error for EOPError {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EOPRecord {
+    // One day of Earth orientation, at 0h UTC on the MJD
+    pub mjd: f64,
+    pub xp: Radians,
+    pub yp: Radians,
+    pub delta_ut1: Seconds,
+    // Excess length of day
+    pub lod: Seconds,
+    // Celestial pole offsets from the IAU 2006/2000A precession-nutation
+    pub dx: Radians,
+    pub dy: Radians,
+    pub predicted: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EarthOrientation {
+    // The parameters at an instant, interpolated from the table or modelled
+    pub xp: Radians,
+    pub yp: Radians,
+    pub delta_ut1: Seconds,
+    pub lod: Seconds,
+    pub dx: Radians,
+    pub dy: Radians,
+    pub source: EOPSource,
+}
+
+impl EarthOrientation {
+    pub fn new_from_model(utc_mjd: f64) -> EarthOrientation {
+        // The long term polar motion prediction formula from IERS Bulletin A, fitted around
+        // MJD 57226 (2015). Good to a few metres on the ground for a few years either side
+        // UT1 - UTC can't be predicted this way so is left at zero, as are LOD and dX/dY
+        let a = 2. * PI * (utc_mjd - 57226.) / 365.25;
+        let c = 2. * PI * (utc_mjd - 57226.) / 435.;
+
+        let xp = 0.1033 + 0.0494 * a.cos() + 0.0482 * a.sin() + 0.0297 * c.cos() + 0.0307 * c.sin();
+        let yp = 0.3498 + 0.0441 * a.cos() - 0.0393 * a.sin() + 0.0307 * c.cos() - 0.0297 * c.sin();
+
+        return EarthOrientation {
+            xp: xp * ARCSEC_TO_RAD,
+            yp: yp * ARCSEC_TO_RAD,
+            delta_ut1: 0.,
+            lod: 0.,
+            dx: 0.,
+            dy: 0.,
+            source: EOPSource::Model,
+        };
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EOPTable {
+    // Daily records in increasing MJD order
+    records: Vec<EOPRecord>,
+}
+
+impl EOPTable {
+    pub fn new(records: Vec<EOPRecord>) -> Result<EOPTable, EOPError> {
+        if records.is_empty() {
+            return Err(EOPError::Empty);
+        }
+        for (i, pair) in records.windows(2).enumerate() {
+            if pair[1].mjd <= pair[0].mjd {
+                return Err(EOPError::NotIncreasing(i + 2));
+            }
+        }
+        return Ok(EOPTable { records });
+    }
+
+    pub fn new_from_str(contents: &str, format: EOPFormat) -> Result<EOPTable, EOPError> {
+        let records = match format {
+            EOPFormat::Finals2000A => parse_finals2000a(contents)?,
+            EOPFormat::BulletinA => parse_bulletin_a(contents)?,
+            EOPFormat::C04 => parse_c04(contents)?,
+        };
+        return EOPTable::new(records);
+    }
+
+    pub fn new_from_file<P: AsRef<Path>>(path: P, format: EOPFormat) -> Result<EOPTable, EOPError> {
+        let contents = fs::read_to_string(path).map_err(|err| EOPError::Io(err.to_string()))?;
+        return EOPTable::new_from_str(&contents, format);
+    }
+
+    pub fn get_records(&self) -> &[EOPRecord] {
+        return &self.records;
+    }
+
+    pub fn get_coverage(&self) -> (f64, f64) {
+        // First and last MJD in the table
+        return (
+            self.records[0].mjd,
+            self.records[self.records.len() - 1].mjd,
+        );
+    }
+
+    pub fn get(&self, utc_mjd: f64) -> EarthOrientation {
+        // Linear interpolation between the daily values, the model outside them
+        let (first, last) = self.get_coverage();
+        if !(first..=last).contains(&utc_mjd) {
+            return EarthOrientation::new_from_model(utc_mjd);
+        }
+
+        let i = self.records.partition_point(|r| r.mjd <= utc_mjd);
+        if i == self.records.len() {
+            return interpolate(&self.records[i - 1], &self.records[i - 1], utc_mjd);
+        }
+        return interpolate(&self.records[i - 1], &self.records[i], utc_mjd);
+    }
+}
+
+pub fn get_eop(epoch: &Epoch) -> EarthOrientation {
+    return get_eop_at_mjd(epoch.get_utc_mjd());
+}
+
+pub fn get_eop_at_mjd(utc_mjd: f64) -> EarthOrientation {
+    // From the loaded table, or the model if there isn't one
+    return match EOP_TABLE.read().unwrap().as_ref() {
+        Some(table) => table.get(utc_mjd),
+        None => EarthOrientation::new_from_model(utc_mjd),
+    };
+}
+
+pub fn set_eop_table(table: EOPTable) {
+    // Replaces the table for the whole program
+    *EOP_TABLE.write().unwrap() = Some(table);
+}
+
+pub fn reset_eop_table() {
+    // Go back to the model
+    *EOP_TABLE.write().unwrap() = None;
+}
+
+pub fn load_eop_file<P: AsRef<Path>>(path: P, format: EOPFormat) -> Result<usize, EOPError> {
+    // Reads a local copy of an IERS file and uses it from now on, returning the number of days
+    let table = EOPTable::new_from_file(path, format)?;
+    let len = table.records.len();
+    set_eop_table(table);
+    return Ok(len);
+}
+
+pub fn parse_finals2000a(contents: &str) -> Result<Vec<EOPRecord>, EOPError> {
+    // Columns from readme.finals2000A, using the Bulletin A values which are always filled in
+    // The end of the file has dates with no values yet, which are left out
+    let mut records = Vec::new();
+
+    for (i, line) in contents.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let invalid = || EOPError::InvalidLine(i + 1, line.to_string());
+        let field = |start: usize, end: usize| -> Result<&str, EOPError> {
+            let end = end.min(line.len());
+            if start > end {
+                return Ok("");
+            }
+            return Ok(line.get(start - 1..end).ok_or_else(invalid)?.trim());
+        };
+        let number = |start: usize, end: usize| -> Result<Option<f64>, EOPError> {
+            let text = field(start, end)?;
+            if text.is_empty() {
+                return Ok(None);
+            }
+            return Ok(Some(text.parse().map_err(|_| invalid())?));
+        };
+
+        let mjd = number(8, 15)?.ok_or_else(invalid)?;
+        let (Some(xp), Some(yp), Some(delta_ut1)) =
+            (number(19, 27)?, number(38, 46)?, number(59, 68)?)
+        else {
+            continue;
+        };
+        let lod = number(80, 86)?.unwrap_or(0.);
+        let dx = number(98, 106)?.unwrap_or(0.);
+        let dy = number(117, 125)?.unwrap_or(0.);
+        let predicted = field(17, 17)? == "P" || field(58, 58)? == "P";
+
+        records.push(EOPRecord {
+            mjd,
+            xp: xp * ARCSEC_TO_RAD,
+            yp: yp * ARCSEC_TO_RAD,
+            delta_ut1,
+            lod: lod * 1e-3,
+            dx: dx * 1e-3 * ARCSEC_TO_RAD,
+            dy: dy * 1e-3 * ARCSEC_TO_RAD,
+            predicted,
+        });
+        check_increasing(&records, i)?;
+    }
+
+    return Ok(records);
+}
+
+pub fn parse_bulletin_a(contents: &str) -> Result<Vec<EOPRecord>, EOPError> {
+    // Bulletin A is mostly prose, so only lines that are entirely numbers and shaped like a row of
+    // the rapid service table (year month day MJD x error y error UT1-UTC error) or the prediction
+    // table (year month day MJD x y UT1-UTC) are read. It has no LOD and its dX/dY are left out
+    let mut records = Vec::new();
+
+    for (i, line) in contents.lines().enumerate() {
+        let Some(values) = numbers(line) else {
+            continue;
+        };
+        let (mjd, xp, yp, delta_ut1, predicted) = match values.len() {
+            10 => (values[3], values[4], values[6], values[8], false),
+            7 => (values[3], values[4], values[5], values[6], true),
+            _ => continue,
+        };
+
+        records.push(EOPRecord {
+            mjd,
+            xp: xp * ARCSEC_TO_RAD,
+            yp: yp * ARCSEC_TO_RAD,
+            delta_ut1,
+            lod: 0.,
+            dx: 0.,
+            dy: 0.,
+            predicted,
+        });
+        check_increasing(&records, i)?;
+    }
+
+    return Ok(records);
+}
+
+pub fn parse_c04(contents: &str) -> Result<Vec<EOPRecord>, EOPError> {
+    // EOP 14 C04 rows are year month day MJD x y UT1-UTC LOD dX dY and six errors, EOP 20 C04
+    // rows are year month day hour MJD x y UT1-UTC dX dY xrt yrt LOD and eight errors
+    // Angles are in arcseconds. Header lines are the ones that don't start with a number
+    let mut records = Vec::new();
+
+    for (i, line) in contents.lines().enumerate() {
+        let is_record = match line.split_whitespace().next() {
+            Some(first) => first.parse::<i32>().is_ok(),
+            None => false,
+        };
+        if !is_record {
+            continue;
+        }
+        let invalid = || EOPError::InvalidLine(i + 1, line.to_string());
+        let values = numbers(line).ok_or_else(invalid)?;
+
+        let (mjd, xp, yp, delta_ut1, lod, dx, dy) = match values.len() {
+            16 => (
+                values[3], values[4], values[5], values[6], values[7], values[8], values[9],
+            ),
+            21 => (
+                values[4], values[5], values[6], values[7], values[12], values[8], values[9],
+            ),
+            _ => return Err(invalid()),
+        };
+
+        records.push(EOPRecord {
+            mjd,
+            xp: xp * ARCSEC_TO_RAD,
+            yp: yp * ARCSEC_TO_RAD,
+            delta_ut1,
+            lod,
+            dx: dx * ARCSEC_TO_RAD,
+            dy: dy * ARCSEC_TO_RAD,
+            predicted: false,
+        });
+        check_increasing(&records, i)?;
+    }
+
+    return Ok(records);
+}
+
+fn numbers(line: &str) -> Option<Vec<f64>> {
+    // Every whitespace separated field of the line as a number, or None if any isn't one
+    let values: Option<Vec<f64>> = line.split_whitespace().map(|f| f.parse().ok()).collect();
+    return values.filter(|values| !values.is_empty());
+}
+
+fn check_increasing(records: &[EOPRecord], line_index: usize) -> Result<(), EOPError> {
+    if let [.., previous, last] = records {
+        if last.mjd <= previous.mjd {
+            return Err(EOPError::NotIncreasing(line_index + 1));
+        }
+    }
+    return Ok(());
+}
+
+fn interpolate(a: &EOPRecord, b: &EOPRecord, utc_mjd: f64) -> EarthOrientation {
+    let t = if b.mjd > a.mjd {
+        (utc_mjd - a.mjd) / (b.mjd - a.mjd)
+    } else {
+        0.
+    };
+    let lerp = |x: f64, y: f64| x + (y - x) * t;
+
+    // UT1 - UTC jumps by a second at a leap second, UT1 - TAI doesn't
+    let ut1_tai_a = a.delta_ut1 - tai_minus_utc(a.mjd);
+    let ut1_tai_b = b.delta_ut1 - tai_minus_utc(b.mjd);
+    let delta_ut1 = lerp(ut1_tai_a, ut1_tai_b) + tai_minus_utc(utc_mjd);
+
+    let source = if a.predicted || b.predicted {
+        EOPSource::Predicted
+    } else {
+        EOPSource::Observed
+    };
+
+    return EarthOrientation {
+        xp: lerp(a.xp, b.xp),
+        yp: lerp(a.yp, b.yp),
+        delta_ut1,
+        lod: lerp(a.lod, b.lod),
+        dx: lerp(a.dx, b.dx),
+        dy: lerp(a.dy, b.dy),
+        source,
+    };
+}
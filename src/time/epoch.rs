@@ -0,0 +1,284 @@
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+
+use crate::types::Seconds;
+
+use super::{
+    eop::get_eop_at_mjd,
+    leap_seconds::{tai_minus_utc, tai_minus_utc_at_tai},
+};
+
+pub const SECONDS_PER_DAY: Seconds = 86400.;
+// TT - TAI, exact by definition
+pub const TT_MINUS_TAI: Seconds = 32.184;
+// TAI - GPS, fixed when GPS time started in 1980
+pub const TAI_MINUS_GPS: Seconds = 19.;
+
+// Number of fixed point iterations for scales defined in terms of themselves, each gains about 8 digits
+const TDB_ITERATIONS: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeScale {
+    UTC,
+    TAI,
+    TT,
+    GPS,
+    UT1,
+    TDB,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Epoch {
+    // An instant held as TAI whole days and seconds since 2000-01-01 12:00:00 TAI, which keeps
+    // sub-nanosecond resolution for centuries either side. UT1 also needs UT1 - UTC, which the
+    // IERS publishes (Bulletin A) and changes too irregularly to model. Unless it's given it comes
+    // from the loaded EOP table, or is zero without one
+    tai_days: i64,
+    tai_seconds: Seconds,
+    delta_ut1: Option<Seconds>,
+}
+
+impl Epoch {
+    pub fn new_from_datetime(datetime: &NaiveDateTime, scale: TimeScale) -> Epoch {
+        // A calendar date and time read in the given scale. For UT1 use new_from_ut1 to give UT1 - UTC
+        // A leap second in UTC is written as chrono does, 23:59:59 with a nanosecond past 1e9
+        return Epoch::new_from_datetime_and_delta_ut1(datetime, scale, None);
+    }
+
+    pub fn new_from_utc(datetime: &NaiveDateTime) -> Epoch {
+        return Epoch::new_from_datetime(datetime, TimeScale::UTC);
+    }
+
+    pub fn new_from_ut1(datetime: &NaiveDateTime, delta_ut1: Seconds) -> Epoch {
+        return Epoch::new_from_datetime_and_delta_ut1(datetime, TimeScale::UT1, Some(delta_ut1));
+    }
+
+    pub fn new_from_seconds(seconds_since_j2000: Seconds, scale: TimeScale) -> Epoch {
+        // Seconds since 2000-01-01 12:00:00 in the given scale. For UTC and UT1 every day is
+        // counted as 86400 s, so these are not SI seconds across a leap second
+        let days = (seconds_since_j2000 / SECONDS_PER_DAY).floor();
+        let seconds = seconds_since_j2000 - days * SECONDS_PER_DAY;
+        return Epoch::new_from_scale_days(days as i64, seconds, scale, None);
+    }
+
+    pub fn new_from_gps_week(week: u32, seconds_of_week: Seconds) -> Epoch {
+        // Weeks since 1980-01-06 00:00:00 GPS, counted continuously without the 1024 week rollover
+        let days = gps_epoch_days() + 7 * week as i64;
+        let seconds = seconds_of_week + SECONDS_PER_DAY / 2.;
+        return Epoch::new_from_scale_days(days, seconds, TimeScale::GPS, None);
+    }
+
+    pub fn set_delta_ut1(&mut self, delta_ut1: Seconds) {
+        // UT1 - UTC, kept within 0.9 s by leap seconds. Overrides the EOP table for this epoch
+        self.delta_ut1 = Some(delta_ut1);
+    }
+
+    pub fn get_delta_ut1(&self) -> Seconds {
+        return match self.delta_ut1 {
+            Some(delta_ut1) => delta_ut1,
+            None => get_eop_at_mjd(self.get_utc_mjd()).delta_ut1,
+        };
+    }
+
+    pub fn get_utc_mjd(&self) -> f64 {
+        // UTC as a modified Julian date, as the IERS tabulates against
+        let (days, seconds) = self.get_scale_days(TimeScale::UTC);
+        return utc_mjd(days, seconds);
+    }
+
+    pub fn add_seconds(&self, seconds: Seconds) -> Epoch {
+        // SI seconds later (or earlier if negative), so leap seconds are counted
+        return Epoch::new_from_tai(self.tai_days, self.tai_seconds + seconds, self.delta_ut1);
+    }
+
+    pub fn seconds_since(&self, other: &Epoch) -> Seconds {
+        // Elapsed SI seconds from other to self
+        return (self.tai_days - other.tai_days) as f64 * SECONDS_PER_DAY
+            + (self.tai_seconds - other.tai_seconds);
+    }
+
+    pub fn get_seconds_since_j2000(&self, scale: TimeScale) -> Seconds {
+        let (days, seconds) = self.get_scale_days(scale);
+        return days as f64 * SECONDS_PER_DAY + seconds;
+    }
+
+    pub fn get_days_since_j2000(&self, scale: TimeScale) -> (f64, f64) {
+        // Whole days and the fraction of a day since 2000-01-01 12:00:00 in the scale, as two parts
+        // so that adding them to 2451545 gives a Julian date without losing precision
+        let (days, seconds) = self.get_scale_days(scale);
+        return (days as f64, seconds / SECONDS_PER_DAY);
+    }
+
+    pub fn get_datetime(&self, scale: TimeScale) -> NaiveDateTime {
+        // The calendar date and time in the scale, rounded to the nanosecond. UTC during a leap
+        // second comes back as 23:59:59 with a nanosecond past 1e9, which chrono displays as :60
+        if scale == TimeScale::UTC {
+            if let Some(leap) = self.get_leap_second_datetime() {
+                return leap;
+            }
+        }
+        let (days, seconds) = self.get_scale_days(scale);
+        return datetime_from_days(days, seconds);
+    }
+
+    pub fn get_gps_week(&self) -> (u32, Seconds) {
+        // GPS week number and seconds into the week, for epochs after 1980-01-06
+        let (days, seconds) = self.get_scale_days(TimeScale::GPS);
+        // Counting from the noon before midnight at the start of the first week
+        let seconds = seconds - SECONDS_PER_DAY / 2.;
+        let days_since_epoch = days - gps_epoch_days() + (seconds / SECONDS_PER_DAY).floor() as i64;
+        let seconds = seconds.rem_euclid(SECONDS_PER_DAY);
+        let week = days_since_epoch.div_euclid(7);
+        let seconds = days_since_epoch.rem_euclid(7) as f64 * SECONDS_PER_DAY + seconds;
+        return (week as u32, seconds);
+    }
+
+    fn new_from_tai(days: i64, seconds: Seconds, delta_ut1: Option<Seconds>) -> Epoch {
+        let carry = (seconds / SECONDS_PER_DAY).floor();
+        return Epoch {
+            tai_days: days + carry as i64,
+            tai_seconds: seconds - carry * SECONDS_PER_DAY,
+            delta_ut1,
+        };
+    }
+
+    fn new_from_datetime_and_delta_ut1(
+        datetime: &NaiveDateTime,
+        scale: TimeScale,
+        delta_ut1: Option<Seconds>,
+    ) -> Epoch {
+        let days = (datetime.date() - j2000_date()).num_days();
+        let time = datetime.time();
+        // Nanoseconds past 1e9 are a leap second and carry on past 59 s
+        let seconds = time.num_seconds_from_midnight() as f64 + time.nanosecond() as f64 * 1e-9
+            - SECONDS_PER_DAY / 2.;
+
+        if scale == TimeScale::UTC {
+            // The offset for the calendar day holds right through a leap second at its end
+            let offset = tai_minus_utc(51544. + days as f64);
+            return Epoch::new_from_tai(days, seconds + offset, delta_ut1);
+        }
+        return Epoch::new_from_scale_days(days, seconds, scale, delta_ut1);
+    }
+
+    fn new_from_scale_days(
+        days: i64,
+        seconds: Seconds,
+        scale: TimeScale,
+        delta_ut1: Option<Seconds>,
+    ) -> Epoch {
+        let offset = match scale {
+            TimeScale::TAI => 0.,
+            TimeScale::TT => -TT_MINUS_TAI,
+            TimeScale::GPS => TAI_MINUS_GPS,
+            TimeScale::UTC => tai_minus_utc(utc_mjd(days, seconds)),
+            TimeScale::UT1 => {
+                // UT1 - UTC barely changes over a second, so the table can be read at UT1
+                let delta_ut1 =
+                    delta_ut1.unwrap_or_else(|| get_eop_at_mjd(utc_mjd(days, seconds)).delta_ut1);
+                let utc_seconds = seconds - delta_ut1;
+                tai_minus_utc(utc_mjd(days, utc_seconds)) - delta_ut1
+            }
+            TimeScale::TDB => {
+                // TDB - TT depends on TT, which is only a couple of milliseconds away
+                let mut tdb_minus_tt = 0.;
+                for _ in 0..TDB_ITERATIONS {
+                    tdb_minus_tt = tdb_minus_tt_at(days, seconds - tdb_minus_tt);
+                }
+                -TT_MINUS_TAI - tdb_minus_tt
+            }
+        };
+        return Epoch::new_from_tai(days, seconds + offset, delta_ut1);
+    }
+
+    fn get_scale_days(&self, scale: TimeScale) -> (i64, Seconds) {
+        // Whole days and seconds into the day since 2000-01-01 12:00:00 in the given scale
+        let offset = match scale {
+            TimeScale::TAI => 0.,
+            TimeScale::TT => TT_MINUS_TAI,
+            TimeScale::GPS => -TAI_MINUS_GPS,
+            TimeScale::UTC => -self.get_tai_minus_utc(),
+            TimeScale::UT1 => self.get_delta_ut1() - self.get_tai_minus_utc(),
+            TimeScale::TDB => {
+                TT_MINUS_TAI + tdb_minus_tt_at(self.tai_days, self.tai_seconds + TT_MINUS_TAI)
+            }
+        };
+        let seconds = self.tai_seconds + offset;
+        let carry = (seconds / SECONDS_PER_DAY).floor();
+        return (
+            self.tai_days + carry as i64,
+            seconds - carry * SECONDS_PER_DAY,
+        );
+    }
+
+    fn get_tai_minus_utc(&self) -> Seconds {
+        return tai_minus_utc_at_tai(self.get_seconds_since_j2000(TimeScale::TAI));
+    }
+
+    fn get_leap_second_datetime(&self) -> Option<NaiveDateTime> {
+        // Inside a positive leap second TAI - UTC hasn't stepped yet but UTC has reached midnight
+        let before = self.get_tai_minus_utc();
+        let utc_seconds = self.tai_seconds - before;
+        let mjd = utc_mjd(self.tai_days, utc_seconds);
+        let after = tai_minus_utc(mjd);
+        // Seconds since UTC midnight, worked out from the seconds rather than the MJD to keep precision
+        let into_day = (utc_seconds + SECONDS_PER_DAY / 2.).rem_euclid(SECONDS_PER_DAY);
+        if after > before && into_day < after - before {
+            let previous = datetime_from_days(self.tai_days, utc_seconds - into_day - 1.);
+            let nanoseconds = ((into_day + 1.) * 1e9).round() as u32;
+            let time = NaiveTime::from_hms_nano_opt(23, 59, 59, nanoseconds.min(1_999_999_999))?;
+            return Some(NaiveDateTime::new(previous.date(), time));
+        }
+        return None;
+    }
+}
+
+impl From<&NaiveDateTime> for Epoch {
+    // Bare datetimes are taken to be UTC, as they always were in this crate
+    fn from(datetime: &NaiveDateTime) -> Epoch {
+        return Epoch::new_from_utc(datetime);
+    }
+}
+
+impl From<NaiveDateTime> for Epoch {
+    fn from(datetime: NaiveDateTime) -> Epoch {
+        return Epoch::new_from_utc(&datetime);
+    }
+}
+
+impl From<&Epoch> for Epoch {
+    fn from(epoch: &Epoch) -> Epoch {
+        return *epoch;
+    }
+}
+
+fn j2000_date() -> NaiveDate {
+    return NaiveDate::from_ymd_opt(2000, 1, 1).unwrap();
+}
+
+fn gps_epoch_days() -> i64 {
+    // The noon before 1980-01-06 00:00:00, as days since 2000-01-01 12:00:00
+    return (NaiveDate::from_ymd_opt(1980, 1, 6).unwrap() - j2000_date()).num_days() - 1;
+}
+
+fn utc_mjd(days: i64, seconds: Seconds) -> f64 {
+    // Modified Julian date of a UTC instant given as days and seconds since 2000-01-01 12:00:00
+    return 51544.5 + days as f64 + seconds / SECONDS_PER_DAY;
+}
+
+fn tdb_minus_tt_at(days: i64, tt_seconds: Seconds) -> Seconds {
+    // The leading periodic terms of TDB - TT from the Earth's orbit (Explanatory Supplement),
+    // good to about 10 microseconds, which is well below anything a TLE or EOP file resolves
+    let tt_days = days as f64 + tt_seconds / SECONDS_PER_DAY;
+    let g = (357.53_f64 + 0.98560028 * tt_days).to_radians();
+    return 0.001657 * g.sin() + 0.000014 * (2. * g).sin();
+}
+
+fn datetime_from_days(days: i64, seconds: Seconds) -> NaiveDateTime {
+    let noon = NaiveDateTime::new(j2000_date(), NaiveTime::from_hms_opt(12, 0, 0).unwrap());
+    let whole = seconds.floor();
+    let nanoseconds = ((seconds - whole) * 1e9).round() as i64;
+    return noon
+        + Duration::days(days)
+        + Duration::seconds(whole as i64)
+        + Duration::nanoseconds(nanoseconds);
+}
@@ -0,0 +1,155 @@
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+
+use crate::types::Seconds;
+
+use super::epoch::{Epoch, TimeScale, SECONDS_PER_DAY};
+
+// Julian dates of 2000-01-01 12:00:00 and of the MJD origin, 1858-11-17 00:00:00
+pub const J2000: f64 = 2451545.;
+pub const MJD_OFFSET: f64 = 2400000.5;
+pub const DAYS_PER_JULIAN_CENTURY: f64 = 36525.;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JulianDate {
+    // Held in two parts as a single f64 Julian date only resolves about 40 microseconds
+    // day is the Julian date of the preceding midnight (so always ends in .5) and fraction is
+    // the part of the day since then, in [0, 1). The time scale is whatever the caller used
+    day: f64,
+    fraction: f64,
+}
+
+impl JulianDate {
+    pub fn new(day: f64, fraction: f64) -> JulianDate {
+        // Any split of a Julian date into two parts, e.g. 2451545.0 and 0.25 or 2451545.25 and 0
+        let midnight = (day - 0.5).floor() + 0.5;
+        let fraction = fraction + (day - midnight);
+        let carry = fraction.floor();
+        return JulianDate {
+            day: midnight + carry,
+            fraction: fraction - carry,
+        };
+    }
+
+    pub fn new_from_calendar(
+        year: i32,
+        month: u32,
+        day: u32,
+        hour: u32,
+        minute: u32,
+        second: Seconds,
+    ) -> JulianDate {
+        // A date in the proleptic Gregorian calendar, days past the end of the month carry on into
+        // the next (Fliegel and Van Flandern, exact in integers)
+        let a = (14 - month as i64) / 12;
+        let y = year as i64 + 4800 - a;
+        let m = month as i64 + 12 * a - 3;
+        let day_number = day as i64 + (153 * m + 2) / 5 + 365 * y + y.div_euclid(4)
+            - y.div_euclid(100)
+            + y.div_euclid(400)
+            - 32045;
+
+        let seconds = (hour * 3600 + minute * 60) as f64 + second;
+        return JulianDate::new(day_number as f64 - 0.5, seconds / SECONDS_PER_DAY);
+    }
+
+    pub fn new_from_datetime(datetime: &NaiveDateTime) -> JulianDate {
+        // Keeps the nanoseconds. A UTC leap second runs on into the next day, as UTC Julian dates
+        // can't represent it
+        let time = datetime.time();
+        let second = time.second() as f64 + time.nanosecond() as f64 * 1e-9;
+        return JulianDate::new_from_calendar(
+            datetime.year(),
+            datetime.month(),
+            datetime.day(),
+            time.hour(),
+            time.minute(),
+            second,
+        );
+    }
+
+    pub fn new_from_epoch(epoch: &Epoch, scale: TimeScale) -> JulianDate {
+        // Sidereal time wants UT1 and the precession and nutation series want TT
+        let (days, fraction) = epoch.get_days_since_j2000(scale);
+        return JulianDate::new(J2000 + days, fraction);
+    }
+
+    pub fn new_from_mjd(mjd: f64) -> JulianDate {
+        return JulianDate::new(MJD_OFFSET, mjd);
+    }
+
+    pub fn new_from_j2000_seconds(seconds: Seconds) -> JulianDate {
+        // Seconds since 2000-01-01 12:00:00, counting every day as 86400 s
+        let days = (seconds / SECONDS_PER_DAY).floor();
+        let fraction = (seconds - days * SECONDS_PER_DAY) / SECONDS_PER_DAY;
+        return JulianDate::new(J2000 + days, fraction);
+    }
+
+    pub fn get_day(&self) -> f64 {
+        return self.day;
+    }
+
+    pub fn get_fraction(&self) -> f64 {
+        return self.fraction;
+    }
+
+    pub fn get_julian(&self) -> f64 {
+        // As a single number, for anything that doesn't need better than about 40 microseconds
+        return self.day + self.fraction;
+    }
+
+    pub fn get_mjd(&self) -> f64 {
+        return (self.day - MJD_OFFSET) + self.fraction;
+    }
+
+    pub fn get_days_since_j2000(&self) -> f64 {
+        return (self.day - J2000) + self.fraction;
+    }
+
+    pub fn get_j2000_seconds(&self) -> Seconds {
+        return (self.day - J2000) * SECONDS_PER_DAY + self.fraction * SECONDS_PER_DAY;
+    }
+
+    pub fn get_julian_centuries(&self) -> f64 {
+        // Julian centuries since J2000, the argument of most of the precession and nutation series
+        return self.get_days_since_j2000() / DAYS_PER_JULIAN_CENTURY;
+    }
+
+    pub fn get_datetime(&self) -> NaiveDateTime {
+        // Back to the proleptic Gregorian calendar, rounded to the nanosecond
+        let mut day_number = (self.day + 0.5) as i64;
+        let mut nanoseconds = (self.fraction * SECONDS_PER_DAY * 1e9).round() as i64;
+        if nanoseconds >= 86_400_000_000_000 {
+            day_number += 1;
+            nanoseconds -= 86_400_000_000_000;
+        }
+
+        // The inverse of new_from_calendar (Richards)
+        let a = day_number + 32044;
+        let b = (4 * a + 3).div_euclid(146097);
+        let c = a - (146097 * b).div_euclid(4);
+        let d = (4 * c + 3).div_euclid(1461);
+        let e = c - (1461 * d).div_euclid(4);
+        let m = (5 * e + 2).div_euclid(153);
+        let day = e - (153 * m + 2).div_euclid(5) + 1;
+        let month = m + 3 - 12 * (m / 10);
+        let year = 100 * b + d - 4800 + m / 10;
+
+        let date = NaiveDate::from_ymd_opt(year as i32, month as u32, day as u32).unwrap();
+        let time = NaiveTime::from_num_seconds_from_midnight_opt(
+            (nanoseconds / 1_000_000_000) as u32,
+            (nanoseconds % 1_000_000_000) as u32,
+        )
+        .unwrap();
+        return NaiveDateTime::new(date, time);
+    }
+
+    pub fn add_days(&self, days: f64) -> JulianDate {
+        return JulianDate::new(self.day, self.fraction + days);
+    }
+}
+
+impl From<&NaiveDateTime> for JulianDate {
+    fn from(datetime: &NaiveDateTime) -> JulianDate {
+        return JulianDate::new_from_datetime(datetime);
+    }
+}
@@ -0,0 +1,163 @@
+use std::{fmt, fs, path::Path, sync::RwLock};
+
+use crate::types::Seconds;
+
+// TAI - UTC from the start of each UTC day (as an MJD) on which it changed, from IERS Bulletin C
+// UTC before 1972 had rubber seconds and isn't supported, the 1972 value is used instead
+const EMBEDDED_LEAP_SECONDS: [(f64, Seconds); 28] = [
+    (41317., 10.),
+    (41499., 11.),
+    (41683., 12.),
+    (42048., 13.),
+    (42413., 14.),
+    (42778., 15.),
+    (43144., 16.),
+    (43509., 17.),
+    (43874., 18.),
+    (44239., 19.),
+    (44786., 20.),
+    (45151., 21.),
+    (45516., 22.),
+    (46247., 23.),
+    (47161., 24.),
+    (47892., 25.),
+    (48257., 26.),
+    (48804., 27.),
+    (49169., 28.),
+    (49534., 29.),
+    (50083., 30.),
+    (50630., 31.),
+    (51179., 32.),
+    (53736., 33.),
+    (54832., 34.),
+    (56109., 35.),
+    (57204., 36.),
+    (57754., 37.),
+];
+
+// Shared by every Epoch so that loading a newer file once updates all conversions
+static LEAP_SECONDS: RwLock<Vec<(f64, Seconds)>> = RwLock::new(Vec::new());
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LeapSecondError {
+    Io(String),
+    // Line number (from 1) and contents of a line that isn't "MJD day month year TAI-UTC"
+    InvalidLine(usize, String),
+    NotIncreasing(usize),
+    Empty,
+}
+
+impl fmt::Display for LeapSecondError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LeapSecondError::Io(err) => write!(f, "couldn't read leap second file: {}", err),
+            LeapSecondError::InvalidLine(n, line) => {
+                write!(f, "invalid leap second entry on line {}: {}", n, line)
+            }
+            LeapSecondError::NotIncreasing(n) => {
+                write!(f, "leap second dates out of order on line {}", n)
+            }
+            LeapSecondError::Empty => write!(f, "no leap seconds found"),
+        }
+    }
+}
+
+impl std::error::Error for LeapSecondError {}
+
+pub fn tai_minus_utc(utc_mjd: f64) -> Seconds {
+    // TAI - UTC in force on a UTC date given as a modified Julian date
+    let table = LEAP_SECONDS.read().unwrap();
+    let table: &[(f64, Seconds)] = if table.is_empty() {
+        &EMBEDDED_LEAP_SECONDS
+    } else {
+        &table
+    };
+
+    let i = table.partition_point(|(mjd, _)| *mjd <= utc_mjd);
+    return table[i.saturating_sub(1)].1;
+}
+
+pub fn tai_minus_utc_at_tai(tai_seconds_since_j2000: Seconds) -> Seconds {
+    // As tai_minus_utc for an instant given as TAI seconds since 2000-01-01 12:00:00 TAI. Each
+    // change happens when UTC reaches the start of its day, which in TAI is the new offset later
+    // Working in whole seconds keeps the comparison exact, during a leap second the old offset applies
+    let table = LEAP_SECONDS.read().unwrap();
+    let table: &[(f64, Seconds)] = if table.is_empty() {
+        &EMBEDDED_LEAP_SECONDS
+    } else {
+        &table
+    };
+
+    let i = table.partition_point(|(mjd, offset)| {
+        (mjd - 51544.5) * 86400. + offset <= tai_seconds_since_j2000
+    });
+    return table[i.saturating_sub(1)].1;
+}
+
+pub fn get_leap_seconds() -> Vec<(f64, Seconds)> {
+    // The (MJD, TAI - UTC) table currently in use
+    let table = LEAP_SECONDS.read().unwrap();
+    if table.is_empty() {
+        return EMBEDDED_LEAP_SECONDS.to_vec();
+    }
+    return table.clone();
+}
+
+pub fn set_leap_seconds(table: &[(f64, Seconds)]) -> Result<(), LeapSecondError> {
+    // Replaces the table for the whole program. Dates must be increasing
+    if table.is_empty() {
+        return Err(LeapSecondError::Empty);
+    }
+    for (i, pair) in table.windows(2).enumerate() {
+        if pair[1].0 <= pair[0].0 {
+            return Err(LeapSecondError::NotIncreasing(i + 2));
+        }
+    }
+    *LEAP_SECONDS.write().unwrap() = table.to_vec();
+    return Ok(());
+}
+
+pub fn reset_leap_seconds() {
+    // Go back to the table built into the library
+    LEAP_SECONDS.write().unwrap().clear();
+}
+
+pub fn parse_leap_second_file(contents: &str) -> Result<Vec<(f64, Seconds)>, LeapSecondError> {
+    // The IERS Leap_Second.dat format, '#' comments then lines of "MJD day month year TAI-UTC"
+    let mut table = Vec::new();
+
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let invalid = || LeapSecondError::InvalidLine(i + 1, line.to_string());
+        if fields.len() != 5 {
+            return Err(invalid());
+        }
+
+        let mjd: f64 = fields[0].parse().map_err(|_| invalid())?;
+        let offset: Seconds = fields[4].parse().map_err(|_| invalid())?;
+        for field in &fields[1..4] {
+            field.parse::<u32>().map_err(|_| invalid())?;
+        }
+        if table.last().is_some_and(|(last, _)| mjd <= *last) {
+            return Err(LeapSecondError::NotIncreasing(i + 1));
+        }
+        table.push((mjd, offset));
+    }
+
+    if table.is_empty() {
+        return Err(LeapSecondError::Empty);
+    }
+    return Ok(table);
+}
+
+pub fn load_leap_second_file<P: AsRef<Path>>(path: P) -> Result<usize, LeapSecondError> {
+    // Reads a local copy of the IERS file and uses it from now on, returning the number of entries
+    let contents = fs::read_to_string(path).map_err(|err| LeapSecondError::Io(err.to_string()))?;
+    let table = parse_leap_second_file(&contents)?;
+    set_leap_seconds(&table)?;
+    return Ok(table.len());
+}
@@ -0,0 +1,5 @@
+pub mod eop;
+pub mod epoch;
+pub mod julian_date;
+pub mod leap_seconds;
+pub mod sidereal;
@@ -0,0 +1,76 @@
+// Sidereal time and the Earth rotation angle, in radians within [0, 2pi). The Earth turns with
+// UT1, so each takes a UT1 date; the IAU 2006 forms also take TT for their precession terms
+use std::f64::consts::TAU;
+
+use crate::{
+    constants::ARCSEC_TO_RAD,
+    reference_frames::{
+        cio::{cio_locator, get_bias_precession_nutation_matrix},
+        fk5::Nutation,
+    },
+    types::Radians,
+};
+
+use super::julian_date::{JulianDate, J2000};
+
+fn turns_since_j2000(ut1: &JulianDate) -> f64 {
+    // Where one turn a day since J2000 leaves off, from the two parts of the date so that the
+    // fraction isn't lost against the day number
+    return (ut1.get_day() - J2000).rem_euclid(1.) + ut1.get_fraction();
+}
+
+pub fn earth_rotation_angle(ut1: &JulianDate) -> Radians {
+    // The angle from the CIO to the TIO (IERS Conventions 2010, equation 5.15)
+    let days = ut1.get_days_since_j2000();
+    let turns = turns_since_j2000(ut1) + 0.779057273264 + 0.00273781191135448 * days;
+    return (turns * TAU).rem_euclid(TAU);
+}
+
+pub fn gmst_iau82(ut1: &JulianDate) -> Radians {
+    // Greenwich mean sidereal time, the IAU 1982 model in UT1 alone
+    let t = ut1.get_julian_centuries();
+
+    // Seconds of time. The 876600 h per century term is the one turn a day
+    let seconds = 67310.54841 + (8640184.812866 + (0.093104 - 6.2e-6 * t) * t) * t;
+    let turns = seconds / 86400. + turns_since_j2000(ut1);
+    return (turns * TAU).rem_euclid(TAU);
+}
+
+pub fn gmst_iau2006(ut1: &JulianDate, tt: &JulianDate) -> Radians {
+    // Greenwich mean sidereal time consistent with the IAU 2006 precession, the Earth rotation
+    // angle plus the accumulated precession in right ascension
+    let t = tt.get_julian_centuries();
+    let arcseconds = 0.014506
+        + (4612.156534
+            + (1.3915817 + (-0.00000044 + (-0.000029956 - 0.0000000368 * t) * t) * t) * t)
+            * t;
+    return (earth_rotation_angle(ut1) + arcseconds * ARCSEC_TO_RAD).rem_euclid(TAU);
+}
+
+pub fn gast_iau82(ut1: &JulianDate, nutation: &Nutation) -> Radians {
+    // Greenwich apparent sidereal time, GMST plus the equation of the equinoxes from the IAU 1980
+    // nutation. Vallado's FK5 reduction uses this between TOD and PEF
+    let gast = gmst_iau82(ut1) + nutation.get_equation_of_equinoxes();
+    return gast.rem_euclid(TAU);
+}
+
+pub fn gast_iau2006(ut1: &JulianDate, tt: &JulianDate) -> Radians {
+    // Greenwich apparent sidereal time, the Earth rotation angle less the equation of the origins
+    // (the CIO's right ascension from the true equinox) with the IAU 2006/2000B nutation
+    let matrix = get_bias_precession_nutation_matrix(tt);
+    let (x, y) = (matrix.get(2, 0), matrix.get(2, 1));
+    let s = cio_locator(tt, x, y);
+
+    // The CIO in the true equator and equinox of date
+    let a = x / (1. + matrix.get(2, 2));
+    let cio = (1. - a * x, -a * y, -x);
+    let p = matrix.get(0, 0) * cio.0 + matrix.get(0, 1) * cio.1 + matrix.get(0, 2) * cio.2;
+    let q = matrix.get(1, 0) * cio.0 + matrix.get(1, 1) * cio.1 + matrix.get(1, 2) * cio.2;
+    let equation_of_origins = if p != 0. || q != 0. {
+        s - q.atan2(p)
+    } else {
+        s
+    };
+
+    return (earth_rotation_angle(ut1) - equation_of_origins).rem_euclid(TAU);
+}
@@ -0,0 +1,377 @@
+use std::{fmt, fs, path::Path};
+
+use chrono::{Duration, NaiveDate};
+
+use crate::{constants::DEG_TO_RAD, time::epoch::Epoch, types::Radians};
+
+// Every element line is exactly this long, the last column being the checksum
+const LINE_LENGTH: usize = 69;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TLEError {
+    Io(String),
+    // The line number (from 1 in the file or string) comes first in each of the others
+    InvalidLength(usize, usize),
+    // A line that isn't where it should be, e.g. a line 2 without a line 1 before it
+    UnexpectedLine(usize, String),
+    // The file ended after a name or line 1
+    MissingLine(usize),
+    // Expected from the other columns, then the one in column 69
+    InvalidChecksum(usize, u8, u8),
+    InvalidField(usize, &'static str, String),
+    // Line 2 is for a different satellite to line 1
+    CatalogueNumberMismatch(usize, u32, u32),
+}
+
+impl fmt::Display for TLEError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TLEError::Io(err) => write!(f, "couldn't read TLE file: {}", err),
+            TLEError::InvalidLength(n, len) => {
+                write!(f, "line {} is {} characters, not {}", n, len, LINE_LENGTH)
+            }
+            TLEError::UnexpectedLine(n, line) => write!(f, "unexpected line {}: {}", n, line),
+            TLEError::MissingLine(n) => write!(f, "element set from line {} is incomplete", n),
+            TLEError::InvalidChecksum(n, expected, found) => write!(
+                f,
+                "checksum on line {} is {}, should be {}",
+                n, found, expected
+            ),
+            TLEError::InvalidField(n, field, text) => {
+                write!(f, "invalid {} on line {}: {:?}", field, n, text)
+            }
+            TLEError::CatalogueNumberMismatch(n, first, second) => write!(
+                f,
+                "catalogue number {} on line {} doesn't match {} on the line before",
+                second, n, first
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TLEError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TLE {
+    // A NORAD two-line element set, the mean elements SGP4 propagates in the TEME frame
+    // Angles are in radians, the mean motion and its derivatives in revolutions per day
+    pub name: Option<String>,
+    pub catalogue_number: u32,
+    pub classification: char,
+    // Launch year, launch number and piece, e.g. 98067A. Blank for some objects
+    pub international_designator: String,
+    pub epoch: Epoch,
+    // Half the first and a sixth of the second derivative of the mean motion, as given
+    pub mean_motion_dot: f64,
+    pub mean_motion_ddot: f64,
+    // Drag term, per Earth radius
+    pub bstar: f64,
+    pub ephemeris_type: u8,
+    pub element_set_number: u32,
+    pub inclination: Radians,
+    pub raan: Radians,
+    pub eccentricity: f64,
+    pub argument_of_perigee: Radians,
+    pub mean_anomaly: Radians,
+    pub mean_motion: f64,
+    pub revolution_number: u32,
+}
+
+impl TLE {
+    pub fn new_from_lines(line1: &str, line2: &str) -> Result<TLE, TLEError> {
+        return parse_element_lines(None, (1, line1), (2, line2));
+    }
+
+    pub fn new_from_str(contents: &str) -> Result<TLE, TLEError> {
+        // A single two or three line set, the name line (optionally starting "0 ") first
+        let mut sets = parse_tle_catalogue(contents);
+        if sets.is_empty() {
+            return Err(TLEError::MissingLine(1));
+        }
+        return sets.remove(0);
+    }
+
+    pub fn get_alpha5(&self) -> Option<String> {
+        // The catalogue number as written in the element lines, with a letter above 99999
+        return format_alpha5(self.catalogue_number);
+    }
+}
+
+pub fn parse_tle_catalogue(contents: &str) -> Vec<Result<TLE, TLEError>> {
+    // Every element set in a file of two or three line sets, each either read or with the reason it
+    // couldn't be, so that one bad entry doesn't lose the rest of a catalogue
+    let mut sets = Vec::new();
+    let mut lines = contents
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim_end()))
+        .filter(|(_, line)| !line.is_empty())
+        .peekable();
+    let mut name: Option<(usize, String)> = None;
+
+    while let Some((n, line)) = lines.next() {
+        if line.starts_with("1 ") {
+            let Some(&(n2, line2)) = lines.peek() else {
+                sets.push(Err(TLEError::MissingLine(n)));
+                break;
+            };
+            if !line2.starts_with("2 ") {
+                sets.push(Err(TLEError::UnexpectedLine(n2, line2.to_string())));
+                name = None;
+                continue;
+            }
+            lines.next();
+            let set_name = name.take().map(|(_, name)| name);
+            sets.push(parse_element_lines(set_name, (n, line), (n2, line2)));
+        } else if line.starts_with("2 ") {
+            sets.push(Err(TLEError::UnexpectedLine(n, line.to_string())));
+            name = None;
+        } else {
+            // A name line, which must be followed by line 1
+            if let Some((previous, _)) = name {
+                sets.push(Err(TLEError::MissingLine(previous)));
+            }
+            let text = line.strip_prefix("0 ").unwrap_or(line).trim();
+            name = Some((n, text.to_string()));
+        }
+    }
+    if let Some((n, _)) = name {
+        sets.push(Err(TLEError::MissingLine(n)));
+    }
+
+    return sets;
+}
+
+pub fn load_tle_catalogue<P: AsRef<Path>>(path: P) -> Result<Vec<Result<TLE, TLEError>>, TLEError> {
+    // Reads a local catalogue file, e.g. from CelesTrak or Space-Track
+    let contents = fs::read_to_string(path).map_err(|err| TLEError::Io(err.to_string()))?;
+    return Ok(parse_tle_catalogue(&contents));
+}
+
+pub fn checksum(line: &str) -> u8 {
+    // The sum of the digits in the first 68 columns, with each minus sign counting as 1, modulo 10
+    let sum: u32 = line
+        .chars()
+        .take(LINE_LENGTH - 1)
+        .map(|c| match c {
+            '-' => 1,
+            _ => c.to_digit(10).unwrap_or(0),
+        })
+        .sum();
+    return (sum % 10) as u8;
+}
+
+pub fn parse_alpha5(text: &str) -> Option<u32> {
+    // Up to five digits, or a letter standing for 10 to 33 (skipping I and O) then four digits
+    let text = text.trim();
+    let first = text.chars().next()?;
+    if first.is_ascii_digit() {
+        if text.len() > 5 || !text.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        return text.parse().ok();
+    }
+
+    let digits = text.get(1..)?;
+    if digits.len() != 4 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let leading = match first {
+        'A'..='H' => first as u32 - 'A' as u32 + 10,
+        'J'..='N' => first as u32 - 'J' as u32 + 18,
+        'P'..='Z' => first as u32 - 'P' as u32 + 23,
+        _ => return None,
+    };
+    return Some(leading * 10000 + digits.parse::<u32>().ok()?);
+}
+
+pub fn format_alpha5(catalogue_number: u32) -> Option<String> {
+    // The inverse of parse_alpha5. Z9999 is the largest number five columns can hold
+    if catalogue_number < 100000 {
+        return Some(format!("{:05}", catalogue_number));
+    }
+    let leading = catalogue_number / 10000;
+    let letter = match leading {
+        10..=17 => b'A' + (leading - 10) as u8,
+        18..=22 => b'J' + (leading - 18) as u8,
+        23..=33 => b'P' + (leading - 23) as u8,
+        _ => return None,
+    };
+    return Some(format!("{}{:04}", letter as char, catalogue_number % 10000));
+}
+
+fn parse_element_lines(
+    name: Option<String>,
+    (n1, line1): (usize, &str),
+    (n2, line2): (usize, &str),
+) -> Result<TLE, TLEError> {
+    let line1 = ElementLine::new(n1, line1, '1')?;
+    let line2 = ElementLine::new(n2, line2, '2')?;
+
+    let catalogue_number = line1.alpha5(3, 7)?;
+    let second_number = line2.alpha5(3, 7)?;
+    if catalogue_number != second_number {
+        return Err(TLEError::CatalogueNumberMismatch(
+            n2,
+            catalogue_number,
+            second_number,
+        ));
+    }
+
+    let eccentricity = line2.implied_decimal(27, 33, "eccentricity")?;
+    let angle = |start: usize, end: usize, field: &'static str, max: f64| {
+        let degrees = line2.number(start, end, field)?;
+        if !(0. ..=max).contains(&degrees) {
+            return Err(line2.invalid(start, end, field));
+        }
+        return Ok(degrees * DEG_TO_RAD);
+    };
+    let mean_motion = line2.number(53, 63, "mean motion")?;
+    if mean_motion <= 0. {
+        return Err(line2.invalid(53, 63, "mean motion"));
+    }
+
+    return Ok(TLE {
+        name,
+        catalogue_number,
+        classification: line1.field(8, 8).chars().next().unwrap_or('U'),
+        international_designator: line1.field(10, 17).to_string(),
+        epoch: line1.epoch(19, 32)?,
+        mean_motion_dot: line1.number(34, 43, "mean motion derivative")?,
+        mean_motion_ddot: line1.exponent(45, 52, "mean motion second derivative")?,
+        bstar: line1.exponent(54, 61, "B*")?,
+        ephemeris_type: line1.integer(63, 63, "ephemeris type")? as u8,
+        element_set_number: line1.integer(65, 68, "element set number")?,
+        inclination: angle(9, 16, "inclination", 180.)?,
+        raan: angle(18, 25, "right ascension of the ascending node", 360.)?,
+        eccentricity,
+        argument_of_perigee: angle(35, 42, "argument of perigee", 360.)?,
+        mean_anomaly: angle(44, 51, "mean anomaly", 360.)?,
+        mean_motion,
+        revolution_number: line2.integer(64, 68, "revolution number")?,
+    });
+}
+
+struct ElementLine<'a> {
+    n: usize,
+    line: &'a str,
+}
+
+impl<'a> ElementLine<'a> {
+    fn new(n: usize, line: &'a str, number: char) -> Result<ElementLine<'a>, TLEError> {
+        // Checks the line is an element line of the right number, length and checksum
+        let line = line.trim_end();
+        if !line.starts_with(number) {
+            return Err(TLEError::UnexpectedLine(n, line.to_string()));
+        }
+        if !line.is_ascii() || line.len() != LINE_LENGTH {
+            return Err(TLEError::InvalidLength(n, line.chars().count()));
+        }
+        let found = line.as_bytes()[LINE_LENGTH - 1];
+        if !found.is_ascii_digit() {
+            return Err(TLEError::InvalidField(
+                n,
+                "checksum",
+                (found as char).to_string(),
+            ));
+        }
+        let expected = checksum(line);
+        if expected != found - b'0' {
+            return Err(TLEError::InvalidChecksum(n, expected, found - b'0'));
+        }
+        return Ok(ElementLine { n, line });
+    }
+
+    fn field(&self, start: usize, end: usize) -> &'a str {
+        // Columns from 1, inclusive, as the format is documented
+        return self.line[start - 1..end].trim();
+    }
+
+    fn invalid(&self, start: usize, end: usize, field: &'static str) -> TLEError {
+        return TLEError::InvalidField(self.n, field, self.field(start, end).to_string());
+    }
+
+    fn number(&self, start: usize, end: usize, field: &'static str) -> Result<f64, TLEError> {
+        let text = self.field(start, end);
+        // Rust reads ".5" and "-.5" but not "+.5", which some generators write
+        let text = text.strip_prefix('+').unwrap_or(text);
+        return text.parse().map_err(|_| self.invalid(start, end, field));
+    }
+
+    fn integer(&self, start: usize, end: usize, field: &'static str) -> Result<u32, TLEError> {
+        // Blank counts as 0, as older sets leave the element set and revolution numbers empty
+        let text = self.field(start, end);
+        if text.is_empty() {
+            return Ok(0);
+        }
+        return text.parse().map_err(|_| self.invalid(start, end, field));
+    }
+
+    fn alpha5(&self, start: usize, end: usize) -> Result<u32, TLEError> {
+        return parse_alpha5(self.field(start, end))
+            .ok_or_else(|| self.invalid(start, end, "catalogue number"));
+    }
+
+    fn implied_decimal(
+        &self,
+        start: usize,
+        end: usize,
+        field: &'static str,
+    ) -> Result<f64, TLEError> {
+        // Digits with a decimal point assumed before them, e.g. 0006703 for 0.0006703
+        let text = self.field(start, end);
+        if text.is_empty() || !text.chars().all(|c| c.is_ascii_digit()) {
+            return Err(self.invalid(start, end, field));
+        }
+        return Ok(format!("0.{}", text).parse().unwrap());
+    }
+
+    fn exponent(&self, start: usize, end: usize, field: &'static str) -> Result<f64, TLEError> {
+        // An implied decimal mantissa and a power of ten, e.g. -11606-4 for -0.11606e-4
+        let text = self.field(start, end);
+        let invalid = || self.invalid(start, end, field);
+        if text.len() < 3 {
+            return Err(invalid());
+        }
+        let (mantissa, exponent) = text.split_at(text.len() - 2);
+        let (sign, digits) = match mantissa.strip_prefix('-') {
+            Some(digits) => (-1., digits),
+            None => (1., mantissa.strip_prefix('+').unwrap_or(mantissa)),
+        };
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let mantissa: f64 = format!("0.{}", digits).parse().map_err(|_| invalid())?;
+        let exponent: i32 = exponent.parse().map_err(|_| invalid())?;
+        return Ok(sign * mantissa * 10_f64.powi(exponent));
+    }
+
+    fn epoch(&self, start: usize, end: usize) -> Result<Epoch, TLEError> {
+        // Two digit year (57 to 99 for 1957 to 1999) and the day of the year from 1.0 at midnight
+        // on 1 January, in UTC
+        let invalid = || self.invalid(start, end, "epoch");
+        let year: i32 = self
+            .field(start, start + 1)
+            .parse()
+            .map_err(|_| invalid())?;
+        let year = if year < 57 { 2000 + year } else { 1900 + year };
+        let day: f64 = self.field(start + 2, end).parse().map_err(|_| invalid())?;
+        let days_in_year = if NaiveDate::from_ymd_opt(year, 2, 29).is_some() {
+            366.
+        } else {
+            365.
+        };
+        if !(1. ..days_in_year + 1.).contains(&day) {
+            return Err(invalid());
+        }
+
+        // To the microsecond, well below the 8 decimal places of a day given
+        let microseconds = ((day - 1.) * 86400e6).round() as i64;
+        let datetime = NaiveDate::from_ymd_opt(year, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            + Duration::microseconds(microseconds);
+        return Ok(Epoch::new_from_utc(&datetime));
+    }
+}
@@ -2,7 +2,9 @@ use wasm_bindgen::prelude::*;
 
 pub type Radians = f64;
 pub type Degrees = f64;
+pub type ArcSeconds = f64;
 pub type Metres = f64;
+pub type SquareMetres = f64;
 pub type Kilometres = f64;
 pub type Minutes = f64;
 pub type Seconds = f64;
@@ -0,0 +1,230 @@
+// Small numerical helpers shared by the projection and geodesic code
+// Most of these follow the conventions of GeographicLib's Math class
+use std::f64::consts::PI;
+
+pub fn eatanhe(x: f64, es: f64) -> f64 {
+    // e * atanh(e * x), continued to prolate ellipsoids (es < 0)
+    if es > 0_f64 {
+        return es * (es * x).atanh();
+    }
+    return -es * (es * x).atan();
+}
+
+pub fn taupf(tau: f64, es: f64) -> f64 {
+    // tan(chi) for conformal latitude chi given tan(phi) for geodetic latitude phi
+    let tau1 = tau.hypot(1.);
+    let sig = eatanhe(tau / tau1, es).sinh();
+    return sig.hypot(1.) * tau - sig * tau1;
+}
+
+pub fn tauf(taup: f64, es: f64) -> f64 {
+    // Inverse of taupf, by Newton's method
+    let tol = f64::EPSILON.sqrt() / 10.;
+    let taumax = 2. / f64::EPSILON.sqrt();
+    let e2m = 1. - es * es;
+
+    let mut tau = if taup.abs() > 70. {
+        taup * eatanhe(1., es).exp()
+    } else {
+        taup / e2m
+    };
+    let stol = tol * taup.abs().max(1.);
+
+    if tau.abs() >= taumax || tau.is_nan() {
+        return tau;
+    }
+
+    for _ in 0..5 {
+        let taupa = taupf(tau, es);
+        let dtau =
+            (taup - taupa) * (1. + e2m * tau * tau) / (e2m * tau.hypot(1.) * taupa.hypot(1.));
+        tau += dtau;
+        if dtau.abs() < stol {
+            break;
+        }
+    }
+    return tau;
+}
+
+pub fn ang_normalize(x: f64) -> f64 {
+    // Reduce an angle in radians to [-pi, pi]
+    let y = x % (2. * PI);
+    if y > PI {
+        return y - 2. * PI;
+    }
+    if y < -PI {
+        return y + 2. * PI;
+    }
+    return y;
+}
+
+pub fn polyval(coeffs: &[f64], x: f64) -> f64 {
+    // Horner evaluation, highest order coefficient first
+    let mut y = 0_f64;
+    for c in coeffs.iter() {
+        y = y * x + c;
+    }
+    return y;
+}
+
+pub fn sum(u: f64, v: f64) -> (f64, f64) {
+    // Error free sum of two numbers, returning the rounded sum and the round-off error
+    let s = u + v;
+    let up = s - v;
+    let vpp = s - up;
+    let t = -((up - u) + (vpp - v));
+    return (s, t);
+}
+
+pub fn norm(x: f64, y: f64) -> (f64, f64) {
+    // Scale (x, y) to unit length, used for sine/cosine pairs
+    let r = x.hypot(y);
+    return (x / r, y / r);
+}
+
+pub fn ang_normalize_degrees(x: f64) -> f64 {
+    // Reduce an angle in degrees to (-180, 180], exact for all finite inputs
+    let mut y = x % 360.;
+    if y > 180. {
+        y -= 360.;
+    } else if y < -180. {
+        y += 360.;
+    }
+    if y == -180. {
+        return 180.;
+    }
+    return y;
+}
+
+pub fn ang_diff_degrees(x: f64, y: f64) -> (f64, f64) {
+    // y - x in degrees reduced to [-180, 180], with the round-off error carried separately
+    let (d, t) = sum(ang_normalize_degrees(-x), ang_normalize_degrees(y));
+    let d = ang_normalize_degrees(d);
+    if d == 180. && t > 0. {
+        return sum(-180., t);
+    }
+    return sum(d, t);
+}
+
+pub fn ang_round(x: f64) -> f64 {
+    // Round tiny angles in degrees so that the smallest non-zero gap is 1/2^57 (under a picometre),
+    // avoiding near singular cases when an angle is non-zero but tiny
+    let z = 1. / 16.;
+    let mut y = x.abs();
+    if y < z {
+        y = z - (z - y);
+    }
+    if x == 0. {
+        return 0.;
+    }
+    return y.copysign(x);
+}
+
+pub fn sincosd(x: f64) -> (f64, f64) {
+    // Sine and cosine of an angle in degrees, exact at multiples of 90 degrees
+    let mut r = if x.is_finite() { x % 360. } else { f64::NAN };
+    let q = if r.is_nan() {
+        0
+    } else {
+        (r / 90.).round() as i32
+    };
+    r -= 90. * q as f64;
+    let r = r.to_radians();
+    let (s, c) = (r.sin(), r.cos());
+
+    let (s, c) = match q.rem_euclid(4) {
+        1 => (c, -s),
+        2 => (-s, -c),
+        3 => (-c, s),
+        _ => (s, c),
+    };
+
+    // Keep the sign of sin(-0) but remove it everywhere else
+    if x == 0. {
+        return (x, c + 0.);
+    }
+    return (s + 0., c + 0.);
+}
+
+pub fn atan2d(y: f64, x: f64) -> f64 {
+    // atan2 in degrees, exact at multiples of 45 degrees and returning values in [-180, 180]
+    let (mut x, mut y) = (x, y);
+    let mut q = 0;
+    if y.abs() > x.abs() {
+        std::mem::swap(&mut x, &mut y);
+        q = 2;
+    }
+    if x < 0. {
+        q += 1;
+        x = -x;
+    }
+    let ang = y.atan2(x).to_degrees();
+    return match q {
+        1 => {
+            if y >= 0. {
+                180. - ang
+            } else {
+                -180. - ang
+            }
+        }
+        2 => 90. - ang,
+        3 => -90. + ang,
+        _ => ang,
+    };
+}
+
+pub fn sin_cos_series(sinp: bool, sinx: f64, cosx: f64, c: &[f64]) -> f64 {
+    // Clenshaw summation of sum(c[l] * sin(2 l x)) for l = 1..n when sinp,
+    // or sum(c[l] * cos((2 l + 1) x)) for l = 0..n-1 otherwise
+    let mut k = c.len();
+    let mut n = k - usize::from(sinp);
+    let ar = 2. * (cosx - sinx) * (cosx + sinx);
+    let mut y1 = 0_f64;
+    let mut y0 = if n & 1 != 0 {
+        k -= 1;
+        c[k]
+    } else {
+        0.
+    };
+    n /= 2;
+    while n > 0 {
+        n -= 1;
+        k -= 1;
+        y1 = ar * y0 - y1 + c[k];
+        k -= 1;
+        y0 = ar * y1 - y0 + c[k];
+    }
+    if sinp {
+        return 2. * sinx * cosx * y0;
+    }
+    return cosx * (y0 - y1);
+}
+
+pub fn astroid(x: f64, y: f64) -> f64 {
+    // Positive root k of k^4 + 2 k^3 - (x^2 + y^2 - 1) k^2 - 2 y^2 k - y^2 = 0
+    let p = x * x;
+    let q = y * y;
+    let r = (p + q - 1.) / 6.;
+    if q == 0. && r <= 0. {
+        return 0.;
+    }
+
+    let s = p * q / 4.;
+    let r2 = r * r;
+    let r3 = r * r2;
+    let disc = s * (s + 2. * r3);
+    let mut u = r;
+    if disc >= 0. {
+        let mut t3 = s + r3;
+        t3 += disc.sqrt().copysign(t3);
+        let t = t3.cbrt();
+        u += t + if t != 0. { r2 / t } else { 0. };
+    } else {
+        let ang = (-disc).sqrt().atan2(-(s + r3));
+        u += 2. * r * (ang / 3.).cos();
+    }
+    let v = (u * u + q).sqrt();
+    let uv = if u < 0. { q / (v - u) } else { u + v };
+    let w = (uv - q) / (2. * v);
+    return uv / ((uv + w * w).sqrt() + w);
+}